@@ -3,30 +3,58 @@
 /**
  * PptxGenJS Sidecar
  *
- * 通过 stdin 接收 JSON 请求，生成 PPTX 文件，通过 stdout 返回结果
+ * 常驻进程，通过 stdin/stdout 按行收发 JSON-RPC 2.0 消息生成 PPTX 文件
  */
 
 const PptxGenJS = require('pptxgenjs');
 const fs = require('fs');
 const path = require('path');
+const readline = require('readline');
+const { version } = require('./package.json');
+
+/** 协议版本（与 Rust 端的 PROTOCOL_VERSION 一致） */
+const PROTOCOL_VERSION = 1;
+
+/** 可直接处理的幻灯片内容类型（SVG 由 Rust 端渲染为 PNG 后发送） */
+const CONTENT_TYPES = ['png'];
+
+/** 可写入的切换效果（PptxGenJS 没有幻灯片切换 API，目前为空） */
+const TRANSITIONS = [];
+
+// stdout 专用于协议消息，其他日志一律写入 stderr
+console.log = console.error;
+
+/** 处理中的请求 ID */
+const active = new Set();
+
+/** 收到 cancel 通知、尚未结束的请求 ID */
+const cancelled = new Set();
 
 /**
- * 读取 stdin 的所有数据
+ * 请求已取消时抛出错误
  */
-async function readStdin() {
-  return new Promise((resolve, reject) => {
-    const chunks = [];
-    process.stdin.on('data', chunk => chunks.push(chunk));
-    process.stdin.on('end', () => resolve(Buffer.concat(chunks).toString('utf8')));
-    process.stdin.on('error', reject);
-  });
+function checkCancelled(id) {
+  if (cancelled.has(id)) {
+    const error = new Error('请求已取消');
+    error.code = -32800;
+    throw error;
+  }
+}
+
+/**
+ * 发送一条 JSON-RPC 消息
+ */
+function send(message) {
+  process.stdout.write(JSON.stringify({ jsonrpc: '2.0', ...message }) + '\n');
 }
 
 /**
  * 生成 PPTX 文件
+ *
+ * 每处理完一页通过 onProgress 上报进度，并通过 checkCancelled 检查是否已取消
  */
-async function generatePptx(request) {
-  const { slides, output, config } = request;
+async function generatePptx(request, onProgress, checkCancelled) {
+  const { slides, output, config, sections = [] } = request;
 
   // 创建 PPTX 实例
   const pptx = new PptxGenJS();
@@ -38,15 +66,25 @@ async function generatePptx(request) {
   pptx.defineLayout({ name: 'CUSTOM', width: widthInches, height: heightInches });
   pptx.layout = 'CUSTOM';
 
+  // 创建分节，并记录每页所属的节
+  const sectionOf = new Map();
+  for (const section of sections) {
+    pptx.addSection({ title: section.name });
+    for (const number of section.slides) {
+      sectionOf.set(number, section.name);
+    }
+  }
+
   // 处理每个幻灯片
-  for (const slideData of slides) {
-    const slide = pptx.addSlide();
+  for (const [index, slideData] of slides.entries()) {
+    const sectionTitle = sectionOf.get(slideData.number);
+    const slide = sectionTitle ? pptx.addSlide({ sectionTitle }) : pptx.addSlide();
 
     // 添加内容
     if (slideData.content.type === 'svg') {
       // SVG 内容
-      // 注意：PptxGenJS 不直接支持 SVG，需要转换为图片或形状
-      // 这里我们将 SVG 作为文本添加（临时方案）
+      // 注意：PptxGenJS 不直接支持 SVG，capabilities 未声明 svg，Rust 端会先渲染为 PNG；
+      // 这里仅为不做协商的调用方保留占位符文本
       slide.addText('SVG Content (需要转换)', {
         x: 0.5,
         y: 0.5,
@@ -72,18 +110,11 @@ async function generatePptx(request) {
       slide.addNotes(slideData.notes);
     }
 
-    // 添加切换效果
-    if (config.enableTransitions && config.transitionType) {
-      const transitionMap = {
-        'fade': 'fade',
-        'push': 'push',
-        'wipe': 'wipe',
-        'split': 'split',
-        'reveal': 'reveal',
-      };
-      const transition = transitionMap[config.transitionType] || 'fade';
-      slide.transition = { type: transition };
-    }
+    onProgress(index + 1, slides.length, `已生成第 ${index + 1}/${slides.length} 页: ${slideData.title}`);
+
+    // 让出事件循环以处理 cancel 通知
+    await new Promise(setImmediate);
+    checkCancelled();
   }
 
   // 确保输出目录存在
@@ -95,34 +126,89 @@ async function generatePptx(request) {
   // 保存 PPTX 文件
   await pptx.writeFile({ fileName: output });
 
+  // 写文件期间被取消时删除输出
+  try {
+    checkCancelled();
+  } catch (error) {
+    fs.rmSync(output, { force: true });
+    throw error;
+  }
+
   return { success: true, output };
 }
 
 /**
- * 主函数
+ * 处理一条请求
  */
-async function main() {
-  try {
-    // 读取请求
-    const input = await readStdin();
-    const request = JSON.parse(input);
+async function handle(id, method, params) {
+  switch (method) {
+    case 'initialize':
+      return { protocolVersion: PROTOCOL_VERSION, name: 'pptxgen-sidecar', version };
+    case 'capabilities':
+      return {
+        nodeVersion: process.versions.node,
+        libraryVersion: new PptxGenJS().version,
+        contentTypes: CONTENT_TYPES,
+        transitions: TRANSITIONS,
+      };
+    case 'export':
+      return generatePptx(
+        params,
+        (current, total, message) => {
+          send({ method: 'progress', params: { requestId: id, current, total, message } });
+        },
+        () => checkCancelled(id),
+      );
+    case 'shutdown':
+      setImmediate(() => process.exit(0));
+      return null;
+    default: {
+      const error = new Error(`未知方法: ${method}`);
+      error.code = -32601;
+      throw error;
+    }
+  }
+}
 
-    // 生成 PPTX
-    const result = await generatePptx(request);
+/**
+ * 主函数：逐行读取请求，stdin 关闭时退出
+ */
+function main() {
+  const rl = readline.createInterface({ input: process.stdin });
+
+  rl.on('line', async (line) => {
+    if (!line.trim()) return;
+
+    let request;
+    try {
+      request = JSON.parse(line);
+    } catch (error) {
+      send({ id: null, error: { code: -32700, message: `无法解析请求: ${error.message}` } });
+      return;
+    }
 
-    // 返回结果
-    console.log(JSON.stringify(result));
-    process.exit(0);
-  } catch (error) {
-    // 返回错误
-    console.log(JSON.stringify({
-      success: false,
-      error: error.message,
-      stack: error.stack,
-    }));
-    process.exit(1);
-  }
+    const { id, method, params = {} } = request;
+
+    // 取消通知（无 id）：其他请求仍在使用本进程时，Rust 端以此放弃单个请求
+    if (method === 'cancel') {
+      if (active.has(params.requestId)) cancelled.add(params.requestId);
+      return;
+    }
+
+    active.add(id);
+    try {
+      const result = await handle(id, method, params);
+      send({ id, result });
+    } catch (error) {
+      if (!error.code) console.error(error.stack);
+      send({ id, error: { code: error.code || -32000, message: error.message } });
+    } finally {
+      active.delete(id);
+      cancelled.delete(id);
+    }
+  });
+
+  rl.on('close', () => process.exit(0));
 }
 
-// 运行主函数
 main();
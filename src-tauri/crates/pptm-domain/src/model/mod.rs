@@ -1,4 +1,4 @@
 /// 领域模型定义。
 pub mod project;
 
-pub use project::{ProjectInfo, ProjectMetadata};
+pub use project::{ProjectInfo, ProjectMetadata, SectionSpec, PROJECT_METADATA_FILE};
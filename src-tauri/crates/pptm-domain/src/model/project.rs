@@ -20,6 +20,18 @@ pub struct ProjectInfo {
     pub has_spec: bool,
 }
 
+/// 项目元数据文件名。
+pub const PROJECT_METADATA_FILE: &str = ".pptm-meta.json";
+
+/// 幻灯片分节声明。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionSpec {
+    /// 节名称。
+    pub name: String,
+    /// 本节包含的幻灯片（`svg_final` 中的文件名，可省略 `.svg` 扩展名）。
+    pub slides: Vec<String>,
+}
+
 /// 项目持久化元数据（用于 `.pptm-meta.json`）。
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ProjectMetadata {
@@ -33,6 +45,9 @@ pub struct ProjectMetadata {
     pub created_at: String,
     /// 更新时间。
     pub updated_at: String,
+    /// 幻灯片分节（为空时按文件名前缀推断）。
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<SectionSpec>,
 }
 
 #[cfg(test)]
@@ -66,6 +81,10 @@ mod tests {
             format: "ppt169".to_string(),
             created_at: "2026-02-10T00:00:00Z".to_string(),
             updated_at: "2026-02-10T12:30:00Z".to_string(),
+            sections: vec![SectionSpec {
+                name: "背景".to_string(),
+                slides: vec!["P01_封面".to_string(), "P02_目录.svg".to_string()],
+            }],
         };
 
         let json = serde_json::to_string(&metadata).expect("序列化 ProjectMetadata 应成功");
@@ -74,4 +93,16 @@ mod tests {
 
         assert_eq!(decoded, metadata);
     }
+
+    #[test]
+    fn test_project_metadata_without_sections() {
+        let json = r#"{"version":"1.0.0","name":"demo","format":"ppt169","created_at":"","updated_at":""}"#;
+        let decoded: ProjectMetadata =
+            serde_json::from_str(json).expect("缺少 sections 时应使用默认值");
+
+        assert!(decoded.sections.is_empty());
+        assert!(!serde_json::to_string(&decoded)
+            .unwrap()
+            .contains("sections"));
+    }
 }
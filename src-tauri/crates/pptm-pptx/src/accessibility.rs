@@ -0,0 +1,122 @@
+// 无障碍检查
+// 导出时列出没有替代文字的图片，便于交付前在 SVG 中补充 <title>/<desc> 或 aria-label
+
+use crate::drawingml::alt_text::extract_alt_text;
+use crate::{Result, Slide, SlideContent};
+use serde::Serialize;
+
+/// 没有替代文字的图片
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingAltText {
+    /// 幻灯片编号
+    pub slide: usize,
+    /// 幻灯片标题（文件名）
+    pub slide_title: String,
+    /// 图片的 ID、文件名或序号
+    pub image: String,
+}
+
+/// 无障碍检查报告
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilityReport {
+    /// 没有替代文字的图片（装饰性图片除外）
+    pub missing_alt_text: Vec<MissingAltText>,
+}
+
+impl AccessibilityReport {
+    /// 是否没有发现问题
+    pub fn is_clean(&self) -> bool {
+        self.missing_alt_text.is_empty()
+    }
+}
+
+/// 检查幻灯片中的图片是否都有替代文字
+///
+/// 与导出模式无关：SVG 中的 `<image>` 自身或祖先元素需要 `<title>`、`<desc>` 或
+/// `aria-label`，或标记为装饰性（`aria-hidden="true"` / `role="presentation"`）
+pub fn audit_slides(slides: &[Slide]) -> Result<AccessibilityReport> {
+    let mut report = AccessibilityReport::default();
+
+    for slide in slides {
+        let SlideContent::Svg(svg) = &slide.content else {
+            continue;
+        };
+        let (_, alt) = extract_alt_text(svg)?;
+        report
+            .missing_alt_text
+            .extend(
+                alt.unlabeled_images
+                    .into_iter()
+                    .map(|image| MissingAltText {
+                        slide: slide.number,
+                        slide_title: slide.title.clone(),
+                        image,
+                    }),
+            );
+    }
+
+    Ok(report)
+}
+
+/// 读屏软件使用的幻灯片标题：去掉文件名的序号前缀（如 `02-01_`），下划线改为空格
+pub fn readable_title(slide: &Slide) -> String {
+    let title = slide
+        .title
+        .trim_start_matches(|c: char| c.is_ascii_digit() || matches!(c, '-' | '_' | '.' | ' '))
+        .replace('_', " ");
+    let title = title.trim();
+
+    if title.is_empty() {
+        format!("幻灯片 {}", slide.number)
+    } else {
+        title.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slide(number: usize, title: &str, content: SlideContent) -> Slide {
+        Slide {
+            number,
+            title: title.to_string(),
+            content,
+            notes: None,
+            transition: None,
+            animation: None,
+            section: None,
+            source_svg: None,
+        }
+    }
+
+    #[test]
+    fn test_audit_slides() {
+        let slides = vec![
+            slide(
+                1,
+                "01_cover",
+                SlideContent::Svg(
+                    r#"<svg xmlns="http://www.w3.org/2000/svg"><title>封面</title><image id="hero" href="hero.png"/><image href="logo.png" aria-label="标志"/></svg>"#.to_string(),
+                ),
+            ),
+            slide(2, "02", SlideContent::Png(Vec::new())),
+        ];
+
+        let report = audit_slides(&slides).expect("检查应成功");
+        assert_eq!(
+            report.missing_alt_text,
+            vec![MissingAltText {
+                slide: 1,
+                slide_title: "01_cover".to_string(),
+                image: "hero".to_string(),
+            }]
+        );
+        assert!(!report.is_clean());
+
+        assert_eq!(readable_title(&slides[0]), "cover");
+        assert_eq!(readable_title(&slides[1]), "幻灯片 2");
+    }
+}
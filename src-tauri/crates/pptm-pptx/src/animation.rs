@@ -0,0 +1,336 @@
+// 切换效果与入场动画
+// 与 Python `tools/pptx_animations.py` 保持一致，生成 p:transition 与 p:timing XML
+
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// 切换效果定义
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionEffect {
+    /// 效果键，例如 `fade`
+    pub key: &'static str,
+    /// 效果名称
+    pub name: &'static str,
+    /// PresentationML 元素名（不含 `p:` 前缀）
+    pub element: &'static str,
+    /// 元素属性
+    pub attrs: &'static [(&'static str, &'static str)],
+}
+
+/// 支持的切换效果（统一数据源）
+pub const TRANSITIONS: &[TransitionEffect] = &[
+    TransitionEffect {
+        key: "fade",
+        name: "淡入淡出",
+        element: "fade",
+        attrs: &[],
+    },
+    TransitionEffect {
+        key: "push",
+        name: "推入",
+        element: "push",
+        attrs: &[("dir", "r")],
+    },
+    TransitionEffect {
+        key: "wipe",
+        name: "擦除",
+        element: "wipe",
+        attrs: &[("dir", "r")],
+    },
+    TransitionEffect {
+        key: "split",
+        name: "分割",
+        element: "split",
+        attrs: &[("orient", "horz"), ("dir", "out")],
+    },
+    TransitionEffect {
+        key: "reveal",
+        name: "揭示",
+        element: "strips",
+        attrs: &[("dir", "rd")],
+    },
+    TransitionEffect {
+        key: "cover",
+        name: "覆盖",
+        element: "cover",
+        attrs: &[("dir", "r")],
+    },
+    TransitionEffect {
+        key: "random",
+        name: "随机",
+        element: "random",
+        attrs: &[],
+    },
+];
+
+/// 入场动画定义
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntranceEffect {
+    /// 动画键，例如 `fly`
+    pub key: &'static str,
+    /// 动画名称
+    pub name: &'static str,
+    /// `p:animEffect` 的滤镜，`None` 表示仅切换可见性
+    pub filter: Option<&'static str>,
+    /// `p:animEffect` 的 `prLst` 属性
+    pub pr_lst: Option<&'static str>,
+}
+
+/// 支持的入场动画（统一数据源）
+pub const ANIMATIONS: &[EntranceEffect] = &[
+    EntranceEffect {
+        key: "fade",
+        name: "淡入",
+        filter: Some("fade"),
+        pr_lst: None,
+    },
+    EntranceEffect {
+        key: "fly",
+        name: "飞入",
+        filter: Some("fly"),
+        pr_lst: Some("from(b)"),
+    },
+    EntranceEffect {
+        key: "zoom",
+        name: "缩放",
+        filter: Some("zoom"),
+        pr_lst: Some("in"),
+    },
+    EntranceEffect {
+        key: "appear",
+        name: "出现",
+        filter: None,
+        pr_lst: None,
+    },
+];
+
+/// 切换效果设置
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionSpec {
+    /// 效果键（未知效果回退为 `fade`）
+    pub effect: String,
+    /// 持续时间（秒）
+    pub duration: f32,
+    /// 自动翻页间隔（秒），None 表示手动翻页
+    pub advance_after: Option<f32>,
+}
+
+/// 入场动画设置
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationSpec {
+    /// 动画键（未知动画回退为 `fade`）
+    pub effect: String,
+    /// 单个元素的动画时长（秒）
+    pub duration: f32,
+    /// 首个元素的延迟（秒）
+    pub delay: f32,
+}
+
+/// 查找切换效果，未知效果回退为 `fade`
+pub fn find_transition(key: &str) -> &'static TransitionEffect {
+    TRANSITIONS
+        .iter()
+        .find(|t| t.key == key)
+        .unwrap_or(&TRANSITIONS[0])
+}
+
+/// 查找入场动画，未知动画回退为 `fade`
+pub fn find_animation(key: &str) -> &'static EntranceEffect {
+    ANIMATIONS
+        .iter()
+        .find(|a| a.key == key)
+        .unwrap_or(&ANIMATIONS[0])
+}
+
+/// 将持续时间（秒）转换为 OOXML 速度值
+pub fn duration_to_speed(duration: f32) -> &'static str {
+    if duration >= 0.75 {
+        "slow"
+    } else if duration >= 0.35 {
+        "med"
+    } else {
+        "fast"
+    }
+}
+
+/// 生成幻灯片切换效果 XML（`p:transition`）
+pub fn transition_xml(spec: &TransitionSpec) -> String {
+    let effect = find_transition(&spec.effect);
+
+    let advance = spec
+        .advance_after
+        .map(|secs| format!(r#" advTm="{}""#, (secs * 1000.0) as u32))
+        .unwrap_or_default();
+
+    let attrs: String = effect
+        .attrs
+        .iter()
+        .map(|(key, value)| format!(r#" {}="{}""#, key, value))
+        .collect();
+
+    format!(
+        r#"<p:transition spd="{}"{}><p:{}{}/></p:transition>"#,
+        duration_to_speed(spec.duration),
+        advance,
+        effect.element,
+        attrs
+    )
+}
+
+/// 生成入场动画 XML（`p:timing`）
+///
+/// `shape_ids` 中的形状依次入场，每个形状在前一个形状动画结束后开始
+pub fn timing_xml(spec: &AnimationSpec, shape_ids: &[u32]) -> String {
+    if shape_ids.is_empty() {
+        return String::new();
+    }
+
+    let effect = find_animation(&spec.effect);
+    let duration_ms = (spec.duration * 1000.0) as u32;
+    let delay_ms = (spec.delay * 1000.0) as u32;
+
+    // cTn 1-3 为根节点、主序列与点击组
+    let mut next_id = 4;
+    let mut effects = String::new();
+
+    for (index, shape_id) in shape_ids.iter().enumerate() {
+        let start = delay_ms + duration_ms * index as u32;
+        let group_id = next_id;
+        let set_id = next_id + 1;
+        next_id += 2;
+
+        let mut behaviors = format!(
+            r#"<p:set><p:cBhvr><p:cTn id="{}" dur="1" fill="hold"><p:stCondLst><p:cond delay="0"/></p:stCondLst></p:cTn><p:tgtEl><p:spTgt spid="{}"/></p:tgtEl><p:attrNameLst><p:attrName>style.visibility</p:attrName></p:attrNameLst></p:cBhvr><p:to><p:strVal val="visible"/></p:to></p:set>"#,
+            set_id, shape_id
+        );
+
+        if let Some(filter) = effect.filter {
+            let pr_lst = effect
+                .pr_lst
+                .map(|value| format!(r#" prLst="{}""#, value))
+                .unwrap_or_default();
+            behaviors.push_str(&format!(
+                r#"<p:animEffect transition="in" filter="{}"{}><p:cBhvr><p:cTn id="{}" dur="{}"/><p:tgtEl><p:spTgt spid="{}"/></p:tgtEl></p:cBhvr></p:animEffect>"#,
+                filter, pr_lst, next_id, duration_ms, shape_id
+            ));
+            next_id += 1;
+        }
+
+        effects.push_str(&format!(
+            r#"<p:par><p:cTn id="{}" presetClass="entr" nodeType="withEffect" fill="hold"><p:stCondLst><p:cond delay="{}"/></p:stCondLst><p:childTnLst>{}</p:childTnLst></p:cTn></p:par>"#,
+            group_id, start, behaviors
+        ));
+    }
+
+    format!(
+        r#"<p:timing><p:tnLst><p:par><p:cTn id="1" dur="indefinite" restart="never" nodeType="tmRoot"><p:childTnLst><p:seq concurrent="1" nextAc="seek"><p:cTn id="2" dur="indefinite" nodeType="mainSeq"><p:childTnLst><p:par><p:cTn id="3" fill="hold"><p:stCondLst><p:cond delay="0"/></p:stCondLst><p:childTnLst>{}</p:childTnLst></p:cTn></p:par></p:childTnLst></p:cTn></p:seq></p:childTnLst></p:cTn></p:par></p:tnLst></p:timing>"#,
+        effects
+    )
+}
+
+/// 读取 SVG 根元素上声明的单页切换效果与入场动画
+///
+/// 支持的属性：`data-transition`、`data-transition-duration`、`data-advance-after`、
+/// `data-animation`、`data-animation-duration`、`data-animation-delay`
+pub fn read_slide_effects(svg: &str) -> (Option<TransitionSpec>, Option<AnimationSpec>) {
+    let mut reader = Reader::from_str(svg);
+    let mut buf = Vec::new();
+
+    let root = loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => break e.into_owned(),
+            Ok(Event::Eof) | Err(_) => return (None, None),
+            _ => {}
+        }
+        buf.clear();
+    };
+
+    let attr = |name: &str| -> Option<String> {
+        root.try_get_attribute(name)
+            .ok()
+            .flatten()
+            .and_then(|a| a.unescape_value().ok())
+            .map(|v| v.trim().to_string())
+    };
+    let number = |name: &str| attr(name).and_then(|v| v.parse::<f32>().ok());
+
+    let transition = attr("data-transition").map(|effect| TransitionSpec {
+        effect,
+        duration: number("data-transition-duration").unwrap_or(0.5),
+        advance_after: number("data-advance-after"),
+    });
+
+    let animation = attr("data-animation").map(|effect| AnimationSpec {
+        effect,
+        duration: number("data-animation-duration").unwrap_or(0.5),
+        delay: number("data-animation-delay").unwrap_or(0.0),
+    });
+
+    (transition, animation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_to_speed() {
+        assert_eq!(duration_to_speed(1.0), "slow");
+        assert_eq!(duration_to_speed(0.5), "med");
+        assert_eq!(duration_to_speed(0.2), "fast");
+    }
+
+    #[test]
+    fn test_transition_xml() {
+        let spec = TransitionSpec {
+            effect: "reveal".to_string(),
+            duration: 1.0,
+            advance_after: Some(3.0),
+        };
+        assert_eq!(
+            transition_xml(&spec),
+            r#"<p:transition spd="slow" advTm="3000"><p:strips dir="rd"/></p:transition>"#
+        );
+
+        let unknown = TransitionSpec {
+            effect: "unknown".to_string(),
+            duration: 0.5,
+            advance_after: None,
+        };
+        assert_eq!(
+            transition_xml(&unknown),
+            r#"<p:transition spd="med"><p:fade/></p:transition>"#
+        );
+    }
+
+    #[test]
+    fn test_timing_xml_unique_ids() {
+        let spec = AnimationSpec {
+            effect: "fly".to_string(),
+            duration: 0.5,
+            delay: 0.0,
+        };
+        let xml = timing_xml(&spec, &[2, 3]);
+
+        assert!(xml.contains(r#"filter="fly" prLst="from(b)""#));
+        assert!(xml.contains(r#"<p:spTgt spid="3"/>"#));
+        assert!(xml.contains(r#"<p:cond delay="500"/>"#));
+        for id in 1..=9 {
+            assert_eq!(xml.matches(&format!(r#"<p:cTn id="{}" "#, id)).count(), 1);
+        }
+        assert!(timing_xml(&spec, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_read_slide_effects() {
+        let svg = r#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg" data-transition="push" data-transition-duration="0.8" data-animation="zoom"></svg>"#;
+        let (transition, animation) = read_slide_effects(svg);
+
+        let transition = transition.expect("应读取到切换效果");
+        assert_eq!(transition.effect, "push");
+        assert_eq!(transition.duration, 0.8);
+        assert_eq!(animation.expect("应读取到入场动画").effect, "zoom");
+
+        assert_eq!(read_slide_effects("<svg/>"), (None, None));
+    }
+}
@@ -0,0 +1,282 @@
+// HTML 放映后端
+// 将所有幻灯片（内嵌图片）、备注与切换效果打包为单个离线 HTML 文件，
+// 支持键盘翻页、全屏与演讲者视图，无需 PowerPoint 即可放映
+
+use crate::media::inline_images;
+use crate::notes;
+use crate::{
+    accessibility, escape_xml, run_blocking, slide_checkpoint, ExportFuture, ExportSummary,
+    PptxBackend, PptxConfig, ProgressSink, Result, Slide, SlideContent,
+};
+use base64::Engine;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tokio_util::sync::CancellationToken;
+
+/// 放映页面模板
+const TEMPLATE: &str = include_str!("slideshow.html");
+
+/// 页面中的幻灯片数据
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeckSlide {
+    title: String,
+    section: Option<String>,
+    /// 幻灯片图片（data URI）
+    image: String,
+    /// 备注 HTML
+    notes: String,
+    transition: Option<DeckTransition>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DeckTransition {
+    effect: String,
+    duration: f32,
+    advance_after: Option<f32>,
+}
+
+/// HTML 放映后端
+///
+/// 每页幻灯片以 data URI 图片嵌入（各页 SVG 相互隔离，避免 ID 冲突）；
+/// SVG 中以相对路径引用的图片按 `base_dir` 解析后内嵌
+pub struct HtmlSlideshow {
+    base_dir: Option<PathBuf>,
+}
+
+impl HtmlSlideshow {
+    pub fn new() -> Self {
+        Self { base_dir: None }
+    }
+
+    /// 设置解析 SVG 中相对图片路径的目录（通常为项目的 `svg_final` 目录）
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+}
+
+impl Default for HtmlSlideshow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PptxBackend for HtmlSlideshow {
+    fn export<'a>(
+        &'a self,
+        slides: &'a [Slide],
+        output_path: &'a Path,
+        config: &'a PptxConfig,
+        progress: &'a dyn ProgressSink,
+        cancel: &'a CancellationToken,
+    ) -> ExportFuture<'a> {
+        Box::pin(async move {
+            let title = output_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Presentation");
+
+            let html = self
+                .build_html(slides, title, config, progress, cancel)
+                .await?;
+            std::fs::write(output_path, html)?;
+
+            Ok(ExportSummary {
+                slide_count: slides.len(),
+                media_count: slides.len(),
+                media_bytes_saved: 0,
+                accessibility: accessibility::audit_slides(slides)?,
+            })
+        })
+    }
+
+    fn name(&self) -> &str {
+        "html"
+    }
+
+    fn is_available(&self) -> bool {
+        true // 纯 Rust 实现，总是可用
+    }
+}
+
+impl HtmlSlideshow {
+    /// 生成完整的放映页面
+    async fn build_html(
+        &self,
+        slides: &[Slide],
+        title: &str,
+        config: &PptxConfig,
+        progress: &dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> Result<String> {
+        let mut deck = Vec::with_capacity(slides.len());
+        for (index, slide) in slides.iter().enumerate() {
+            slide_checkpoint(slides, index, progress, cancel).await?;
+
+            // 内嵌图片需要读取文件并编码，在阻塞线程池中执行
+            let (source, base_dir) = (slide.clone(), self.base_dir.clone());
+            let image = run_blocking(move || slide_image(&source, base_dir.as_deref())).await?;
+            deck.push(DeckSlide {
+                title: accessibility::readable_title(slide),
+                section: slide.section.clone(),
+                image,
+                notes: slide.notes.as_deref().map(notes_html).unwrap_or_default(),
+                transition: config.slide_transition(slide).map(|spec| DeckTransition {
+                    effect: spec.effect,
+                    duration: spec.duration,
+                    advance_after: spec.advance_after,
+                }),
+            });
+        }
+
+        // 脚本内嵌 JSON 时转义 `</`，避免提前结束 <script>
+        let deck = serde_json::to_string(&deck)?.replace("</", r"<\/");
+
+        Ok(TEMPLATE
+            .replace("{{TITLE}}", &escape_xml(title))
+            .replace("{{WIDTH}}", &config.width.to_string())
+            .replace("{{HEIGHT}}", &config.height.to_string())
+            .replace("{{DECK}}", &deck))
+    }
+}
+
+/// 幻灯片的 data URI
+fn slide_image(slide: &Slide, base_dir: Option<&Path>) -> Result<String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    match &slide.content {
+        SlideContent::Svg(svg) => {
+            let svg = match base_dir {
+                Some(base_dir) => inline_images(svg, base_dir)?,
+                None => svg.clone(),
+            };
+            Ok(format!("data:image/svg+xml;base64,{}", engine.encode(svg)))
+        }
+        SlideContent::Png(data) => Ok(format!("data:image/png;base64,{}", engine.encode(data))),
+    }
+}
+
+/// 将 Markdown 备注渲染为 HTML
+fn notes_html(markdown: &str) -> String {
+    notes::markdown_to_lines(markdown)
+        .iter()
+        .map(|line| {
+            if line.heading {
+                format!("<h3>{}</h3>", escape_xml(&line.text))
+            } else if line.level > 0 {
+                format!(
+                    r#"<p style="margin-left: {}em">{}</p>"#,
+                    line.level as f32 * 1.5,
+                    escape_xml(&line.text)
+                )
+            } else {
+                format!("<p>{}</p>", escape_xml(&line.text))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::TransitionSpec;
+    use crate::NoProgress;
+
+    #[tokio::test]
+    async fn test_export_html() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        std::fs::create_dir(dir.path().join("images")).expect("创建目录失败");
+        std::fs::write(dir.path().join("images/logo.png"), b"\x89PNG").expect("写入图片失败");
+        let svg_dir = dir.path().join("svg_final");
+        std::fs::create_dir(&svg_dir).expect("创建目录失败");
+
+        let slides = vec![
+            Slide {
+                number: 1,
+                title: "01_封面".to_string(),
+                content: SlideContent::Svg(
+                    r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"><image href="../images/logo.png" width="10" height="10"/><image href="../images/missing.png"/></svg>"#
+                        .to_string(),
+                ),
+                notes: Some("# 开场\n\n先讲 </script> 的问题\n\n- 细节".to_string()),
+                transition: Some(TransitionSpec {
+                    effect: "push".to_string(),
+                    duration: 0.8,
+                    advance_after: Some(5.0),
+                }),
+                animation: None,
+                section: Some("开场".to_string()),
+                source_svg: None,
+            },
+            Slide {
+                number: 2,
+                title: "02".to_string(),
+                content: SlideContent::Png(b"\x89PNG".to_vec()),
+                notes: None,
+                transition: None,
+                animation: None,
+                section: None,
+                source_svg: None,
+            },
+        ];
+        let output = dir.path().join("deck.html");
+
+        HtmlSlideshow::new()
+            .with_base_dir(&svg_dir)
+            .export(
+                &slides,
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let html = std::fs::read_to_string(&output).expect("读取 HTML 失败");
+        assert!(html.contains("<title>deck</title>"));
+        assert!(html.contains("--aspect: 1280 / 720;"));
+        assert!(!html.contains("{{"));
+
+        // 备注中的 </script> 不会提前结束脚本
+        assert_eq!(html.matches("</script>").count(), 2);
+
+        let start = html
+            .find(r#"<script type="application/json" id="deck">"#)
+            .expect("缺少幻灯片数据")
+            + 42;
+        let end = start + html[start..].find("</script>").expect("脚本未结束");
+        let deck: serde_json::Value =
+            serde_json::from_str(&html[start..end]).expect("幻灯片数据应为 JSON");
+        assert_eq!(deck[0]["title"], "封面");
+        assert_eq!(deck[0]["section"], "开场");
+        assert_eq!(deck[0]["transition"]["effect"], "push");
+        assert_eq!(deck[0]["transition"]["advanceAfter"], 5.0);
+        assert_eq!(
+            deck[0]["notes"],
+            "<h3>开场</h3><p>先讲 &lt;/script&gt; 的问题</p><p>• 细节</p>"
+        );
+        assert!(deck[1]["image"]
+            .as_str()
+            .expect("图片应为字符串")
+            .starts_with("data:image/png;base64,"));
+        // 第二页使用全局切换效果
+        assert_eq!(deck[1]["transition"]["effect"], "fade");
+
+        // 本地图片内嵌为 data URI，找不到的图片保持原样
+        let engine = base64::engine::general_purpose::STANDARD;
+        let image = deck[0]["image"].as_str().expect("图片应为字符串");
+        let svg = String::from_utf8(
+            engine
+                .decode(image.trim_start_matches("data:image/svg+xml;base64,"))
+                .expect("应为 base64"),
+        )
+        .expect("应为 UTF-8");
+        assert!(svg.contains(&format!(
+            r#"href="data:image/png;base64,{}""#,
+            engine.encode(b"\x89PNG")
+        )));
+        assert!(svg.contains(r#"href="../images/missing.png""#));
+    }
+}
@@ -0,0 +1,394 @@
+// 图片序列后端
+// 将每页幻灯片导出为编号的 PNG/JPEG 图片（1x/2x/3x），可选同时打包为 zip，
+// 用于小红书、朋友圈、公众号等以图片发布的画布格式
+
+use crate::{
+    accessibility, pixmap_to_rgb, render_svg, run_blocking, slide_checkpoint, ExportFuture,
+    ExportSummary, PptxBackend, PptxConfig, PptxError, ProgressSink, Result, Slide, SlideContent,
+};
+use image::codecs::jpeg::JpegEncoder;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tokio_util::sync::CancellationToken;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// 最大导出倍率
+const MAX_SCALE: u32 = 3;
+
+/// 默认 JPEG 质量
+const DEFAULT_JPEG_QUALITY: u8 = 90;
+
+/// 图片格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFormat {
+    /// PNG（无损）
+    #[default]
+    Png,
+    /// JPEG，附带质量（1-100）
+    Jpeg { quality: u8 },
+}
+
+impl ImageFormat {
+    /// 按名称解析格式（"png"、"jpg"/"jpeg"），JPEG 质量缺省为 90
+    pub fn parse(name: &str, quality: Option<u8>) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "png" => Some(Self::Png),
+            "jpg" | "jpeg" => Some(Self::Jpeg {
+                quality: quality.unwrap_or(DEFAULT_JPEG_QUALITY).clamp(1, 100),
+            }),
+            _ => None,
+        }
+    }
+
+    /// 文件扩展名
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg { .. } => "jpg",
+        }
+    }
+}
+
+/// 图片序列后端
+///
+/// `output_path` 为输出目录，图片按幻灯片顺序命名为 `01.png`、`02.png`……，
+/// 导出前清除目录中上次生成的编号图片；启用打包时在目录旁写入同名 zip（如 `images/` 对应 `images.zip`）
+pub struct ImageSequence {
+    format: ImageFormat,
+    scale: u32,
+    zip: bool,
+}
+
+impl ImageSequence {
+    pub fn new() -> Self {
+        Self {
+            format: ImageFormat::default(),
+            scale: 1,
+            zip: false,
+        }
+    }
+
+    /// 设置图片格式
+    pub fn with_format(mut self, format: ImageFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// 设置导出倍率（1x/2x/3x）
+    pub fn with_scale(mut self, scale: u32) -> Self {
+        self.scale = scale.clamp(1, MAX_SCALE);
+        self
+    }
+
+    /// 设置是否同时打包为 zip
+    pub fn with_zip(mut self, zip: bool) -> Self {
+        self.zip = zip;
+        self
+    }
+
+    /// 打包文件路径：输出目录同级的同名 zip
+    pub fn zip_path(output_dir: &Path) -> PathBuf {
+        output_dir.with_extension("zip")
+    }
+}
+
+impl Default for ImageSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PptxBackend for ImageSequence {
+    fn export<'a>(
+        &'a self,
+        slides: &'a [Slide],
+        output_path: &'a Path,
+        config: &'a PptxConfig,
+        progress: &'a dyn ProgressSink,
+        cancel: &'a CancellationToken,
+    ) -> ExportFuture<'a> {
+        Box::pin(async move {
+            std::fs::create_dir_all(output_path)?;
+            remove_previous_images(output_path)?;
+
+            let width = config.width * self.scale;
+            let height = config.height * self.scale;
+            // 序号位数随页数增加，保证按文件名排序即为幻灯片顺序
+            let digits = slides.len().to_string().len().max(2);
+
+            let mut files = Vec::with_capacity(slides.len());
+            for (index, slide) in slides.iter().enumerate() {
+                slide_checkpoint(slides, index, progress, cancel).await?;
+
+                let name = format!(
+                    "{:0digits$}.{}",
+                    index + 1,
+                    self.format.extension(),
+                    digits = digits
+                );
+                // 渲染、编码与写入在阻塞线程池中执行
+                let (source, format, path) = (slide.clone(), self.format, output_path.join(&name));
+                let data = run_blocking(move || {
+                    let data = encode_slide(&source, format, width, height)?;
+                    std::fs::write(path, &data)?;
+                    Ok(data)
+                })
+                .await?;
+                files.push((name, data));
+            }
+
+            let media_count = files.len();
+            if self.zip {
+                let path = Self::zip_path(output_path);
+                run_blocking(move || write_zip(&path, &files)).await?;
+            }
+
+            Ok(ExportSummary {
+                slide_count: slides.len(),
+                media_count,
+                media_bytes_saved: 0,
+                accessibility: accessibility::audit_slides(slides)?,
+            })
+        })
+    }
+
+    fn name(&self) -> &str {
+        "images"
+    }
+
+    fn is_available(&self) -> bool {
+        true // 纯 Rust 实现，总是可用
+    }
+}
+
+/// 渲染并编码一页幻灯片
+///
+/// 回退为 PNG 的幻灯片按原始 SVG 重新渲染，只有没有 SVG 来源的位图（如导入的图片）才缩放
+fn encode_slide(slide: &Slide, format: ImageFormat, width: u32, height: u32) -> Result<Vec<u8>> {
+    let pixmap = match (&slide.content, &slide.source_svg) {
+        (SlideContent::Svg(svg), _) | (SlideContent::Png(_), Some(svg)) => {
+            render_svg(svg, width, height)?
+        }
+        (SlideContent::Png(data), None) => {
+            let source = tiny_skia::Pixmap::decode_png(data)
+                .map_err(|e| PptxError::PngConversion(e.to_string()))?;
+            resize(&source, width, height)?
+        }
+    };
+
+    match format {
+        ImageFormat::Png => pixmap
+            .encode_png()
+            .map_err(|e| PptxError::PngConversion(e.to_string())),
+        ImageFormat::Jpeg { quality } => encode_jpeg(&pixmap, quality),
+    }
+}
+
+/// 删除目录中上次导出的编号图片（`01.png`、`02.jpg` 等），避免页数减少或切换格式后残留
+///
+/// 其他文件保持不变
+fn remove_previous_images(dir: &Path) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let numbered = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| !stem.is_empty() && stem.bytes().all(|b| b.is_ascii_digit()));
+        let image = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("png") || ext.eq_ignore_ascii_case("jpg"));
+        if numbered && image && path.is_file() {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// 将位图缩放到指定尺寸（尺寸相同时直接复制）
+fn resize(source: &tiny_skia::Pixmap, width: u32, height: u32) -> Result<tiny_skia::Pixmap> {
+    if source.width() == width && source.height() == height {
+        return Ok(source.clone());
+    }
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| PptxError::PngConversion("无法创建 pixmap".to_string()))?;
+    let transform = tiny_skia::Transform::from_scale(
+        width as f32 / source.width() as f32,
+        height as f32 / source.height() as f32,
+    );
+    let paint = tiny_skia::PixmapPaint {
+        quality: tiny_skia::FilterQuality::Bicubic,
+        ..tiny_skia::PixmapPaint::default()
+    };
+    pixmap.draw_pixmap(0, 0, source.as_ref(), &paint, transform, None);
+    Ok(pixmap)
+}
+
+/// 编码为 JPEG（透明部分合成到白色背景上）
+fn encode_jpeg(pixmap: &tiny_skia::Pixmap, quality: u8) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    JpegEncoder::new_with_quality(&mut data, quality)
+        .encode(
+            &pixmap_to_rgb(pixmap),
+            pixmap.width(),
+            pixmap.height(),
+            image::ExtendedColorType::Rgb8,
+        )
+        .map_err(|e| PptxError::PngConversion(e.to_string()))?;
+    Ok(data)
+}
+
+/// 将图片打包为 zip（图片已压缩，直接存储）
+fn write_zip(path: &Path, files: &[(String, Vec<u8>)]) -> Result<()> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let options = FileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .unix_permissions(0o644);
+
+    for (name, data) in files {
+        zip.start_file(name.as_str(), options)?;
+        zip.write_all(data)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{svg_to_png, NoProgress};
+    use std::io::Read;
+
+    fn slide(number: usize, content: SlideContent) -> Slide {
+        Slide {
+            number,
+            title: format!("{:02}_page", number),
+            content,
+            notes: None,
+            transition: None,
+            animation: None,
+            section: None,
+            source_svg: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_images() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let output = dir.path().join("images");
+        let config = PptxConfig {
+            width: 90,
+            height: 120,
+            ..PptxConfig::default()
+        };
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 90 120"><rect width="90" height="120" fill="#FF2442"/></svg>"##;
+        let slides = vec![
+            slide(1, SlideContent::Svg(svg.to_string())),
+            slide(
+                2,
+                SlideContent::Png(svg_to_png(svg, 90, 120).expect("渲染应成功")),
+            ),
+        ];
+
+        let summary = ImageSequence::new()
+            .with_scale(2)
+            .with_zip(true)
+            .export(
+                &slides,
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        assert_eq!(summary.media_count, 2);
+
+        // PNG 内容同样按倍率放大
+        for name in ["01.png", "02.png"] {
+            let pixmap = tiny_skia::Pixmap::load_png(output.join(name)).expect("读取 PNG 失败");
+            assert_eq!((pixmap.width(), pixmap.height()), (180, 240));
+        }
+
+        let mut archive = zip::ZipArchive::new(
+            File::open(ImageSequence::zip_path(&output)).expect("缺少 zip 文件"),
+        )
+        .expect("zip 格式错误");
+        assert_eq!(archive.len(), 2);
+        let mut packed = Vec::new();
+        archive
+            .by_name("02.png")
+            .expect("zip 中缺少图片")
+            .read_to_end(&mut packed)
+            .expect("读取 zip 失败");
+        assert_eq!(
+            packed,
+            std::fs::read(output.join("02.png")).expect("读取图片失败")
+        );
+
+        std::fs::write(output.join("notes.txt"), "保留").expect("写入文件失败");
+        let format = ImageFormat::parse("JPEG", Some(80)).expect("应识别 JPEG");
+        ImageSequence::new()
+            .with_format(format)
+            .with_scale(5)
+            .export(
+                &slides[..1],
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        let jpeg = image::open(output.join("01.jpg")).expect("读取 JPEG 失败");
+        assert_eq!((jpeg.width(), jpeg.height()), (270, 360));
+        // 上次导出的 PNG 已被清除，其他文件保持不变
+        let mut names: Vec<_> = std::fs::read_dir(&output)
+            .expect("读取目录失败")
+            .map(|entry| entry.expect("读取目录失败").file_name())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["01.jpg", "notes.txt"]);
+        assert_eq!(ImageFormat::parse("gif", None), None);
+    }
+
+    #[tokio::test]
+    async fn test_export_fallback_slide_from_source_svg() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let output = dir.path().join("images");
+        let config = PptxConfig {
+            width: 90,
+            height: 120,
+            ..PptxConfig::default()
+        };
+        // 细线在 1x 位图放大后会变模糊，按原始 SVG 渲染时应与直接渲染一致
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 90 120"><style>rect { fill: #1A73E8 }</style><rect x="10.5" y="0" width="0.5" height="120"/></svg>"##;
+        let slides = vec![Slide {
+            source_svg: Some(svg.to_string()),
+            ..slide(
+                1,
+                SlideContent::Png(svg_to_png(svg, 90, 120).expect("渲染应成功")),
+            )
+        }];
+
+        ImageSequence::new()
+            .with_scale(3)
+            .export(
+                &slides,
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        assert_eq!(
+            std::fs::read(output.join("01.png")).expect("读取图片失败"),
+            svg_to_png(svg, 270, 360).expect("渲染应成功")
+        );
+    }
+}
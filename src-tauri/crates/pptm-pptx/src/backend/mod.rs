@@ -1,7 +1,15 @@
 // PPTX 后端实现
 
+pub mod html;
+pub mod images;
 pub mod native_ooxml;
+pub mod odp;
+pub mod pdf;
 pub mod pptxgen_sidecar;
 
-pub use native_ooxml::NativeOoxml;
+pub use html::HtmlSlideshow;
+pub use images::{ImageFormat, ImageSequence};
+pub use native_ooxml::{NativeOoxml, SvgMode};
+pub use odp::Odp;
+pub use pdf::PdfExport;
 pub use pptxgen_sidecar::PptxGenSidecar;
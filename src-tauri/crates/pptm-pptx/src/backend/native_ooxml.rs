@@ -1,21 +1,63 @@
 // Native OOXML 后端
 // 使用 Rust 原生实现生成 OOXML 格式的 PPTX
 
-use crate::{PptxBackend, PptxConfig, Result, Slide, SlideContent};
-use std::fs::File;
-use std::io::Write;
+use crate::drawingml::alt_text::AltText;
+use crate::media::{MediaStats, MediaStore};
+use crate::package::{content_types, rel_types, OoxmlPackage};
+use crate::section::{self, Section};
+use crate::theme::Theme;
+use crate::{
+    accessibility, animation, drawingml, escape_xml, notes, run_blocking, slide_checkpoint,
+    svg_to_png, validate, ExportFuture, ExportSummary, PptxBackend, PptxConfig, ProgressSink,
+    Result, Slide, SlideContent,
+};
 use std::path::Path;
-use zip::write::{FileOptions, ZipWriter};
-use zip::CompressionMethod;
+use tokio_util::sync::CancellationToken;
+
+/// 应用名称（写入 docProps）
+const APPLICATION_NAME: &str = "Agents for PPT";
+
+/// 备注页尺寸（EMU，纵向 A4 近似）
+const NOTES_WIDTH: i64 = 6_858_000;
+const NOTES_HEIGHT: i64 = 9_144_000;
+
+/// SVG 幻灯片的导出模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SvgMode {
+    /// 转换为可编辑的 DrawingML 形状
+    #[default]
+    Shapes,
+    /// 嵌入原生 SVG 图片（`asvg:svgBlip`），并附带 PNG 后备图（兼容模式）
+    SvgBlip,
+}
+
+/// 整页图片引用的关系 ID
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SlidePicture {
+    /// PNG 图片（或 SVG 的后备图）
+    image_rid: String,
+    /// 原生 SVG 图片
+    svg_rid: Option<String>,
+}
 
 /// Native OOXML 后端
 ///
 /// 使用 Rust 原生实现生成 OOXML 格式的 PPTX
-pub struct NativeOoxml;
+pub struct NativeOoxml {
+    svg_mode: SvgMode,
+}
 
 impl NativeOoxml {
     pub fn new() -> Self {
-        Self
+        Self {
+            svg_mode: SvgMode::default(),
+        }
+    }
+
+    /// 设置 SVG 幻灯片的导出模式
+    pub fn with_svg_mode(mut self, mode: SvgMode) -> Self {
+        self.svg_mode = mode;
+        self
     }
 }
 
@@ -26,70 +68,38 @@ impl Default for NativeOoxml {
 }
 
 impl PptxBackend for NativeOoxml {
-    fn export(&self, slides: &[Slide], output_path: &Path, config: &PptxConfig) -> Result<()> {
-        let file = File::create(output_path)?;
-        let mut zip = ZipWriter::new(file);
-        let options = FileOptions::default()
-            .compression_method(CompressionMethod::Deflated)
-            .unix_permissions(0o644);
-
-        // 写入 [Content_Types].xml
-        zip.start_file("[Content_Types].xml", options)?;
-        zip.write_all(self.generate_content_types(slides.len()).as_bytes())?;
-
-        // 写入 _rels/.rels
-        zip.start_file("_rels/.rels", options)?;
-        zip.write_all(self.generate_root_rels().as_bytes())?;
-
-        // 写入 ppt/presentation.xml
-        zip.start_file("ppt/presentation.xml", options)?;
-        zip.write_all(self.generate_presentation(slides.len(), config).as_bytes())?;
-
-        // 写入 ppt/_rels/presentation.xml.rels
-        zip.start_file("ppt/_rels/presentation.xml.rels", options)?;
-        zip.write_all(self.generate_presentation_rels(slides.len()).as_bytes())?;
-
-        // 写入每个幻灯片
-        for (index, slide) in slides.iter().enumerate() {
-            let slide_num = index + 1;
-
-            // 写入 ppt/slides/slide{n}.xml
-            zip.start_file(format!("ppt/slides/slide{}.xml", slide_num), options)?;
-            zip.write_all(self.generate_slide(slide, config).as_bytes())?;
+    fn export<'a>(
+        &'a self,
+        slides: &'a [Slide],
+        output_path: &'a Path,
+        config: &'a PptxConfig,
+        progress: &'a dyn ProgressSink,
+        cancel: &'a CancellationToken,
+    ) -> ExportFuture<'a> {
+        Box::pin(async move {
+            let title = output_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Presentation");
 
-            // 写入 ppt/slides/_rels/slide{n}.xml.rels
-            zip.start_file(
-                format!("ppt/slides/_rels/slide{}.xml.rels", slide_num),
-                options,
-            )?;
-            zip.write_all(self.generate_slide_rels(slide_num).as_bytes())?;
-
-            // 如果有演讲备注，写入 ppt/notesSlides/notesSlide{n}.xml
-            if slide.notes.is_some() {
-                zip.start_file(
-                    format!("ppt/notesSlides/notesSlide{}.xml", slide_num),
-                    options,
-                )?;
-                zip.write_all(self.generate_notes_slide(slide).as_bytes())?;
-            }
-
-            // 如果是 PNG 内容，写入图片文件
-            if let SlideContent::Png(png_data) = &slide.content {
-                zip.start_file(format!("ppt/media/image{}.png", slide_num), options)?;
-                zip.write_all(png_data)?;
-            }
-        }
+            // 写入后检查所有关系目标都指向实际存在的部件，再整体校验写出的文件
+            let (package, media) = self
+                .build_package(slides, title, config, progress, cancel)
+                .await?;
+            let output = output_path.to_path_buf();
+            run_blocking(move || {
+                package.write(&output)?;
+                validate::ensure_valid(&output)
+            })
+            .await?;
 
-        // 写入 ppt/slideLayouts/slideLayout1.xml（简化版）
-        zip.start_file("ppt/slideLayouts/slideLayout1.xml", options)?;
-        zip.write_all(self.generate_slide_layout().as_bytes())?;
-
-        // 写入 ppt/slideMasters/slideMaster1.xml（简化版）
-        zip.start_file("ppt/slideMasters/slideMaster1.xml", options)?;
-        zip.write_all(self.generate_slide_master().as_bytes())?;
-
-        zip.finish()?;
-        Ok(())
+            Ok(ExportSummary {
+                slide_count: slides.len(),
+                media_count: media.media_count,
+                media_bytes_saved: media.bytes_saved,
+                accessibility: accessibility::audit_slides(slides)?,
+            })
+        })
     }
 
     fn name(&self) -> &str {
@@ -102,100 +112,453 @@ impl PptxBackend for NativeOoxml {
 }
 
 impl NativeOoxml {
-    fn generate_content_types(&self, slide_count: usize) -> String {
-        let mut xml = String::from(
-            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
-  <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
-  <Default Extension="xml" ContentType="application/xml"/>
-  <Default Extension="png" ContentType="image/png"/>
-  <Override PartName="/ppt/presentation.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml"/>
-"#,
+    /// 幻灯片是否以原生 SVG 图片形式嵌入
+    fn embeds_svg(&self, slide: &Slide) -> bool {
+        self.svg_mode == SvgMode::SvgBlip && matches!(slide.content, SlideContent::Svg(_))
+    }
+
+    /// 组装完整的包：只为实际写入的部件声明关系与内容类型
+    ///
+    /// 同时返回媒体去重统计；每页开始前检查取消并上报进度，SVG 转换与渲染在阻塞线程池中执行
+    async fn build_package(
+        &self,
+        slides: &[Slide],
+        title: &str,
+        config: &PptxConfig,
+        progress: &dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> Result<(OoxmlPackage, MediaStats)> {
+        const PRESENTATION: &str = "ppt/presentation.xml";
+        const MASTER: &str = "ppt/slideMasters/slideMaster1.xml";
+        const LAYOUT: &str = "ppt/slideLayouts/slideLayout1.xml";
+        const NOTES_MASTER: &str = "ppt/notesMasters/notesMaster1.xml";
+
+        let mut package = OoxmlPackage::new();
+        let mut media = MediaStore::new();
+        let has_notes = slides.iter().any(|slide| slide.notes.is_some());
+
+        // 包级关系与文档属性
+        package.add_relationship("", rel_types::OFFICE_DOCUMENT, PRESENTATION);
+        package.add_relationship("", rel_types::CORE_PROPERTIES, "docProps/core.xml");
+        package.add_relationship("", rel_types::EXTENDED_PROPERTIES, "docProps/app.xml");
+        package.add_part(
+            "docProps/core.xml",
+            content_types::CORE_PROPERTIES,
+            self.generate_core_properties(title),
+        );
+        package.add_part(
+            "docProps/app.xml",
+            content_types::EXTENDED_PROPERTIES,
+            self.generate_app_properties(slides),
         );
 
-        for i in 1..=slide_count {
-            xml.push_str(&format!(
-                r#"  <Override PartName="/ppt/slides/slide{}.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slide+xml"/>
-"#,
-                i
-            ));
-            xml.push_str(&format!(
-                r#"  <Override PartName="/ppt/notesSlides/notesSlide{}.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.notesSlide+xml"/>
-"#,
-                i
+        // 母版、版式与主题
+        let master_rid = package.add_relationship(
+            PRESENTATION,
+            rel_types::SLIDE_MASTER,
+            "slideMasters/slideMaster1.xml",
+        );
+        let layout_rid = package.add_relationship(
+            MASTER,
+            rel_types::SLIDE_LAYOUT,
+            "../slideLayouts/slideLayout1.xml",
+        );
+        package.add_relationship(MASTER, rel_types::THEME, "../theme/theme1.xml");
+        package.add_relationship(
+            LAYOUT,
+            rel_types::SLIDE_MASTER,
+            "../slideMasters/slideMaster1.xml",
+        );
+        package.add_part(
+            MASTER,
+            content_types::SLIDE_MASTER,
+            self.generate_slide_master(&layout_rid),
+        );
+        package.add_part(
+            LAYOUT,
+            content_types::SLIDE_LAYOUT,
+            self.generate_slide_layout(),
+        );
+        package.add_part(
+            "ppt/theme/theme1.xml",
+            content_types::THEME,
+            config.theme.clone().unwrap_or_default().to_xml(),
+        );
+
+        // 幻灯片
+        let mut slide_rids = Vec::with_capacity(slides.len());
+        let mut chart_count = 0usize;
+        for (index, slide) in slides.iter().enumerate() {
+            slide_checkpoint(slides, index, progress, cancel).await?;
+
+            let slide_num = index + 1;
+            let slide_part = format!("ppt/slides/slide{}.xml", slide_num);
+
+            slide_rids.push(package.add_relationship(
+                PRESENTATION,
+                rel_types::SLIDE,
+                &format!("slides/slide{}.xml", slide_num),
             ));
+            package.add_relationship(
+                &slide_part,
+                rel_types::SLIDE_LAYOUT,
+                "../slideLayouts/slideLayout1.xml",
+            );
+
+            // 图片统一登记到媒体库，内容相同的图片只写入一次
+            let shapes = match &slide.content {
+                SlideContent::Png(png_data) => {
+                    let target = media
+                        .insert(png_data, "png", content_types::PNG)
+                        .slide_target();
+                    let picture = SlidePicture {
+                        image_rid: package.add_relationship(&slide_part, rel_types::IMAGE, &target),
+                        svg_rid: None,
+                    };
+                    self.picture_shapes(slide, config, &picture, AltText::default())
+                }
+                // SVG 图片模式写入原始 SVG 与 PNG 后备图
+                SlideContent::Svg(svg) if self.embeds_svg(slide) => {
+                    let (source, width, height) = (svg.clone(), config.width, config.height);
+                    let png = run_blocking(move || svg_to_png(&source, width, height)).await?;
+                    let png_target = media.insert(&png, "png", content_types::PNG).slide_target();
+                    let svg_target = media
+                        .insert(svg.as_bytes(), "svg", content_types::SVG)
+                        .slide_target();
+                    let picture = SlidePicture {
+                        image_rid: package.add_relationship(
+                            &slide_part,
+                            rel_types::IMAGE,
+                            &png_target,
+                        ),
+                        svg_rid: Some(package.add_relationship(
+                            &slide_part,
+                            rel_types::IMAGE,
+                            &svg_target,
+                        )),
+                    };
+                    let (_, alt) = drawingml::alt_text::extract_alt_text(svg)?;
+                    self.picture_shapes(slide, config, &picture, alt.document)
+                }
+                // 形状模式只写入 SVG 中嵌入的图片
+                SlideContent::Svg(svg) => {
+                    let (source, width, height) = (svg.clone(), config.width, config.height);
+                    let mut shapes =
+                        run_blocking(move || drawingml::svg_to_shapes(&source, width, height))
+                            .await?;
+                    for warning in &shapes.warnings {
+                        progress.log("warn", format!("第 {} 页: {}", slide.number, warning));
+                    }
+                    shapes.ensure_title(&accessibility::readable_title(slide), config.width);
+                    let rids: Vec<String> = shapes
+                        .images
+                        .iter()
+                        .map(|image| {
+                            let target = media
+                                .insert(&image.data, image.extension, image.content_type)
+                                .slide_target();
+                            package.add_relationship(&slide_part, rel_types::IMAGE, &target)
+                        })
+                        .collect();
+                    shapes.bind_images(&rids);
+
+                    // 外部链接写入外部关系，页内跳转指向目标幻灯片
+                    let link_rids: Vec<Option<String>> = shapes
+                        .links
+                        .iter()
+                        .map(|link| match link.fragment() {
+                            Some(fragment) => resolve_slide_link(fragment, slides).map(|target| {
+                                package.add_relationship(
+                                    &slide_part,
+                                    rel_types::SLIDE,
+                                    &format!("slide{}.xml", target + 1),
+                                )
+                            }),
+                            None => Some(package.add_external_relationship(
+                                &slide_part,
+                                rel_types::HYPERLINK,
+                                &link.href,
+                            )),
+                        })
+                        .collect();
+                    shapes.bind_links(&link_rids);
+
+                    // 图表部件按整个演示文稿编号，各自引用内嵌工作簿
+                    let mut chart_rids = Vec::with_capacity(shapes.charts.len());
+                    for spec in &shapes.charts {
+                        chart_count += 1;
+                        let chart_part = format!("ppt/charts/chart{}.xml", chart_count);
+                        let workbook_rid = package.add_relationship(
+                            &chart_part,
+                            rel_types::PACKAGE,
+                            &format!(
+                                "../embeddings/Microsoft_Excel_Worksheet{}.xlsx",
+                                chart_count
+                            ),
+                        );
+                        package.add_part(
+                            format!(
+                                "ppt/embeddings/Microsoft_Excel_Worksheet{}.xlsx",
+                                chart_count
+                            ),
+                            content_types::XLSX,
+                            spec.to_workbook()?,
+                        );
+                        package.add_part(
+                            chart_part,
+                            content_types::CHART,
+                            spec.to_chart_xml(&workbook_rid),
+                        );
+                        chart_rids.push(package.add_relationship(
+                            &slide_part,
+                            rel_types::CHART,
+                            &format!("../charts/chart{}.xml", chart_count),
+                        ));
+                    }
+                    shapes.bind_charts(&chart_rids);
+                    shapes
+                }
+            };
+
+            package.add_part(
+                slide_part.as_str(),
+                content_types::SLIDE,
+                self.generate_slide(slide, config, &shapes),
+            );
+
+            // 演讲备注：备注页与幻灯片互相引用，并引用备注母版
+            if slide.notes.is_some() {
+                let notes_part = format!("ppt/notesSlides/notesSlide{}.xml", slide_num);
+
+                package.add_relationship(
+                    &slide_part,
+                    rel_types::NOTES_SLIDE,
+                    &format!("../notesSlides/notesSlide{}.xml", slide_num),
+                );
+                package.add_relationship(
+                    &notes_part,
+                    rel_types::NOTES_MASTER,
+                    "../notesMasters/notesMaster1.xml",
+                );
+                package.add_relationship(
+                    &notes_part,
+                    rel_types::SLIDE,
+                    &format!("../slides/slide{}.xml", slide_num),
+                );
+                package.add_part(
+                    notes_part,
+                    content_types::NOTES_SLIDE,
+                    self.generate_notes_slide(slide),
+                );
+            }
         }
 
-        xml.push_str(r#"  <Override PartName="/ppt/slideLayouts/slideLayout1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideLayout+xml"/>
-  <Override PartName="/ppt/slideMasters/slideMaster1.xml" ContentType="application/vnd.openxmlformats-officedocument.presentationml.slideMaster+xml"/>
-</Types>"#);
+        // 备注母版只在存在备注时写入
+        let notes_master_rid = if has_notes {
+            package.add_relationship(NOTES_MASTER, rel_types::THEME, "../theme/theme2.xml");
+            package.add_part(
+                NOTES_MASTER,
+                content_types::NOTES_MASTER,
+                self.generate_notes_master(),
+            );
+            package.add_part(
+                "ppt/theme/theme2.xml",
+                content_types::THEME,
+                Theme::default().to_xml(),
+            );
 
-        xml
+            Some(package.add_relationship(
+                PRESENTATION,
+                rel_types::NOTES_MASTER,
+                "notesMasters/notesMaster1.xml",
+            ))
+        } else {
+            None
+        };
+
+        // 演示文稿级属性部件
+        package.add_relationship(PRESENTATION, rel_types::THEME, "theme/theme1.xml");
+        package.add_relationship(PRESENTATION, rel_types::PRES_PROPS, "presProps.xml");
+        package.add_relationship(PRESENTATION, rel_types::VIEW_PROPS, "viewProps.xml");
+        package.add_relationship(PRESENTATION, rel_types::TABLE_STYLES, "tableStyles.xml");
+        package.add_part(
+            "ppt/presProps.xml",
+            content_types::PRES_PROPS,
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:presentationPr xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"/>"#,
+        );
+        package.add_part(
+            "ppt/viewProps.xml",
+            content_types::VIEW_PROPS,
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:viewPr xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"/>"#,
+        );
+        package.add_part(
+            "ppt/tableStyles.xml",
+            content_types::TABLE_STYLES,
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<a:tblStyleLst xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" def="{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}"/>"#,
+        );
+
+        package.add_part(
+            PRESENTATION,
+            content_types::PRESENTATION,
+            self.generate_presentation(
+                &master_rid,
+                notes_master_rid.as_deref(),
+                &slide_rids,
+                &section::group_sections(slides),
+                config,
+            ),
+        );
+
+        let stats = media.stats();
+        media.write_into(&mut package);
+
+        Ok((package, stats))
     }
 
-    fn generate_root_rels(&self) -> String {
-        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
-  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="ppt/presentation.xml"/>
-</Relationships>"#.to_string()
+    fn generate_core_properties(&self, title: &str) -> String {
+        let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<cp:coreProperties xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/" xmlns:dcmitype="http://purl.org/dc/dcmitype/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+  <dc:title>{}</dc:title>
+  <dc:creator>{}</dc:creator>
+  <cp:lastModifiedBy>{}</cp:lastModifiedBy>
+  <cp:revision>1</cp:revision>
+  <dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created>
+  <dcterms:modified xsi:type="dcterms:W3CDTF">{}</dcterms:modified>
+</cp:coreProperties>"#,
+            escape_xml(title),
+            APPLICATION_NAME,
+            APPLICATION_NAME,
+            now,
+            now
+        )
     }
 
-    fn generate_presentation(&self, slide_count: usize, config: &PptxConfig) -> String {
-        let mut xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+    fn generate_app_properties(&self, slides: &[Slide]) -> String {
+        let notes_count = slides.iter().filter(|slide| slide.notes.is_some()).count();
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+  <Application>{}</Application>
+  <Slides>{}</Slides>
+  <Notes>{}</Notes>
+  <HiddenSlides>0</HiddenSlides>
+</Properties>"#,
+            APPLICATION_NAME,
+            slides.len(),
+            notes_count
+        )
+    }
+
+    fn generate_presentation(
+        &self,
+        master_rid: &str,
+        notes_master_rid: Option<&str>,
+        slide_rids: &[String],
+        sections: &[Section],
+        config: &PptxConfig,
+    ) -> String {
+        let mut xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <p:presentation xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
   <p:sldMasterIdLst>
-    <p:sldMasterId id="2147483648" r:id="rId1"/>
+    <p:sldMasterId id="2147483648" r:id="{}"/>
   </p:sldMasterIdLst>
-  <p:sldIdLst>
-"#.to_string();
+"#,
+            master_rid
+        );
 
-        for i in 1..=slide_count {
+        if let Some(rid) = notes_master_rid {
             xml.push_str(&format!(
-                r#"    <p:sldId id="{}" r:id="rId{}"/>
+                r#"  <p:notesMasterIdLst>
+    <p:notesMasterId r:id="{}"/>
+  </p:notesMasterIdLst>
 "#,
-                255 + i,
-                i + 1
+                rid
             ));
         }
 
+        // 空演示文稿不能写入空的 p:sldIdLst
+        if !slide_rids.is_empty() {
+            xml.push_str("  <p:sldIdLst>\n");
+            for (index, rid) in slide_rids.iter().enumerate() {
+                xml.push_str(&format!(
+                    r#"    <p:sldId id="{}" r:id="{}"/>
+"#,
+                    256 + index,
+                    rid
+                ));
+            }
+            xml.push_str("  </p:sldIdLst>\n");
+        }
+
         xml.push_str(&format!(
-            r#"  </p:sldIdLst>
-  <p:sldSz cx="{}" cy="{}"/>
-  <p:notesSz cx="6858000" cy="9144000"/>
-</p:presentation>"#,
+            r#"  <p:sldSz cx="{}" cy="{}"/>
+  <p:notesSz cx="{}" cy="{}"/>
+"#,
             config.width * 9525,
-            config.height * 9525
+            config.height * 9525,
+            NOTES_WIDTH,
+            NOTES_HEIGHT
         ));
 
-        xml
-    }
-
-    fn generate_presentation_rels(&self, slide_count: usize) -> String {
-        let mut xml = String::from(
-            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
-  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideMaster" Target="slideMasters/slideMaster1.xml"/>
+        // 分节（PowerPoint 2010 扩展），幻灯片 ID 与 p:sldIdLst 一致
+        if !sections.is_empty() {
+            xml.push_str(
+                r#"  <p:extLst>
+    <p:ext uri="{521415D9-36F7-43E2-AB2F-B90AF26B5E84}">
+      <p14:sectionLst xmlns:p14="http://schemas.microsoft.com/office/powerpoint/2010/main">
 "#,
-        );
-
-        for i in 1..=slide_count {
-            xml.push_str(&format!(
-                r#"  <Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide" Target="slides/slide{}.xml"/>
+            );
+            for (index, section) in sections.iter().enumerate() {
+                xml.push_str(&format!(
+                    r#"        <p14:section name="{}" id="{{{:08X}-5EC7-4000-8000-000000000000}}">
+          <p14:sldIdLst>
 "#,
-                i + 1,
-                i
-            ));
+                    escape_xml(&section.name),
+                    index + 1
+                ));
+                for slide_index in &section.slides {
+                    xml.push_str(&format!(
+                        r#"            <p14:sldId id="{}"/>
+"#,
+                        256 + slide_index
+                    ));
+                }
+                xml.push_str("          </p14:sldIdLst>\n        </p14:section>\n");
+            }
+            xml.push_str("      </p14:sectionLst>\n    </p:ext>\n  </p:extLst>\n");
         }
 
-        xml.push_str("</Relationships>");
+        xml.push_str("</p:presentation>");
         xml
     }
 
-    fn generate_slide(&self, slide: &Slide, config: &PptxConfig) -> String {
-        let content = match &slide.content {
-            SlideContent::Svg(svg) => self.svg_to_pml(svg, config),
-            SlideContent::Png(_) => self.png_to_pml(slide.number, config),
-        };
+    fn generate_slide(
+        &self,
+        slide: &Slide,
+        config: &PptxConfig,
+        shapes: &drawingml::ShapeTree,
+    ) -> String {
+        // 切换效果与入场动画位于 p:clrMapOvr 之后
+        let mut effects = String::new();
+        if let Some(transition) = config.slide_transition(slide) {
+            effects.push_str("\n  ");
+            effects.push_str(&animation::transition_xml(&transition));
+        }
+        if let Some(spec) = config.slide_animation(slide) {
+            let timing = animation::timing_xml(&spec, &shapes.shape_ids);
+            if !timing.is_empty() {
+                effects.push_str("\n  ");
+                effects.push_str(&timing);
+            }
+        }
 
         format!(
             r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
@@ -220,51 +583,51 @@ impl NativeOoxml {
   </p:cSld>
   <p:clrMapOvr>
     <a:masterClrMapping/>
-  </p:clrMapOvr>
+  </p:clrMapOvr>{}
 </p:sld>"#,
             config.width * 9525,
             config.height * 9525,
             config.width * 9525,
             config.height * 9525,
-            content
+            shapes.xml,
+            effects
         )
     }
 
-    fn svg_to_pml(&self, _svg: &str, _config: &PptxConfig) -> String {
-        // TODO: 实现 SVG 到 PresentationML 的转换
-        // 这是一个复杂的过程，需要解析 SVG 并转换为 OOXML 图形元素
-        // 目前返回一个占位符文本
-        r#"<p:sp>
-        <p:nvSpPr>
-          <p:cNvPr id="2" name="SVG Content"/>
-          <p:cNvSpPr/>
-          <p:nvPr/>
-        </p:nvSpPr>
-        <p:spPr>
-          <a:xfrm>
-            <a:off x="914400" y="914400"/>
-            <a:ext cx="9144000" cy="5486400"/>
-          </a:xfrm>
-          <a:prstGeom prst="rect">
-            <a:avLst/>
-          </a:prstGeom>
-        </p:spPr>
-        <p:txBody>
-          <a:bodyPr/>
-          <a:lstStyle/>
-          <a:p>
-            <a:r>
-              <a:rPr lang="zh-CN"/>
-              <a:t>SVG Content (TODO: Implement SVG to PML conversion)</a:t>
-            </a:r>
-          </a:p>
-        </p:txBody>
-      </p:sp>"#
-            .to_string()
-    }
+    /// 生成整页图片，引用原生 SVG 时在 PNG 之上附加 `asvg:svgBlip` 扩展
+    ///
+    /// 替代文字取 SVG 根元素的 `<title>`/`<desc>`，没有时使用幻灯片标题；
+    /// 同时在画布外添加标题占位符
+    fn picture_shapes(
+        &self,
+        slide: &Slide,
+        config: &PptxConfig,
+        picture: &SlidePicture,
+        mut alt: AltText,
+    ) -> drawingml::ShapeTree {
+        let title = alt
+            .title
+            .clone()
+            .unwrap_or_else(|| accessibility::readable_title(slide));
+        if alt.text().is_none() {
+            alt.description = Some(title.clone());
+        }
 
-    fn png_to_pml(&self, slide_num: usize, config: &PptxConfig) -> String {
-        format!(
+        let blip = match &picture.svg_rid {
+            Some(svg_rid) => format!(
+                r#"<a:blip r:embed="{}">
+            <a:extLst>
+              <a:ext uri="{{96DAC541-7B7A-43D3-8B79-37D633B846F1}}">
+                <asvg:svgBlip xmlns:asvg="http://schemas.microsoft.com/office/drawing/2016/SVG/main" r:embed="{}"/>
+              </a:ext>
+            </a:extLst>
+          </a:blip>"#,
+                picture.image_rid, svg_rid
+            ),
+            None => format!(r#"<a:blip r:embed="{}"/>"#, picture.image_rid),
+        };
+
+        let xml = format!(
             r#"<p:pic>
         <p:nvPicPr>
           <p:cNvPr id="2" name="Image {}"/>
@@ -274,7 +637,7 @@ impl NativeOoxml {
           <p:nvPr/>
         </p:nvPicPr>
         <p:blipFill>
-          <a:blip r:embed="rId1"/>
+          {}
           <a:stretch>
             <a:fillRect/>
           </a:stretch>
@@ -289,22 +652,20 @@ impl NativeOoxml {
           </a:prstGeom>
         </p:spPr>
       </p:pic>"#,
-            slide_num,
+            slide.number,
+            blip,
             config.width * 9525,
             config.height * 9525
-        )
-    }
+        );
 
-    fn generate_slide_rels(&self, slide_num: usize) -> String {
-        format!(
-            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
-  <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="../media/image{}.png"/>
-  <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout" Target="../slideLayouts/slideLayout1.xml"/>
-  <Relationship Id="rId3" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesSlide" Target="../notesSlides/notesSlide{}.xml"/>
-</Relationships>"#,
-            slide_num, slide_num
-        )
+        let mut shapes = drawingml::ShapeTree {
+            xml: drawingml::alt_text::describe(&xml, &alt),
+            shape_ids: vec![2],
+            next_id: 3,
+            ..Default::default()
+        };
+        shapes.ensure_title(&title, config.width);
+        shapes
     }
 
     fn generate_notes_slide(&self, slide: &Slide) -> String {
@@ -320,30 +681,34 @@ impl NativeOoxml {
         <p:cNvGrpSpPr/>
         <p:nvPr/>
       </p:nvGrpSpPr>
-      <p:grpSpPr>
-        <a:xfrm>
-          <a:off x="0" y="0"/>
-          <a:ext cx="6858000" cy="9144000"/>
-          <a:chOff x="0" y="0"/>
-          <a:chExt cx="6858000" cy="9144000"/>
-        </a:xfrm>
-      </p:grpSpPr>
+      <p:grpSpPr/>
       <p:sp>
         <p:nvSpPr>
-          <p:cNvPr id="2" name="Notes"/>
-          <p:cNvSpPr/>
-          <p:nvPr/>
+          <p:cNvPr id="2" name="Slide Image Placeholder 1"/>
+          <p:cNvSpPr>
+            <a:spLocks noGrp="1" noRot="1" noChangeAspect="1"/>
+          </p:cNvSpPr>
+          <p:nvPr>
+            <p:ph type="sldImg"/>
+          </p:nvPr>
+        </p:nvSpPr>
+        <p:spPr/>
+      </p:sp>
+      <p:sp>
+        <p:nvSpPr>
+          <p:cNvPr id="3" name="Notes Placeholder 2"/>
+          <p:cNvSpPr>
+            <a:spLocks noGrp="1"/>
+          </p:cNvSpPr>
+          <p:nvPr>
+            <p:ph type="body" idx="1"/>
+          </p:nvPr>
         </p:nvSpPr>
         <p:spPr/>
         <p:txBody>
           <a:bodyPr/>
           <a:lstStyle/>
-          <a:p>
-            <a:r>
-              <a:rPr lang="zh-CN"/>
-              <a:t>{}</a:t>
-            </a:r>
-          </a:p>
+          {}
         </p:txBody>
       </p:sp>
     </p:spTree>
@@ -352,7 +717,82 @@ impl NativeOoxml {
     <a:masterClrMapping/>
   </p:clrMapOvr>
 </p:notes>"#,
-            escape_xml(notes_text)
+            notes::markdown_to_paragraphs(notes_text)
+        )
+    }
+
+    /// 备注母版：定义幻灯片缩略图与备注正文占位符的位置
+    fn generate_notes_master(&self) -> String {
+        let margin = NOTES_WIDTH / 10;
+        let content_width = NOTES_WIDTH - margin * 2;
+        let image_top = NOTES_HEIGHT / 8;
+        let image_height = content_width * 9 / 16;
+        let body_top = image_top + image_height + margin / 2;
+        let body_height = NOTES_HEIGHT - body_top - image_top;
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<p:notesMaster xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
+  <p:cSld>
+    <p:spTree>
+      <p:nvGrpSpPr>
+        <p:cNvPr id="1" name=""/>
+        <p:cNvGrpSpPr/>
+        <p:nvPr/>
+      </p:nvGrpSpPr>
+      <p:grpSpPr/>
+      <p:sp>
+        <p:nvSpPr>
+          <p:cNvPr id="2" name="Slide Image Placeholder 1"/>
+          <p:cNvSpPr>
+            <a:spLocks noGrp="1" noRot="1" noChangeAspect="1"/>
+          </p:cNvSpPr>
+          <p:nvPr>
+            <p:ph type="sldImg" idx="2"/>
+          </p:nvPr>
+        </p:nvSpPr>
+        <p:spPr>
+          <a:xfrm>
+            <a:off x="{margin}" y="{image_top}"/>
+            <a:ext cx="{content_width}" cy="{image_height}"/>
+          </a:xfrm>
+          <a:prstGeom prst="rect">
+            <a:avLst/>
+          </a:prstGeom>
+          <a:noFill/>
+        </p:spPr>
+      </p:sp>
+      <p:sp>
+        <p:nvSpPr>
+          <p:cNvPr id="3" name="Notes Placeholder 2"/>
+          <p:cNvSpPr>
+            <a:spLocks noGrp="1"/>
+          </p:cNvSpPr>
+          <p:nvPr>
+            <p:ph type="body" sz="quarter" idx="3"/>
+          </p:nvPr>
+        </p:nvSpPr>
+        <p:spPr>
+          <a:xfrm>
+            <a:off x="{margin}" y="{body_top}"/>
+            <a:ext cx="{content_width}" cy="{body_height}"/>
+          </a:xfrm>
+          <a:prstGeom prst="rect">
+            <a:avLst/>
+          </a:prstGeom>
+        </p:spPr>
+        <p:txBody>
+          <a:bodyPr/>
+          <a:lstStyle/>
+          <a:p>
+            <a:endParaRPr lang="zh-CN"/>
+          </a:p>
+        </p:txBody>
+      </p:sp>
+    </p:spTree>
+  </p:cSld>
+  <p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2" accent1="accent1" accent2="accent2" accent3="accent3" accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink" folHlink="folHlink"/>
+</p:notesMaster>"#
         )
     }
 
@@ -376,8 +816,9 @@ impl NativeOoxml {
             .to_string()
     }
 
-    fn generate_slide_master(&self) -> String {
-        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+    fn generate_slide_master(&self, layout_rid: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <p:sldMaster xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main">
   <p:cSld>
     <p:spTree>
@@ -391,17 +832,573 @@ impl NativeOoxml {
   </p:cSld>
   <p:clrMap bg1="lt1" tx1="dk1" bg2="lt2" tx2="dk2" accent1="accent1" accent2="accent2" accent3="accent3" accent4="accent4" accent5="accent5" accent6="accent6" hlink="hlink" folHlink="folHlink"/>
   <p:sldLayoutIdLst>
-    <p:sldLayoutId id="2147483649" r:id="rId1"/>
+    <p:sldLayoutId id="2147483649" r:id="{}"/>
   </p:sldLayoutIdLst>
-</p:sldMaster>"#
-            .to_string()
+</p:sldMaster>"#,
+            layout_rid
+        )
     }
 }
 
-fn escape_xml(text: &str) -> String {
-    text.replace('&', "&amp;")
-        .replace('<', "&lt;")
-        .replace('>', "&gt;")
-        .replace('"', "&quot;")
-        .replace('\'', "&apos;")
+/// 解析页内跳转目标，返回幻灯片下标
+///
+/// 支持完整文件名（如 `slide_03_summary`）、文件名前缀（如 `slide_03`）与页码（如 `slide_3`、`3`）
+fn resolve_slide_link(fragment: &str, slides: &[Slide]) -> Option<usize> {
+    let fragment = fragment.trim().trim_end_matches(".svg");
+    if fragment.is_empty() {
+        return None;
+    }
+
+    if let Some(index) = slides.iter().position(|slide| {
+        slide.title == fragment
+            || slide
+                .title
+                .strip_prefix(fragment)
+                .is_some_and(|rest| rest.starts_with(['_', '-']))
+    }) {
+        return Some(index);
+    }
+
+    let number: usize = fragment
+        .strip_prefix("slide")
+        .unwrap_or(fragment)
+        .trim_start_matches(['_', '-'])
+        .parse()
+        .ok()?;
+    slides.iter().position(|slide| slide.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{AnimationSpec, TransitionSpec};
+    use crate::{NoProgress, PptxError};
+    use std::fs::File;
+    use std::io::Read;
+    use std::sync::Mutex;
+
+    const SAMPLE_SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" width="320" height="180" viewBox="0 0 320 180">
+        <rect x="0" y="0" width="320" height="180" fill="#1A73E8"/>
+    </svg>"##;
+
+    fn sample_slide() -> Slide {
+        Slide {
+            number: 1,
+            title: "01_封面".to_string(),
+            content: SlideContent::Svg(SAMPLE_SVG.to_string()),
+            notes: None,
+            transition: None,
+            animation: None,
+            section: None,
+            source_svg: None,
+        }
+    }
+
+    fn read_part(archive: &mut zip::ZipArchive<File>, name: &str) -> String {
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .expect("应存在该部件")
+            .read_to_string(&mut content)
+            .expect("部件应为 UTF-8 文本");
+        content
+    }
+
+    #[tokio::test]
+    async fn test_export_svg_blip_mode() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+        let config = PptxConfig {
+            width: 320,
+            height: 180,
+            ..PptxConfig::default()
+        };
+
+        NativeOoxml::new()
+            .with_svg_mode(SvgMode::SvgBlip)
+            .export(
+                &[sample_slide()],
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        assert!(archive.by_name("ppt/media/image1.png").is_ok());
+        assert!(archive.by_name("ppt/media/image2.svg").is_ok());
+
+        let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+        assert!(slide.contains(r#"<a:blip r:embed="rId2">"#));
+        assert!(slide.contains(r#"r:embed="rId3"/>"#));
+
+        let rels = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+        assert!(rels.contains(r#"Target="../media/image2.svg""#));
+
+        let content_types = read_part(&mut archive, "[Content_Types].xml");
+        assert!(content_types.contains(r#"Extension="svg" ContentType="image/svg+xml""#));
+    }
+
+    #[tokio::test]
+    async fn test_export_shapes_mode_has_no_media() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+
+        NativeOoxml::new()
+            .export(
+                &[sample_slide()],
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        assert!(archive.by_name("ppt/media/image1.svg").is_err());
+        assert!(archive.by_name("ppt/media/image1.png").is_err());
+
+        let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+        assert!(slide.contains(r#"<a:prstGeom prst="rect">"#));
+
+        // 形状模式不写入图片，也不声明图片关系与内容类型
+        let rels = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+        assert!(!rels.contains("relationships/image"));
+        let content_types = read_part(&mut archive, "[Content_Types].xml");
+        assert!(!content_types.contains(r#"Extension="png""#));
+    }
+
+    /// 记录进度并在指定页数后取消
+    struct CancelAfter {
+        pages: usize,
+        cancel: CancellationToken,
+        progress: Mutex<Vec<(usize, usize)>>,
+    }
+
+    impl ProgressSink for CancelAfter {
+        fn report_progress(&self, current: usize, total: usize, _message: String) {
+            self.progress.lock().unwrap().push((current, total));
+            if current == self.pages {
+                self.cancel.cancel();
+            }
+        }
+
+        fn log(&self, _level: &str, _message: String) {}
+    }
+
+    #[tokio::test]
+    async fn test_export_progress_and_cancel() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+        let slides: Vec<Slide> = (1..=3)
+            .map(|number| Slide {
+                number,
+                ..sample_slide()
+            })
+            .collect();
+
+        let cancel = CancellationToken::new();
+        let sink = CancelAfter {
+            pages: 2,
+            cancel: cancel.clone(),
+            progress: Mutex::new(Vec::new()),
+        };
+        let result = NativeOoxml::new()
+            .export(&slides, &output, &PptxConfig::default(), &sink, &cancel)
+            .await;
+
+        // 第 2 页上报后取消，第 3 页开始前停止且不写出文件
+        assert!(matches!(result, Err(PptxError::Cancelled)));
+        assert_eq!(*sink.progress.lock().unwrap(), vec![(1, 3), (2, 3)]);
+        assert!(!output.exists());
+    }
+
+    /// 记录日志
+    #[derive(Default)]
+    struct RecordLogs(Mutex<Vec<(String, String)>>);
+
+    impl ProgressSink for RecordLogs {
+        fn report_progress(&self, _current: usize, _total: usize, _message: String) {}
+
+        fn log(&self, level: &str, message: String) {
+            self.0.lock().unwrap().push((level.to_string(), message));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_invalid_chart_as_shapes() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+        let slide = Slide {
+            number: 4,
+            content: SlideContent::Svg(
+                r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"><g data-chart="column"><metadata>{"categories": [</metadata><rect x="100" y="100" width="200" height="300" fill="#1A73E8"/></g></svg>"##
+                    .to_string(),
+            ),
+            ..sample_slide()
+        };
+
+        // 单个图表数据无效不影响整份导出，分组按矢量图形输出
+        let sink = RecordLogs::default();
+        NativeOoxml::new()
+            .export(
+                &[slide],
+                &output,
+                &PptxConfig::default(),
+                &sink,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+        assert!(slide.contains(r#"<a:prstGeom prst="rect">"#));
+        assert!(!slide.contains("graphicFrame"));
+
+        let logs = sink.0.lock().unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].0, "warn");
+        assert!(logs[0].1.starts_with("第 4 页: column 图表数据无效"));
+    }
+
+    #[tokio::test]
+    async fn test_export_deduplicates_shared_images() {
+        use base64::Engine;
+
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+
+        // 每页都引用同一个 logo
+        let logo = base64::engine::general_purpose::STANDARD
+            .encode(crate::svg_to_png(SAMPLE_SVG, 32, 18).expect("渲染应成功"));
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 320 180"><image x="10" y="10" width="32" height="18" xlink:href="data:image/png;base64,{}"/></svg>"#,
+            logo
+        );
+        let slides: Vec<Slide> = (1..=3)
+            .map(|number| Slide {
+                number,
+                content: SlideContent::Svg(svg.clone()),
+                ..sample_slide()
+            })
+            .collect();
+
+        let summary = NativeOoxml::new()
+            .export(
+                &slides,
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        assert_eq!(summary.slide_count, 3);
+        assert_eq!(summary.media_count, 1);
+        assert!(summary.media_bytes_saved > 0);
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        assert!(archive.by_name("ppt/media/image2.png").is_err());
+        for number in 1..=3 {
+            let rels = read_part(
+                &mut archive,
+                &format!("ppt/slides/_rels/slide{}.xml.rels", number),
+            );
+            assert!(rels.contains(r#"Target="../media/image1.png""#));
+
+            let slide = read_part(&mut archive, &format!("ppt/slides/slide{}.xml", number));
+            assert!(slide.contains(r#"<a:blip r:embed="rId2">"#));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_hyperlinks() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+
+        let navigation = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 320 180">
+            <a href="#slide_02"><rect x="0" y="0" width="100" height="40" fill="#1A73E8"/><text x="10" y="30" font-size="20">详情</text></a>
+            <a href="https://example.com/docs"><rect x="0" y="60" width="100" height="40" fill="#34A853"/></a>
+            <a href="#slide_09"><rect x="0" y="120" width="100" height="40" fill="#EA4335"/></a>
+        </svg>"##;
+        let slides = [
+            Slide {
+                title: "slide_01_navigation".to_string(),
+                content: SlideContent::Svg(navigation.to_string()),
+                ..sample_slide()
+            },
+            Slide {
+                number: 2,
+                title: "slide_02_detail".to_string(),
+                ..sample_slide()
+            },
+        ];
+
+        NativeOoxml::new()
+            .export(
+                &slides,
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        let rels = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+        assert!(rels.contains(r#"Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide" Target="slide2.xml""#));
+        assert!(rels.contains(r#"Target="https://example.com/docs" TargetMode="External""#));
+
+        // 跳转链接附加到链接内的每个形状，找不到目标页的链接被移除
+        let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+        assert_eq!(
+            slide
+                .matches(r#"<a:hlinkClick r:id="rId2" action="ppaction://hlinksldjump"/>"#)
+                .count(),
+            2
+        );
+        assert!(slide.contains(r#"<a:hlinkClick r:id="rId3"/>"#));
+        assert!(!slide.contains("__link_"));
+    }
+
+    #[tokio::test]
+    async fn test_export_native_charts() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 640 360">
+            <g id="revenue" data-chart="column">
+                <metadata>{"categories": ["Q1", "Q2"], "series": [{"name": "营收", "values": [120, 150]}]}</metadata>
+                <rect x="40" y="100" width="40" height="120" fill="#1A73E8"/>
+                <rect x="120" y="70" width="40" height="150" fill="#1A73E8"/>
+            </g>
+            <g transform="translate(320 0)" data-chart="pie" data-chart-bounds="0 20 300 300">
+                <metadata>{"categories": ["A", "B"], "series": [{"name": "占比", "values": [60, 40]}]}</metadata>
+            </g>
+        </svg>"##;
+        let slides = [Slide {
+            content: SlideContent::Svg(svg.to_string()),
+            ..sample_slide()
+        }];
+
+        NativeOoxml::new()
+            .export(
+                &slides,
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        let slide = read_part(&mut archive, "ppt/slides/slide1.xml");
+        assert_eq!(slide.matches("<p:graphicFrame>").count(), 2);
+        assert!(slide.contains(r#"name="revenue""#));
+        assert!(slide.contains(r#"r:id="rId2"/>"#));
+        // 缺省区域取分组内图形的范围（幻灯片画布为 viewBox 的 2 倍）
+        assert!(
+            slide.contains(r#"<a:off x="762000" y="1333500"/><a:ext cx="2286000" cy="2857500"/>"#)
+        );
+        assert!(
+            slide.contains(r#"<a:off x="6096000" y="381000"/><a:ext cx="5715000" cy="5715000"/>"#)
+        );
+        // 除画布外的标题占位符外没有其他形状
+        assert_eq!(slide.matches("<p:sp>").count(), 1);
+        assert!(slide.contains(r#"<p:ph type="title"/>"#));
+
+        let rels = read_part(&mut archive, "ppt/slides/_rels/slide1.xml.rels");
+        assert!(rels.contains(r#"Target="../charts/chart2.xml""#));
+        let chart_rels = read_part(&mut archive, "ppt/charts/_rels/chart1.xml.rels");
+        assert!(chart_rels.contains(r#"Target="../embeddings/Microsoft_Excel_Worksheet1.xlsx""#));
+        assert!(read_part(&mut archive, "ppt/charts/chart2.xml").contains("<c:pieChart>"));
+        assert!(archive
+            .by_name("ppt/embeddings/Microsoft_Excel_Worksheet2.xlsx")
+            .is_ok());
+
+        let content_types = read_part(&mut archive, "[Content_Types].xml");
+        assert!(content_types.contains(r#"<Override PartName="/ppt/charts/chart1.xml" ContentType="application/vnd.openxmlformats-officedocument.drawingml.chart+xml"/>"#));
+        assert!(content_types.contains(r#"<Default Extension="xlsx" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"/>"#));
+    }
+
+    #[tokio::test]
+    async fn test_presentation_section_list() {
+        let slides = [
+            Slide {
+                section: Some("开场".to_string()),
+                ..sample_slide()
+            },
+            Slide {
+                number: 2,
+                section: Some("方案 & 计划".to_string()),
+                ..sample_slide()
+            },
+            Slide {
+                number: 3,
+                section: Some("方案 & 计划".to_string()),
+                ..sample_slide()
+            },
+        ];
+
+        let (package, _) = NativeOoxml::new()
+            .build_package(
+                &slides,
+                "deck",
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("组装应成功");
+        let presentation = package
+            .part_data("ppt/presentation.xml")
+            .map(|data| String::from_utf8_lossy(data).into_owned())
+            .expect("应存在 presentation.xml");
+
+        assert!(presentation
+            .contains(r#"<p14:section name="开场" id="{00000001-5EC7-4000-8000-000000000000}">"#));
+        assert!(presentation.contains(r#"<p14:section name="方案 &amp; 计划""#));
+        assert!(presentation.contains(r#"<p14:sldId id="258"/>"#));
+        assert!(presentation.find("<p:notesSz") < presentation.find("<p:extLst>"));
+
+        let (package, _) = NativeOoxml::new()
+            .build_package(
+                &[sample_slide()],
+                "deck",
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("组装应成功");
+        assert!(
+            !String::from_utf8_lossy(package.part_data("ppt/presentation.xml").unwrap())
+                .contains("sectionLst")
+        );
+    }
+
+    #[test]
+    fn test_resolve_slide_link() {
+        let slides = [
+            Slide {
+                title: "slide_01_cover".to_string(),
+                ..sample_slide()
+            },
+            Slide {
+                number: 2,
+                title: "02_目录".to_string(),
+                ..sample_slide()
+            },
+        ];
+
+        assert_eq!(resolve_slide_link("slide_01_cover", &slides), Some(0));
+        assert_eq!(resolve_slide_link("slide_01", &slides), Some(0));
+        assert_eq!(resolve_slide_link("slide_2", &slides), Some(1));
+        assert_eq!(resolve_slide_link("02_目录.svg", &slides), Some(1));
+        assert_eq!(resolve_slide_link("slide_05", &slides), None);
+        assert_eq!(resolve_slide_link("top", &slides), None);
+    }
+
+    #[tokio::test]
+    async fn test_package_parts_without_notes() {
+        let (package, _) = NativeOoxml::new()
+            .build_package(
+                &[sample_slide()],
+                "deck",
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("组装应成功");
+
+        for part in [
+            "ppt/theme/theme1.xml",
+            "docProps/core.xml",
+            "docProps/app.xml",
+            "ppt/presProps.xml",
+        ] {
+            assert!(package.has_part(part), "缺少部件 {}", part);
+        }
+        assert!(!package.has_part("ppt/notesMasters/notesMaster1.xml"));
+        assert!(!package.content_types_xml().contains("notesSlide"));
+        assert!(package
+            .relationships("ppt/slides/slide1.xml")
+            .iter()
+            .all(|rel| rel.rel_type != rel_types::NOTES_SLIDE));
+        assert!(package.check_relationships().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_package_notes_master_and_rels() {
+        let mut slide = sample_slide();
+        slide.notes = Some("开场白".to_string());
+        let slides = [slide, sample_slide()];
+
+        let (package, _) = NativeOoxml::new()
+            .build_package(
+                &slides,
+                "deck",
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("组装应成功");
+
+        assert!(package.has_part("ppt/notesMasters/notesMaster1.xml"));
+        assert!(package.has_part("ppt/notesSlides/notesSlide1.xml"));
+        assert!(!package.has_part("ppt/notesSlides/notesSlide2.xml"));
+        assert!(package
+            .relationships("ppt/notesSlides/notesSlide1.xml")
+            .iter()
+            .any(|rel| rel.rel_type == rel_types::NOTES_MASTER));
+        assert!(package
+            .relationships("ppt/slideMasters/slideMaster1.xml")
+            .iter()
+            .any(|rel| rel.target == "../theme/theme1.xml"));
+        assert!(package.check_relationships().is_ok());
+    }
+
+    #[test]
+    fn test_generate_slide_transition_and_animation() {
+        let config = PptxConfig {
+            animation: Some(AnimationSpec {
+                effect: "fade".to_string(),
+                duration: 0.5,
+                delay: 0.0,
+            }),
+            ..PptxConfig::default()
+        };
+
+        let mut slide = sample_slide();
+        slide.transition = Some(TransitionSpec {
+            effect: "push".to_string(),
+            duration: 1.0,
+            advance_after: None,
+        });
+
+        let backend = NativeOoxml::new();
+        let shapes =
+            drawingml::svg_to_shapes(SAMPLE_SVG, config.width, config.height).expect("转换应成功");
+        let xml = backend.generate_slide(&slide, &config, &shapes);
+        assert!(xml.contains(r#"<p:transition spd="slow"><p:push dir="r"/></p:transition>"#));
+        assert!(xml.contains(r#"<p:spTgt spid="2"/>"#));
+        assert!(xml.find("</p:clrMapOvr>") < xml.find("<p:transition"));
+        assert!(xml.find("<p:transition") < xml.find("<p:timing>"));
+
+        let disabled = PptxConfig {
+            enable_transitions: false,
+            ..PptxConfig::default()
+        };
+        let xml = backend.generate_slide(&sample_slide(), &disabled, &shapes);
+        assert!(!xml.contains("<p:transition"));
+        assert!(!xml.contains("<p:timing>"));
+    }
 }
@@ -0,0 +1,620 @@
+// ODP 后端
+// 生成 OpenDocument 演示文稿（content.xml、styles.xml、meta.xml 与 manifest.xml），
+// 每页幻灯片为整页图片（SVG 附带 PNG 后备图），包含演讲备注与切换效果
+
+use crate::animation::{self, TransitionSpec};
+use crate::media::inline_images;
+use crate::notes;
+use crate::{
+    accessibility, escape_xml, run_blocking, slide_checkpoint, svg_to_png, ExportFuture,
+    ExportSummary, PptxBackend, PptxConfig, ProgressSink, Result, Slide, SlideContent,
+};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tokio_util::sync::CancellationToken;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// 写入 meta.xml 的生成程序名称
+const GENERATOR: &str = "Agents for PPT";
+
+/// ODP 的 MIME 类型（必须作为第一个、不压缩的 mimetype 文件）
+const MIME_TYPE: &str = "application/vnd.oasis.opendocument.presentation";
+
+/// ODF 版本
+const ODF_VERSION: &str = "1.3";
+
+/// 像素到厘米的换算（96 DPI）
+const CM_PER_PIXEL: f32 = 2.54 / 96.0;
+
+/// content.xml 与 styles.xml 共用的命名空间声明
+const NAMESPACES: &str = r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0" xmlns:presentation="urn:oasis:names:tc:opendocument:xmlns:presentation:1.0" xmlns:smil="urn:oasis:names:tc:opendocument:xmlns:smil-compatible:1.0""#;
+
+/// 备注页尺寸（厘米，纵向 A4）
+const NOTES_WIDTH_CM: f32 = 21.0;
+const NOTES_HEIGHT_CM: f32 = 29.7;
+
+/// ODP 后端
+///
+/// 默认嵌入 SVG 原图并附带 PNG 后备图（不支持 SVG 的阅读器显示 PNG），
+/// 也可以只嵌入 PNG；SVG 中以相对路径引用的图片按 `base_dir` 解析后内嵌
+pub struct Odp {
+    embed_svg: bool,
+    base_dir: Option<PathBuf>,
+}
+
+impl Odp {
+    pub fn new() -> Self {
+        Self {
+            embed_svg: true,
+            base_dir: None,
+        }
+    }
+
+    /// 设置解析相对图片路径的目录（通常为项目的 `svg_final` 目录）
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+
+    /// 设置是否嵌入 SVG 原图（否则只嵌入 PNG）
+    pub fn with_embed_svg(mut self, embed_svg: bool) -> Self {
+        self.embed_svg = embed_svg;
+        self
+    }
+}
+
+impl Default for Odp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PptxBackend for Odp {
+    fn export<'a>(
+        &'a self,
+        slides: &'a [Slide],
+        output_path: &'a Path,
+        config: &'a PptxConfig,
+        progress: &'a dyn ProgressSink,
+        cancel: &'a CancellationToken,
+    ) -> ExportFuture<'a> {
+        Box::pin(async move {
+            let title = output_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Presentation");
+
+            let mut pictures = Vec::new();
+            let mut pages = String::new();
+            for (index, slide) in slides.iter().enumerate() {
+                slide_checkpoint(slides, index, progress, cancel).await?;
+
+                // 图片内嵌与 SVG 渲染在阻塞线程池中执行
+                let (source, embed_svg, base_dir) =
+                    (slide.clone(), self.embed_svg, self.base_dir.clone());
+                let (width, height) = (config.width, config.height);
+                let images = run_blocking(move || {
+                    slide_pictures(&source, embed_svg, base_dir.as_deref(), width, height)
+                })
+                .await?;
+                pages.push_str(&page_xml(
+                    slide,
+                    &images
+                        .iter()
+                        .map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>(),
+                    config,
+                ));
+                pictures.extend(images);
+            }
+
+            let media_count = pictures.len();
+            let parts = [
+                ("content.xml", content_xml(slides, &pages, config)),
+                ("styles.xml", styles_xml(config)),
+                ("meta.xml", meta_xml(title)),
+            ];
+            let output = output_path.to_path_buf();
+            run_blocking(move || write_package(&output, &parts, &pictures)).await?;
+
+            Ok(ExportSummary {
+                slide_count: slides.len(),
+                media_count,
+                media_bytes_saved: 0,
+                accessibility: accessibility::audit_slides(slides)?,
+            })
+        })
+    }
+
+    fn name(&self) -> &str {
+        "odp"
+    }
+
+    fn is_available(&self) -> bool {
+        true // 纯 Rust 实现，总是可用
+    }
+}
+
+/// 写出 ODP 压缩包：mimetype、XML 部件、图片与清单
+fn write_package(
+    path: &Path,
+    parts: &[(&str, String)],
+    pictures: &[(String, Vec<u8>)],
+) -> Result<()> {
+    let mut zip = ZipWriter::new(File::create(path)?);
+    let stored = FileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .unix_permissions(0o644);
+    let deflated = stored.compression_method(CompressionMethod::Deflated);
+
+    // mimetype 必须是第一个条目且不压缩，供识别文件类型
+    zip.start_file("mimetype", stored)?;
+    zip.write_all(MIME_TYPE.as_bytes())?;
+
+    for (name, xml) in parts {
+        zip.start_file(*name, deflated)?;
+        zip.write_all(xml.as_bytes())?;
+    }
+
+    for (name, data) in pictures {
+        zip.start_file(name.as_str(), deflated)?;
+        zip.write_all(data)?;
+    }
+
+    zip.start_file("META-INF/manifest.xml", deflated)?;
+    zip.write_all(manifest_xml(pictures).as_bytes())?;
+    zip.finish()?;
+    Ok(())
+}
+
+/// 幻灯片的图片（按优先顺序：SVG 原图在前，PNG 后备图在后）
+///
+/// 相对路径图片先内嵌，包内的 SVG 与渲染出的 PNG 后备图都不依赖项目目录
+fn slide_pictures(
+    slide: &Slide,
+    embed_svg: bool,
+    base_dir: Option<&Path>,
+    width: u32,
+    height: u32,
+) -> Result<Vec<(String, Vec<u8>)>> {
+    let png_name = format!("Pictures/slide{}.png", slide.number);
+    match &slide.content {
+        SlideContent::Svg(svg) => {
+            let svg = match base_dir {
+                Some(base_dir) => inline_images(svg, base_dir)?,
+                None => svg.clone(),
+            };
+            let png = svg_to_png(&svg, width, height)?;
+            if embed_svg {
+                Ok(vec![
+                    (
+                        format!("Pictures/slide{}.svg", slide.number),
+                        svg.into_bytes(),
+                    ),
+                    (png_name, png),
+                ])
+            } else {
+                Ok(vec![(png_name, png)])
+            }
+        }
+        SlideContent::Png(data) => Ok(vec![(png_name, data.clone())]),
+    }
+}
+
+fn cm(pixels: f32) -> String {
+    format!("{:.3}cm", pixels * CM_PER_PIXEL)
+}
+
+/// 一页幻灯片：整页图片框、标题（替代文字）与备注页
+fn page_xml(slide: &Slide, images: &[&str], config: &PptxConfig) -> String {
+    let title = escape_xml(&accessibility::readable_title(slide));
+    let images: String = images
+        .iter()
+        .map(|href| {
+            format!(
+                r#"<draw:image xlink:href="{}" xlink:type="simple" xlink:show="embed" xlink:actuate="onLoad"/>"#,
+                href
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<draw:page draw:name="page{number}" draw:style-name="dp{number}" draw:master-page-name="Default"><draw:frame draw:name="{title}" svg:x="0cm" svg:y="0cm" svg:width="{width}" svg:height="{height}">{images}<svg:title>{title}</svg:title></draw:frame>{notes}</draw:page>"#,
+        number = slide.number,
+        title = title,
+        width = cm(config.width as f32),
+        height = cm(config.height as f32),
+        images = images,
+        notes = notes_xml(slide, config),
+    )
+}
+
+/// 备注页：上方为幻灯片缩略图，下方为备注文字
+fn notes_xml(slide: &Slide, config: &PptxConfig) -> String {
+    let Some(markdown) = &slide.notes else {
+        return String::new();
+    };
+
+    let paragraphs: String = notes::markdown_to_lines(markdown)
+        .iter()
+        .map(|line| {
+            let indent = if line.level > 0 {
+                format!(r#"<text:s text:c="{}"/>"#, line.level * 4)
+            } else {
+                String::new()
+            };
+            format!("<text:p>{}{}</text:p>", indent, escape_xml(&line.text))
+        })
+        .collect();
+
+    // 缩略图按幻灯片长宽比放入备注页上半部分（水平居中）
+    let margin = 2.0;
+    let scale = ((NOTES_WIDTH_CM - margin * 2.0) / config.width as f32)
+        .min((NOTES_HEIGHT_CM / 2.0 - margin * 1.5) / config.height as f32);
+    let thumbnail_width = config.width as f32 * scale;
+    let thumbnail_height = config.height as f32 * scale;
+    let notes_top = margin * 1.5 + thumbnail_height;
+
+    format!(
+        r#"<presentation:notes><draw:page-thumbnail presentation:class="page" draw:page-number="{}" svg:x="{:.3}cm" svg:y="{margin:.3}cm" svg:width="{:.3}cm" svg:height="{:.3}cm"/><draw:frame presentation:class="notes" svg:x="{margin:.3}cm" svg:y="{:.3}cm" svg:width="{:.3}cm" svg:height="{:.3}cm"><draw:text-box>{}</draw:text-box></draw:frame></presentation:notes>"#,
+        slide.number,
+        (NOTES_WIDTH_CM - thumbnail_width) / 2.0,
+        thumbnail_width,
+        thumbnail_height,
+        notes_top,
+        NOTES_WIDTH_CM - margin * 2.0,
+        NOTES_HEIGHT_CM - notes_top - margin,
+        paragraphs,
+        margin = margin,
+    )
+}
+
+/// 切换效果对应的 SMIL 过渡类型与子类型
+fn smil_transition(effect: &str) -> (&'static str, &'static str) {
+    match animation::find_transition(effect).key {
+        "push" => ("pushWipe", "fromRight"),
+        "wipe" => ("barWipe", "leftToRight"),
+        "split" => ("barnDoorWipe", "vertical"),
+        "reveal" => ("diagonalWipe", "topLeft"),
+        "cover" => ("slideWipe", "fromRight"),
+        _ => ("fade", "crossfade"),
+    }
+}
+
+/// 每页的 drawing-page 样式：切换效果与自动翻页
+fn page_style_xml(number: usize, transition: Option<&TransitionSpec>) -> String {
+    let properties = match transition {
+        Some(spec) => {
+            let (smil_type, smil_subtype) = smil_transition(&spec.effect);
+            let speed = match animation::duration_to_speed(spec.duration) {
+                "med" => "medium",
+                speed => speed,
+            };
+            let advance = spec
+                .advance_after
+                .map(|secs| {
+                    format!(
+                        r#" presentation:transition-type="automatic" presentation:duration="PT{}S""#,
+                        secs
+                    )
+                })
+                .unwrap_or_default();
+            format!(
+                r#"<style:drawing-page-properties presentation:transition-speed="{}" smil:type="{}" smil:subtype="{}"{}/>"#,
+                speed, smil_type, smil_subtype, advance
+            )
+        }
+        None => "<style:drawing-page-properties/>".to_string(),
+    };
+
+    format!(
+        r#"<style:style style:name="dp{}" style:family="drawing-page">{}</style:style>"#,
+        number, properties
+    )
+}
+
+fn content_xml(slides: &[Slide], pages: &str, config: &PptxConfig) -> String {
+    let styles: String = slides
+        .iter()
+        .map(|slide| page_style_xml(slide.number, config.slide_transition(slide).as_ref()))
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content {} office:version="{}"><office:automatic-styles>{}</office:automatic-styles><office:body><office:presentation>{}</office:presentation></office:body></office:document-content>"#,
+        NAMESPACES, ODF_VERSION, styles, pages
+    )
+}
+
+/// 页面尺寸取画布尺寸，边距为 0
+fn styles_xml(config: &PptxConfig) -> String {
+    let orientation = if config.width >= config.height {
+        "landscape"
+    } else {
+        "portrait"
+    };
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles {} office:version="{}"><office:automatic-styles><style:page-layout style:name="PM1"><style:page-layout-properties fo:margin-top="0cm" fo:margin-bottom="0cm" fo:margin-left="0cm" fo:margin-right="0cm" fo:page-width="{}" fo:page-height="{}" style:print-orientation="{}"/></style:page-layout><style:style style:name="Mdp1" style:family="drawing-page"><style:drawing-page-properties draw:fill="none"/></style:style></office:automatic-styles><office:master-styles><style:master-page style:name="Default" style:page-layout-name="PM1" draw:style-name="Mdp1"/></office:master-styles></office:document-styles>"#,
+        NAMESPACES,
+        ODF_VERSION,
+        cm(config.width as f32),
+        cm(config.height as f32),
+        orientation
+    )
+}
+
+fn meta_xml(title: &str) -> String {
+    let now = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ");
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/" office:version="{}"><office:meta><meta:generator>{}</meta:generator><dc:title>{}</dc:title><meta:creation-date>{}</meta:creation-date><dc:date>{}</dc:date></office:meta></office:document-meta>"#,
+        ODF_VERSION,
+        GENERATOR,
+        escape_xml(title),
+        now,
+        now
+    )
+}
+
+fn manifest_xml(pictures: &[(String, Vec<u8>)]) -> String {
+    let entry = |path: &str, media_type: &str| {
+        format!(
+            r#"<manifest:file-entry manifest:full-path="{}" manifest:media-type="{}"/>"#,
+            path, media_type
+        )
+    };
+
+    let mut entries = format!(
+        r#"<manifest:file-entry manifest:full-path="/" manifest:version="{}" manifest:media-type="{}"/>"#,
+        ODF_VERSION, MIME_TYPE
+    );
+    for part in ["content.xml", "styles.xml", "meta.xml"] {
+        entries.push_str(&entry(part, "text/xml"));
+    }
+    for (name, _) in pictures {
+        let media_type = if name.ends_with(".svg") {
+            "image/svg+xml"
+        } else {
+            "image/png"
+        };
+        entries.push_str(&entry(name, media_type));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="{}">{}</manifest:manifest>"#,
+        ODF_VERSION, entries
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoProgress;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+    use std::io::Read;
+
+    fn read_entry(archive: &mut zip::ZipArchive<File>, name: &str) -> String {
+        let mut content = String::new();
+        archive
+            .by_name(name)
+            .unwrap_or_else(|_| panic!("缺少 {}", name))
+            .read_to_string(&mut content)
+            .expect("读取失败");
+        content
+    }
+
+    /// 读取所有指定元素的属性值
+    fn attribute_values(xml: &str, element: &[u8], attribute: &[u8]) -> Vec<String> {
+        let mut reader = Reader::from_str(xml);
+        let mut values = Vec::new();
+        loop {
+            match reader.read_event().expect("XML 格式错误") {
+                Event::Eof => break,
+                Event::Start(start) | Event::Empty(start) if start.name().as_ref() == element => {
+                    values.extend(
+                        start
+                            .attributes()
+                            .flatten()
+                            .filter(|attr| attr.key.as_ref() == attribute)
+                            .map(|attr| String::from_utf8_lossy(&attr.value).into_owned()),
+                    );
+                }
+                _ => {}
+            }
+        }
+        values
+    }
+
+    #[tokio::test]
+    async fn test_export_odp() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let output = dir.path().join("报告.odp");
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1024 768"><rect width="1024" height="768" fill="#1A73E8"/></svg>"##;
+        let slides = vec![
+            Slide {
+                number: 1,
+                title: "01_封面".to_string(),
+                content: SlideContent::Svg(svg.to_string()),
+                notes: Some("# 开场\n\n- 介绍 <团队>".to_string()),
+                transition: None,
+                animation: None,
+                section: None,
+                source_svg: None,
+            },
+            Slide {
+                number: 2,
+                title: "02_总结".to_string(),
+                content: SlideContent::Png(svg_to_png(svg, 1024, 768).expect("渲染应成功")),
+                notes: None,
+                transition: Some(TransitionSpec {
+                    effect: "push".to_string(),
+                    duration: 1.0,
+                    advance_after: Some(3.0),
+                }),
+                animation: None,
+                section: None,
+                source_svg: None,
+            },
+        ];
+        let config = PptxConfig {
+            width: 1024,
+            height: 768,
+            ..PptxConfig::default()
+        };
+
+        let summary = Odp::new()
+            .export(
+                &slides,
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        assert_eq!(summary.media_count, 3);
+
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output).expect("打开文件失败")).expect("zip 格式错误");
+
+        // mimetype 为第一个且未压缩的条目
+        {
+            let mut mimetype = archive.by_index(0).expect("缺少条目");
+            assert_eq!(mimetype.name(), "mimetype");
+            assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+            let mut content = String::new();
+            mimetype.read_to_string(&mut content).expect("读取失败");
+            assert_eq!(content, MIME_TYPE);
+        }
+
+        let content = read_entry(&mut archive, "content.xml");
+        assert_eq!(
+            attribute_values(&content, b"draw:page", b"draw:name"),
+            vec!["page1", "page2"]
+        );
+        // SVG 在前、PNG 后备图在后；PNG 幻灯片只有 PNG
+        let images = attribute_values(&content, b"draw:image", b"xlink:href");
+        assert_eq!(
+            images,
+            vec![
+                "Pictures/slide1.svg",
+                "Pictures/slide1.png",
+                "Pictures/slide2.png"
+            ]
+        );
+        assert!(content.contains("<svg:title>封面</svg:title>"));
+        assert!(content.contains("<text:p>开场</text:p>"));
+        assert!(content.contains("<text:p>• 介绍 &lt;团队&gt;</text:p>"));
+        assert_eq!(content.matches("<presentation:notes>").count(), 1);
+        assert!(content.contains(r#"smil:type="fade" smil:subtype="crossfade""#));
+        assert!(content.contains(
+            r#"presentation:transition-speed="slow" smil:type="pushWipe" smil:subtype="fromRight" presentation:transition-type="automatic" presentation:duration="PT3S""#
+        ));
+
+        let styles = read_entry(&mut archive, "styles.xml");
+        assert_eq!(
+            attribute_values(&styles, b"style:page-layout-properties", b"fo:page-width"),
+            vec!["27.093cm"]
+        );
+        assert_eq!(
+            attribute_values(&styles, b"style:page-layout-properties", b"fo:page-height"),
+            vec!["20.320cm"]
+        );
+
+        // 清单列出所有图片，且每个条目都存在
+        let manifest = read_entry(&mut archive, "META-INF/manifest.xml");
+        let paths = attribute_values(&manifest, b"manifest:file-entry", b"manifest:full-path");
+        for image in &images {
+            assert!(paths.contains(image));
+        }
+        for path in paths.iter().filter(|path| path.as_str() != "/") {
+            assert!(archive.by_name(path).is_ok(), "缺少 {}", path);
+        }
+        assert!(read_entry(&mut archive, "meta.xml").contains("<dc:title>报告</dc:title>"));
+
+        // 只嵌入 PNG
+        Odp::new()
+            .with_embed_svg(false)
+            .export(
+                &slides,
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output).expect("打开文件失败")).expect("zip 格式错误");
+        assert!(archive.by_name("Pictures/slide1.svg").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_export_odp_inlines_images() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let svg_dir = dir.path().join("svg_final");
+        std::fs::create_dir_all(dir.path().join("images")).expect("创建目录失败");
+        std::fs::create_dir(&svg_dir).expect("创建目录失败");
+
+        // 纯红色图片铺满画布，PNG 后备图中应能看到
+        let mut logo = tiny_skia::Pixmap::new(4, 4).expect("创建位图失败");
+        logo.fill(tiny_skia::Color::from_rgba8(255, 0, 0, 255));
+        logo.save_png(dir.path().join("images/logo.png"))
+            .expect("写入图片失败");
+
+        let slides = vec![Slide {
+            number: 1,
+            title: "01_封面".to_string(),
+            content: SlideContent::Svg(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 40 30"><image href="../images/logo.png" width="40" height="30" preserveAspectRatio="none"/></svg>"#
+                    .to_string(),
+            ),
+            notes: None,
+            transition: None,
+            animation: None,
+            section: None,
+            source_svg: None,
+        }];
+        let config = PptxConfig {
+            width: 40,
+            height: 30,
+            ..PptxConfig::default()
+        };
+        let output = dir.path().join("deck.odp");
+
+        Odp::new()
+            .with_base_dir(&svg_dir)
+            .export(
+                &slides,
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output).expect("打开文件失败")).expect("zip 格式错误");
+        let svg = read_entry(&mut archive, "Pictures/slide1.svg");
+        assert!(svg.contains("data:image/png;base64,"));
+        assert!(!svg.contains("../images/logo.png"));
+
+        let mut png = Vec::new();
+        archive
+            .by_name("Pictures/slide1.png")
+            .expect("缺少 PNG 后备图")
+            .read_to_end(&mut png)
+            .expect("读取失败");
+        let pixmap = tiny_skia::Pixmap::decode_png(&png).expect("PNG 格式错误");
+        let center = pixmap.pixel(20, 15).expect("缺少像素");
+        assert_eq!((center.red(), center.green(), center.blue()), (255, 0, 0));
+    }
+}
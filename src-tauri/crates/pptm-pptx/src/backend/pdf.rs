@@ -0,0 +1,727 @@
+// PDF 后端
+// 使用 resvg 将每页幻灯片渲染为高分辨率位图写入 PDF 页面，无需外部工具；
+// 可选在幻灯片后附加备注页（缩略图 + 备注文字）
+
+use crate::notes::{self, NoteLine};
+use crate::{
+    accessibility, escape_xml, pixmap_to_rgb, render_svg, run_blocking, slide_checkpoint,
+    ExportFuture, ExportSummary, PptxBackend, PptxConfig, PptxError, ProgressSink, Result, Slide,
+    SlideContent,
+};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::Write;
+use std::path::Path;
+use tokio_util::sync::CancellationToken;
+
+/// 写入 PDF 文档信息的生成程序名称
+const PRODUCER: &str = "Agents for PPT";
+
+/// 像素到 PDF 点的换算（96 DPI → 72 DPI）
+const POINTS_PER_PIXEL: f32 = 0.75;
+
+/// 渲染倍率的范围
+const MIN_SCALE: f32 = 1.0;
+const MAX_SCALE: f32 = 4.0;
+
+/// 备注页默认字体（主题未指定字体时使用）
+const NOTES_FONT_FAMILY: &str =
+    "'Microsoft YaHei', 'PingFang SC', 'Noto Sans CJK SC', 'Source Han Sans SC', sans-serif";
+
+/// PDF 后端
+///
+/// 每页幻灯片渲染为 `scale` 倍分辨率的位图（默认 2 倍，1280 宽的画布约 192 DPI），
+/// 页面尺寸按画布像素换算为点（1px = 0.75pt）
+pub struct PdfExport {
+    scale: f32,
+    notes_pages: bool,
+}
+
+impl PdfExport {
+    pub fn new() -> Self {
+        Self {
+            scale: 2.0,
+            notes_pages: false,
+        }
+    }
+
+    /// 设置渲染倍率（1.0 ~ 4.0）
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale.clamp(MIN_SCALE, MAX_SCALE);
+        self
+    }
+
+    /// 设置是否在有备注的幻灯片后附加备注页
+    pub fn with_notes_pages(mut self, notes_pages: bool) -> Self {
+        self.notes_pages = notes_pages;
+        self
+    }
+}
+
+impl Default for PdfExport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PptxBackend for PdfExport {
+    fn export<'a>(
+        &'a self,
+        slides: &'a [Slide],
+        output_path: &'a Path,
+        config: &'a PptxConfig,
+        progress: &'a dyn ProgressSink,
+        cancel: &'a CancellationToken,
+    ) -> ExportFuture<'a> {
+        Box::pin(async move {
+            let title = output_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("Presentation");
+
+            let (pdf, image_count) = self
+                .build_document(slides, title, config, progress, cancel)
+                .await?;
+            std::fs::write(output_path, pdf)?;
+
+            Ok(ExportSummary {
+                slide_count: slides.len(),
+                media_count: image_count,
+                media_bytes_saved: 0,
+                accessibility: accessibility::audit_slides(slides)?,
+            })
+        })
+    }
+
+    fn name(&self) -> &str {
+        "pdf"
+    }
+
+    fn is_available(&self) -> bool {
+        true // 纯 Rust 实现，总是可用
+    }
+}
+
+/// 页面中的一张图片（位置与尺寸为画布像素，原点在左上角）
+#[derive(Debug, Clone, Copy)]
+struct Placement {
+    image_id: usize,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+/// 备注页布局（画布像素）
+struct NotesLayout {
+    margin: f32,
+    font_size: f32,
+    line_height: f32,
+    thumbnail_height: f32,
+}
+
+impl NotesLayout {
+    fn new(config: &PptxConfig) -> Self {
+        let height = config.height as f32;
+        let font_size = (height / 36.0).max(12.0);
+        Self {
+            margin: height * 0.06,
+            font_size,
+            line_height: font_size * 1.5,
+            thumbnail_height: height * 0.45,
+        }
+    }
+
+    /// 文字区域：首页位于缩略图下方，续页占满整页
+    fn text_area(&self, config: &PptxConfig, first_page: bool) -> (f32, f32, f32, f32) {
+        let top = if first_page {
+            self.margin * 1.5 + self.thumbnail_height
+        } else {
+            self.margin
+        };
+        (
+            self.margin,
+            top,
+            config.width as f32 - self.margin * 2.0,
+            config.height as f32 - self.margin - top,
+        )
+    }
+
+    /// 区域内可容纳的行数（至少一行）
+    fn lines_per_page(&self, area_height: f32) -> usize {
+        ((area_height / self.line_height).floor() as usize).max(1)
+    }
+}
+
+impl PdfExport {
+    /// 生成完整的 PDF 文件，同时返回嵌入的图片数量
+    async fn build_document(
+        &self,
+        slides: &[Slide],
+        title: &str,
+        config: &PptxConfig,
+        progress: &dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> Result<(Vec<u8>, usize)> {
+        let mut writer = PdfWriter::new();
+        let catalog_id = writer.reserve();
+        let pages_id = writer.reserve();
+        let info_id = writer.reserve();
+        let mut page_ids = Vec::new();
+        let mut image_count = 0;
+
+        let layout = NotesLayout::new(config);
+        let font_family = notes_font_family(config);
+
+        for (index, slide) in slides.iter().enumerate() {
+            slide_checkpoint(slides, index, progress, cancel).await?;
+
+            // 渲染与压缩在阻塞线程池中执行，写入器只追加编码好的对象
+            let slide_image = writer.reserve();
+            let (source, width, height) = (
+                slide.clone(),
+                self.pixels(config.width),
+                self.pixels(config.height),
+            );
+            let body =
+                run_blocking(move || image_object(&render_slide(&source, width, height)?)).await?;
+            writer.object(slide_image, &body);
+            image_count += 1;
+
+            page_ids.push(writer.page(
+                pages_id,
+                config,
+                &[Placement {
+                    image_id: slide_image,
+                    x: 0.0,
+                    y: 0.0,
+                    width: config.width as f32,
+                    height: config.height as f32,
+                }],
+            )?);
+
+            if !self.notes_pages {
+                continue;
+            }
+            let lines = slide
+                .notes
+                .as_deref()
+                .map(notes::markdown_to_lines)
+                .unwrap_or_default();
+            if lines.is_empty() {
+                continue;
+            }
+
+            // 首个备注页在上方放置幻灯片缩略图（复用同一图片对象）
+            let thumbnail_width =
+                layout.thumbnail_height * config.width as f32 / config.height as f32;
+            let thumbnail = Placement {
+                image_id: slide_image,
+                x: (config.width as f32 - thumbnail_width) / 2.0,
+                y: layout.margin,
+                width: thumbnail_width,
+                height: layout.thumbnail_height,
+            };
+
+            let (_, _, text_width, _) = layout.text_area(config, true);
+            let wrapped = wrap_lines(&lines, text_width, layout.font_size);
+            for (page_index, chunk) in paginate(&wrapped, &layout, config).into_iter().enumerate() {
+                let (x, y, width, height) = layout.text_area(config, page_index == 0);
+                let text_svg = notes_svg(chunk, width, height, &layout, &font_family);
+                let text_image = writer.reserve();
+                let (pixel_width, pixel_height) = (
+                    (width * self.scale).round() as u32,
+                    (height * self.scale).round() as u32,
+                );
+                let body = run_blocking(move || {
+                    image_object(&render_svg(&text_svg, pixel_width, pixel_height)?)
+                })
+                .await?;
+                writer.object(text_image, &body);
+                image_count += 1;
+
+                let mut placements = Vec::with_capacity(2);
+                if page_index == 0 {
+                    placements.push(thumbnail);
+                }
+                placements.push(Placement {
+                    image_id: text_image,
+                    x,
+                    y,
+                    width,
+                    height,
+                });
+                page_ids.push(writer.page(pages_id, config, &placements)?);
+            }
+        }
+
+        let kids: Vec<String> = page_ids.iter().map(|id| format!("{} 0 R", id)).collect();
+        writer.object(
+            pages_id,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                page_ids.len()
+            )
+            .as_bytes(),
+        );
+        writer.object(
+            catalog_id,
+            format!("<< /Type /Catalog /Pages {} 0 R >>", pages_id).as_bytes(),
+        );
+        writer.object(
+            info_id,
+            format!(
+                "<< /Title {} /Producer {} /CreationDate ({}) >>",
+                text_string(title),
+                text_string(PRODUCER),
+                chrono::Local::now().format("D:%Y%m%d%H%M%S")
+            )
+            .as_bytes(),
+        );
+
+        Ok((writer.finish(catalog_id, info_id), image_count))
+    }
+
+    /// 画布像素按渲染倍率换算后的位图像素
+    fn pixels(&self, canvas: u32) -> u32 {
+        (canvas as f32 * self.scale).round() as u32
+    }
+}
+
+/// 将幻灯片渲染为 `width` x `height` 的位图
+///
+/// 回退为 PNG 的幻灯片按原始 SVG 渲染，没有 SVG 来源的位图直接解码
+fn render_slide(slide: &Slide, width: u32, height: u32) -> Result<tiny_skia::Pixmap> {
+    match (&slide.content, &slide.source_svg) {
+        (SlideContent::Svg(svg), _) | (SlideContent::Png(_), Some(svg)) => {
+            render_svg(svg, width, height)
+        }
+        (SlideContent::Png(data), None) => {
+            tiny_skia::Pixmap::decode_png(data).map_err(|e| PptxError::PngConversion(e.to_string()))
+        }
+    }
+}
+
+/// 备注文字使用主题的正文字体
+fn notes_font_family(config: &PptxConfig) -> String {
+    match &config.theme {
+        Some(theme) => format!(
+            "'{}', '{}', {}",
+            theme.minor_east_asian_font, theme.minor_font, NOTES_FONT_FAMILY
+        ),
+        None => NOTES_FONT_FAMILY.to_string(),
+    }
+}
+
+/// 按区域宽度折行（按字符宽度估算：全角字符 1em，其他 0.55em）
+fn wrap_lines(lines: &[NoteLine], width: f32, font_size: f32) -> Vec<NoteLine> {
+    let mut wrapped = Vec::new();
+
+    for line in lines {
+        let indent = line.level as f32 * 2.0;
+        let max_width = (width / font_size - indent).max(4.0);
+        let mut current = String::new();
+        let mut current_width = 0.0;
+
+        for c in line.text.chars() {
+            let advance = char_width(c);
+            if current_width + advance > max_width && !current.is_empty() {
+                // 超出宽度的空格直接作为断行位置
+                if c == ' ' {
+                    wrapped.push(NoteLine {
+                        text: std::mem::take(&mut current),
+                        level: line.level,
+                        heading: line.heading,
+                    });
+                    current_width = 0.0;
+                    continue;
+                }
+
+                // 西文单词尽量在空格处断开
+                let split = (!is_wide(c))
+                    .then(|| current.rfind(' '))
+                    .flatten()
+                    .filter(|&index| index > 0);
+                let rest = match split {
+                    Some(index) => {
+                        let rest = current[index + 1..].to_string();
+                        current.truncate(index);
+                        rest
+                    }
+                    None => String::new(),
+                };
+                wrapped.push(NoteLine {
+                    text: std::mem::replace(&mut current, rest),
+                    level: line.level,
+                    heading: line.heading,
+                });
+                current_width = current.chars().map(char_width).sum();
+            }
+            current.push(c);
+            current_width += advance;
+        }
+
+        wrapped.push(NoteLine {
+            text: current,
+            level: line.level,
+            heading: line.heading,
+        });
+    }
+
+    wrapped
+}
+
+fn is_wide(c: char) -> bool {
+    matches!(c as u32, 0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x20000..=0x3FFFD)
+}
+
+fn char_width(c: char) -> f32 {
+    if is_wide(c) {
+        1.0
+    } else {
+        0.55
+    }
+}
+
+/// 将折行后的备注分配到各页：首页在缩略图下方，其余为续页
+fn paginate<'a>(
+    lines: &'a [NoteLine],
+    layout: &NotesLayout,
+    config: &PptxConfig,
+) -> Vec<&'a [NoteLine]> {
+    let mut pages = Vec::new();
+    let mut rest = lines;
+    while !rest.is_empty() {
+        let (_, _, _, height) = layout.text_area(config, pages.is_empty());
+        let (page, after) = rest.split_at(layout.lines_per_page(height).min(rest.len()));
+        pages.push(page);
+        rest = after;
+    }
+    pages
+}
+
+/// 生成一页备注文字的 SVG
+fn notes_svg(
+    lines: &[NoteLine],
+    width: f32,
+    height: f32,
+    layout: &NotesLayout,
+    font_family: &str,
+) -> String {
+    let mut svg = format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}"><g font-family="{}" font-size="{}" fill="#333333">"##,
+        escape_xml(font_family),
+        layout.font_size,
+        w = width,
+        h = height
+    );
+    for (index, line) in lines.iter().enumerate() {
+        svg.push_str(&format!(
+            r#"<text x="{}" y="{}"{}>{}</text>"#,
+            line.level as f32 * 2.0 * layout.font_size,
+            index as f32 * layout.line_height + layout.font_size,
+            if line.heading {
+                r#" font-weight="bold""#
+            } else {
+                ""
+            },
+            escape_xml(&line.text)
+        ));
+    }
+    svg.push_str("</g></svg>");
+    svg
+}
+
+/// Flate 压缩的流对象内容
+fn stream_object(dictionary: &str, data: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data)?;
+    let compressed = encoder.finish()?;
+
+    let mut body = format!(
+        "<< {} /Filter /FlateDecode /Length {} >>\nstream\n",
+        dictionary,
+        compressed.len()
+    )
+    .into_bytes();
+    body.extend_from_slice(&compressed);
+    body.extend_from_slice(b"\nendstream");
+    Ok(body)
+}
+
+/// RGB 图片对象内容（透明部分合成到白色背景上）
+fn image_object(pixmap: &tiny_skia::Pixmap) -> Result<Vec<u8>> {
+    stream_object(
+        &format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8",
+            pixmap.width(),
+            pixmap.height()
+        ),
+        &pixmap_to_rgb(pixmap),
+    )
+}
+
+/// PDF 文本字符串：ASCII 直接写入，其他文字使用带 BOM 的 UTF-16BE 十六进制串
+fn text_string(text: &str) -> String {
+    if text.is_ascii() {
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('(', "\\(")
+            .replace(')', "\\)");
+        return format!("({})", escaped);
+    }
+
+    let hex: String = text
+        .encode_utf16()
+        .map(|unit| format!("{:04X}", unit))
+        .collect();
+    format!("<FEFF{}>", hex)
+}
+
+/// 最小 PDF 写入器：按对象编号记录偏移量，结束时写出交叉引用表
+struct PdfWriter {
+    buffer: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        Self {
+            // 第二行的高位字节标记文件包含二进制数据
+            buffer: b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec(),
+            offsets: Vec::new(),
+        }
+    }
+
+    /// 预留对象编号（从 1 开始）
+    fn reserve(&mut self) -> usize {
+        self.offsets.push(0);
+        self.offsets.len()
+    }
+
+    fn object(&mut self, id: usize, body: &[u8]) {
+        self.offsets[id - 1] = self.buffer.len();
+        self.buffer
+            .extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+        self.buffer.extend_from_slice(body);
+        self.buffer.extend_from_slice(b"\nendobj\n");
+    }
+
+    /// 写入 Flate 压缩的流对象
+    fn stream(&mut self, id: usize, dictionary: &str, data: &[u8]) -> Result<()> {
+        let body = stream_object(dictionary, data)?;
+        self.object(id, &body);
+        Ok(())
+    }
+
+    /// 写入一页（页面尺寸取画布尺寸），返回页面对象编号
+    fn page(
+        &mut self,
+        pages_id: usize,
+        config: &PptxConfig,
+        placements: &[Placement],
+    ) -> Result<usize> {
+        let page_width = config.width as f32 * POINTS_PER_PIXEL;
+        let page_height = config.height as f32 * POINTS_PER_PIXEL;
+
+        let mut content = String::new();
+        let mut resources = String::new();
+        for (index, placement) in placements.iter().enumerate() {
+            // PDF 坐标原点在左下角
+            content.push_str(&format!(
+                "q {:.2} 0 0 {:.2} {:.2} {:.2} cm /Im{} Do Q\n",
+                placement.width * POINTS_PER_PIXEL,
+                placement.height * POINTS_PER_PIXEL,
+                placement.x * POINTS_PER_PIXEL,
+                page_height - (placement.y + placement.height) * POINTS_PER_PIXEL,
+                index
+            ));
+            resources.push_str(&format!("/Im{} {} 0 R ", index, placement.image_id));
+        }
+
+        let content_id = self.reserve();
+        self.stream(content_id, "", content.as_bytes())?;
+
+        let page_id = self.reserve();
+        self.object(
+            page_id,
+            format!(
+                "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {:.2} {:.2}] /Resources << /XObject << {}>> >> /Contents {} 0 R >>",
+                pages_id, page_width, page_height, resources, content_id
+            )
+            .as_bytes(),
+        );
+        Ok(page_id)
+    }
+
+    /// 写出交叉引用表与文件尾
+    fn finish(mut self, root_id: usize, info_id: usize) -> Vec<u8> {
+        let xref_offset = self.buffer.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            xref.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len() + 1,
+            root_id,
+            info_id,
+            xref_offset
+        ));
+        self.buffer.extend_from_slice(xref.as_bytes());
+        self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoProgress;
+
+    fn slide(number: usize, notes: Option<&str>) -> Slide {
+        Slide {
+            number,
+            title: format!("{:02}_page", number),
+            content: SlideContent::Svg(
+                r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"><rect width="1280" height="720" fill="#1A73E8"/></svg>"##
+                    .to_string(),
+            ),
+            notes: notes.map(str::to_string),
+            transition: None,
+            animation: None,
+            section: None,
+            source_svg: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_pdf() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let output = dir.path().join("年度报告.pdf");
+        let long_notes = "- 第一点\n".repeat(25);
+        let slides = vec![
+            slide(1, Some("# 开场\n\n大家好")),
+            slide(2, Some(&long_notes)),
+            slide(3, None),
+        ];
+        let config = PptxConfig::default();
+
+        let summary = PdfExport::new()
+            .with_scale(1.0)
+            .with_notes_pages(true)
+            .export(
+                &slides,
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        assert_eq!(summary.slide_count, 3);
+
+        let pdf = std::fs::read(&output).expect("读取 PDF 失败");
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.7"));
+        assert!(text.trim_end().ends_with("%%EOF"));
+        // 3 页幻灯片 + 第 1 页的备注页 + 第 2 页的两个备注页
+        assert!(text.contains("/Count 6"));
+        assert_eq!(text.matches("/Type /Page ").count(), 6);
+        assert!(text.contains("/MediaBox [0 0 960.00 540.00]"));
+        assert!(text.contains("/Width 1280 /Height 720"));
+        assert!(text.contains(&format!("/Title {}", text_string("年度报告"))));
+
+        // 交叉引用表中的偏移量指向对应对象
+        let xref: usize = text
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .expect("缺少 startxref");
+        let table = std::str::from_utf8(&pdf[xref..]).expect("交叉引用表应为 ASCII");
+        assert!(table.starts_with("xref\n"));
+        let entries: Vec<&str> = table
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .collect();
+        assert!(!entries.is_empty());
+        for (index, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().expect("偏移量应为数字");
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", index + 1).as_bytes()));
+        }
+
+        // 不附加备注页时每张幻灯片一页
+        PdfExport::new()
+            .with_scale(1.0)
+            .export(
+                &slides,
+                &output,
+                &config,
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+        let text =
+            String::from_utf8_lossy(&std::fs::read(&output).expect("读取 PDF 失败")).to_string();
+        assert!(text.contains("/Count 3"));
+    }
+
+    #[tokio::test]
+    async fn test_export_fallback_slide_at_scale() {
+        let dir = tempfile::tempdir().expect("创建临时目录失败");
+        let output = dir.path().join("deck.pdf");
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"><style>rect { fill: #1A73E8 }</style><rect width="1280" height="720"/></svg>"##;
+        let slides = vec![Slide {
+            content: SlideContent::Png(crate::svg_to_png(svg, 1280, 720).expect("渲染应成功")),
+            source_svg: Some(svg.to_string()),
+            ..slide(1, None)
+        }];
+
+        PdfExport::new()
+            .with_scale(2.0)
+            .export(
+                &slides,
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        // 回退为 PNG 的幻灯片同样按倍率渲染
+        let text =
+            String::from_utf8_lossy(&std::fs::read(&output).expect("读取 PDF 失败")).to_string();
+        assert!(text.contains("/Width 2560 /Height 1440"));
+        assert!(text.contains("/MediaBox [0 0 960.00 540.00]"));
+    }
+
+    #[test]
+    fn test_wrap_lines() {
+        let lines = vec![
+            NoteLine {
+                text: "这是一段需要折行的中文备注".to_string(),
+                level: 0,
+                heading: false,
+            },
+            NoteLine {
+                text: "• alpha beta gamma".to_string(),
+                level: 1,
+                heading: false,
+            },
+        ];
+
+        let wrapped = wrap_lines(&lines, 160.0, 20.0);
+        let texts: Vec<_> = wrapped.iter().map(|line| line.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec!["这是一段需要折行", "的中文备注", "• alpha", "beta gamma"]
+        );
+        assert_eq!(wrapped[3].level, 1);
+        assert_eq!(text_string("a(b)"), r"(a\(b\))");
+        assert_eq!(text_string("中"), "<FEFF4E2D>");
+    }
+}
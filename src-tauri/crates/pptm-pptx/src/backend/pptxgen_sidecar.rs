@@ -1,32 +1,160 @@
 // PptxGenJS Sidecar 后端
-// 使用 Node.js + PptxGenJS 库生成 PPTX
+// 使用 Node.js + PptxGenJS 库生成 PPTX，通过常驻 sidecar 的 JSON-RPC 会话通信
 
-use crate::{PptxBackend, PptxConfig, PptxError, Result, Slide, SlideContent};
-use std::path::Path;
-use std::process::Stdio;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
-use tokio::process::Command;
+use crate::sidecar::{SidecarClient, SidecarCommand};
+use crate::{
+    accessibility, media, run_blocking, section, slide_checkpoint, svg_to_png, validate,
+    ExportFuture, ExportSummary, PptxBackend, PptxConfig, PptxError, ProgressSink, Result, Slide,
+    SlideContent,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// 缺省 sidecar 脚本路径（相对于资源目录或仓库根目录）
+pub const DEFAULT_SIDECAR_PATH: &str = "sidecars/pptxgen/index.js";
+
+/// 缺省导出超时
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// 能力查询超时
+const PROBE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 在候选根目录中查找 sidecar 脚本，返回第一个存在的 `<root>/sidecars/pptxgen/index.js`
+pub fn find_sidecar_script<I, P>(roots: I) -> Option<PathBuf>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    roots
+        .into_iter()
+        .map(|root| root.as_ref().join(DEFAULT_SIDECAR_PATH))
+        .find(|path| path.is_file())
+}
+
+/// sidecar 通过 `capabilities` 方法报告的能力
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarCapabilities {
+    /// Node.js 版本
+    pub node_version: String,
+    /// PptxGenJS 版本
+    pub library_version: String,
+    /// 可直接处理的幻灯片内容类型（"svg"、"png"）
+    pub content_types: Vec<String>,
+    /// 支持的切换效果
+    pub transitions: Vec<String>,
+}
+
+impl SidecarCapabilities {
+    /// 是否可直接处理指定内容类型
+    pub fn supports_content(&self, content_type: &str) -> bool {
+        self.content_types.iter().any(|t| t == content_type)
+    }
+
+    /// 是否支持指定切换效果
+    pub fn supports_transition(&self, effect: &str) -> bool {
+        self.transitions.iter().any(|t| t == effect)
+    }
+}
+
+/// sidecar 探测结果（供界面展示）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarProbe {
+    /// 是否可用于导出
+    pub available: bool,
+    /// sidecar 脚本路径
+    pub sidecar_path: String,
+    /// sidecar 自身版本（握手返回）
+    pub sidecar_version: Option<String>,
+    /// 能力（探测成功时）
+    pub capabilities: Option<SidecarCapabilities>,
+    /// 不可用的原因
+    pub error: Option<String>,
+}
 
 /// PptxGenJS Sidecar 后端
 ///
-/// 通过 Node.js sidecar 进程调用 PptxGenJS 库生成 PPTX
+/// 通过常驻的 Node.js sidecar 进程调用 PptxGenJS 库生成 PPTX；
+/// 克隆的实例共享同一 [`SidecarClient`]，复用同一进程
+#[derive(Clone)]
 pub struct PptxGenSidecar {
-    sidecar_path: String,
+    sidecar_path: PathBuf,
+    client: Arc<SidecarClient>,
+    timeout: Duration,
 }
 
 impl PptxGenSidecar {
     /// 创建新的 PptxGenSidecar 后端
     pub fn new() -> Self {
         Self {
-            sidecar_path: "sidecars/pptxgen/index.js".to_string(),
+            sidecar_path: PathBuf::from(DEFAULT_SIDECAR_PATH),
+            client: Arc::new(Self::client(Path::new(DEFAULT_SIDECAR_PATH))),
+            timeout: DEFAULT_TIMEOUT,
         }
     }
 
-    /// 设置 sidecar 路径
-    pub fn with_sidecar_path(mut self, path: String) -> Self {
-        self.sidecar_path = path;
+    /// 创建运行指定 sidecar 脚本的客户端
+    fn client(sidecar_path: &Path) -> SidecarClient {
+        SidecarClient::new(SidecarCommand::new("node").with_arg(sidecar_path.to_string_lossy()))
+    }
+
+    /// 设置 sidecar 脚本路径
+    pub fn with_sidecar_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.sidecar_path = path.into();
+        self.client = Arc::new(Self::client(&self.sidecar_path));
         self
     }
+
+    /// 设置单次导出的超时时间
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// sidecar 脚本路径
+    pub fn sidecar_path(&self) -> &Path {
+        &self.sidecar_path
+    }
+
+    /// 启动（如尚未启动）sidecar 并查询能力
+    pub async fn capabilities(&self) -> Result<SidecarCapabilities> {
+        if !self.sidecar_path.is_file() {
+            return Err(PptxError::Backend(format!(
+                "未找到 sidecar 脚本: {}",
+                self.sidecar_path.display()
+            )));
+        }
+        let value = self
+            .client
+            .request("capabilities", serde_json::Value::Null, PROBE_TIMEOUT)
+            .await?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// 探测 sidecar：检查脚本、握手并查询能力
+    pub async fn probe(&self) -> SidecarProbe {
+        let mut probe = SidecarProbe {
+            available: false,
+            sidecar_path: self.sidecar_path.to_string_lossy().into_owned(),
+            sidecar_version: None,
+            capabilities: None,
+            error: None,
+        };
+        match self.capabilities().await {
+            Ok(capabilities) => {
+                probe.available = true;
+                probe.capabilities = Some(capabilities);
+                probe.sidecar_version = self.client.info().await.ok().map(|info| info.version);
+            }
+            Err(e) => probe.error = Some(e.to_string()),
+        }
+        probe
+    }
 }
 
 impl Default for PptxGenSidecar {
@@ -36,12 +164,21 @@ impl Default for PptxGenSidecar {
 }
 
 impl PptxBackend for PptxGenSidecar {
-    fn export(&self, slides: &[Slide], output_path: &Path, config: &PptxConfig) -> Result<()> {
-        // 使用 tokio runtime 执行异步操作
-        let runtime = tokio::runtime::Runtime::new()
-            .map_err(|e| PptxError::Backend(format!("无法创建 tokio runtime: {}", e)))?;
-
-        runtime.block_on(async { self.export_async(slides, output_path, config).await })
+    fn export<'a>(
+        &'a self,
+        slides: &'a [Slide],
+        output_path: &'a Path,
+        config: &'a PptxConfig,
+        progress: &'a dyn ProgressSink,
+        cancel: &'a CancellationToken,
+    ) -> ExportFuture<'a> {
+        Box::pin(async move {
+            if cancel.is_cancelled() {
+                return Err(PptxError::Cancelled);
+            }
+            self.export_async(slides, output_path, config, progress, cancel)
+                .await
+        })
     }
 
     fn name(&self) -> &str {
@@ -49,11 +186,23 @@ impl PptxBackend for PptxGenSidecar {
     }
 
     fn is_available(&self) -> bool {
-        // 检查 Node.js 是否可用
+        // 脚本存在，且在脚本目录下能解析到 pptxgenjs（同时说明 Node.js 可用）
+        let Some(dir) = self
+            .sidecar_path
+            .parent()
+            .filter(|_| self.sidecar_path.is_file())
+        else {
+            return false;
+        };
         std::process::Command::new("node")
-            .arg("--version")
+            .args(["-e", "require.resolve('pptxgenjs')"])
+            .current_dir(if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            })
             .output()
-            .is_ok()
+            .is_ok_and(|output| output.status.success())
     }
 }
 
@@ -63,103 +212,185 @@ impl PptxGenSidecar {
         slides: &[Slide],
         output_path: &Path,
         config: &PptxConfig,
-    ) -> Result<()> {
-        // 准备请求数据
+        progress: &dyn ProgressSink,
+        cancel: &CancellationToken,
+    ) -> Result<ExportSummary> {
+        // 按 sidecar 报告的能力准备请求：不能直接处理的 SVG 先渲染为 PNG，
+        // 不支持的切换效果不发送
+        let capabilities = self.capabilities().await?;
+        let embed_svg = capabilities.supports_content("svg");
+
+        let unsupported: BTreeSet<_> = slides
+            .iter()
+            .filter_map(|slide| config.slide_transition(slide))
+            .map(|spec| spec.effect)
+            .filter(|effect| !capabilities.supports_transition(effect))
+            .collect();
+        if !unsupported.is_empty() {
+            progress.log(
+                "warn",
+                format!(
+                    "PptxGenJS {} 不支持切换效果 {}，已忽略（需要切换效果请使用 native 导出器）",
+                    capabilities.library_version,
+                    unsupported.into_iter().collect::<Vec<_>>().join(", ")
+                ),
+            );
+        }
+
+        // 逐页准备请求数据；SVG 渲染较慢，放到阻塞线程池，并在每页之间响应取消
+        let mut request_slides = Vec::with_capacity(slides.len());
+        for (index, slide) in slides.iter().enumerate() {
+            slide_checkpoint(slides, index, progress, cancel).await?;
+
+            let content = match &slide.content {
+                SlideContent::Svg(svg) if embed_svg => serde_json::json!({
+                    "type": "svg",
+                    "data": svg,
+                }),
+                SlideContent::Svg(svg) => {
+                    let (svg, width, height) = (svg.clone(), config.width, config.height);
+                    let png = run_blocking(move || svg_to_png(&svg, width, height)).await?;
+                    png_content(&png)
+                }
+                SlideContent::Png(png) => png_content(png),
+            };
+
+            let transition = config
+                .slide_transition(slide)
+                .filter(|t| capabilities.supports_transition(&t.effect))
+                .map(|t| {
+                    serde_json::json!({
+                        "type": t.effect,
+                        "duration": t.duration,
+                        "advanceAfter": t.advance_after,
+                    })
+                });
+
+            request_slides.push(serde_json::json!({
+                "number": slide.number,
+                "title": slide.title,
+                "content": content,
+                "notes": slide.notes,
+                "transition": transition,
+            }));
+        }
+
         let request = serde_json::json!({
-            "slides": slides.iter().map(|slide| {
-                let content = match &slide.content {
-                    SlideContent::Svg(svg) => serde_json::json!({
-                        "type": "svg",
-                        "data": svg,
-                    }),
-                    SlideContent::Png(png) => {
-                        use base64::Engine;
-                        serde_json::json!({
-                            "type": "png",
-                            "data": base64::engine::general_purpose::STANDARD.encode(png),
-                        })
-                    }
-                };
-
-                serde_json::json!({
-                    "number": slide.number,
-                    "title": slide.title,
-                    "content": content,
-                    "notes": slide.notes,
-                })
-            }).collect::<Vec<_>>(),
+            "slides": request_slides,
+            "sections": section::group_sections(slides).iter().map(|section| serde_json::json!({
+                "name": section.name,
+                "slides": section.slides.iter().map(|&index| slides[index].number).collect::<Vec<_>>(),
+            })).collect::<Vec<_>>(),
             "output": output_path.to_string_lossy(),
             "config": {
                 "width": config.width,
                 "height": config.height,
                 "enableTransitions": config.enable_transitions,
                 "transitionType": config.transition_type,
+                "transitionDuration": config.transition_duration,
+                "autoAdvance": config.auto_advance,
             },
         });
 
-        let request_json = serde_json::to_string(&request)?;
-
-        // 启动 Node.js sidecar 进程
-        let mut child = Command::new("node")
-            .arg(&self.sidecar_path)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|e| PptxError::Backend(format!("无法启动 Node.js sidecar: {}", e)))?;
-
-        // 发送请求数据
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(request_json.as_bytes())
-                .await
-                .map_err(|e| PptxError::Backend(format!("无法写入 stdin: {}", e)))?;
-            stdin
-                .flush()
-                .await
-                .map_err(|e| PptxError::Backend(format!("无法 flush stdin: {}", e)))?;
-        }
+        // sidecar 每处理完一页发送一次进度通知；取消时只放弃本次导出，
+        // 共用同一进程的其他导出不受影响
+        self.client
+            .request_with_notifications("export", request, self.timeout, cancel, |notification| {
+                if notification.method != "progress" {
+                    return;
+                }
+                let params = &notification.params;
+                let count = |key: &str| params[key].as_u64().unwrap_or_default() as usize;
+                progress.report_progress(
+                    count("current"),
+                    count("total"),
+                    params["message"].as_str().unwrap_or_default().to_string(),
+                );
+            })
+            .await?;
 
-        // 读取响应
-        let mut stdout = String::new();
-        let mut stderr = String::new();
+        // PptxGenJS 为每次引用单独写入图片，写出后再按内容去重
+        let media = media::deduplicate_package(output_path)?;
 
-        if let Some(mut out) = child.stdout.take() {
-            out.read_to_string(&mut stdout)
-                .await
-                .map_err(|e| PptxError::Backend(format!("无法读取 stdout: {}", e)))?;
-        }
+        // 校验 sidecar 写出的文件
+        validate::ensure_valid(output_path)?;
 
-        if let Some(mut err) = child.stderr.take() {
-            err.read_to_string(&mut stderr)
-                .await
-                .map_err(|e| PptxError::Backend(format!("无法读取 stderr: {}", e)))?;
-        }
+        Ok(ExportSummary {
+            slide_count: slides.len(),
+            media_count: media.media_count,
+            media_bytes_saved: media.bytes_saved,
+            accessibility: accessibility::audit_slides(slides)?,
+        })
+    }
+}
 
-        // 等待进程结束
-        let status = child
-            .wait()
-            .await
-            .map_err(|e| PptxError::Backend(format!("等待进程失败: {}", e)))?;
+/// PNG 幻灯片内容（base64）
+fn png_content(png: &[u8]) -> serde_json::Value {
+    use base64::Engine;
+    serde_json::json!({
+        "type": "png",
+        "data": base64::engine::general_purpose::STANDARD.encode(png),
+    })
+}
 
-        if !status.success() {
-            return Err(PptxError::Backend(format!(
-                "Node.js sidecar 执行失败: {}\nstderr: {}",
-                stdout, stderr
-            )));
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // 解析响应
-        let response: serde_json::Value = serde_json::from_str(&stdout)
-            .map_err(|e| PptxError::Backend(format!("无法解析响应: {}", e)))?;
+    #[tokio::test]
+    async fn test_probe_sidecar() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
 
-        if let Some(error) = response.get("error") {
-            return Err(PptxError::Backend(format!(
-                "Sidecar 返回错误: {}",
-                error.as_str().unwrap_or("未知错误")
-            )));
+        // 找不到脚本时不可用，且不启动进程
+        let missing =
+            PptxGenSidecar::new().with_sidecar_path(Path::new(fixtures).join("missing.js"));
+        assert!(!missing.is_available());
+        let probe = missing.probe().await;
+        assert!(!probe.available);
+        assert!(probe
+            .error
+            .expect("应报告原因")
+            .contains("未找到 sidecar 脚本"));
+        assert_eq!(find_sidecar_script([fixtures]), None);
+
+        // 测试 sidecar 需要 Node.js
+        let has_node = std::process::Command::new("node")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success());
+        if !has_node {
+            return;
         }
 
-        Ok(())
+        let sidecar =
+            PptxGenSidecar::new().with_sidecar_path(Path::new(fixtures).join("stub_sidecar.cjs"));
+        let probe = sidecar.probe().await;
+        assert!(probe.available, "{:?}", probe.error);
+        assert_eq!(probe.sidecar_version.as_deref(), Some("0.0.1"));
+
+        let capabilities = probe.capabilities.expect("应返回能力");
+        assert!(capabilities
+            .node_version
+            .starts_with(|c: char| c.is_ascii_digit()));
+        assert_eq!(capabilities.library_version, "0.0.0-stub");
+        assert!(capabilities.supports_content("png"));
+        assert!(!capabilities.supports_content("svg"));
+        assert!(capabilities.supports_transition("push"));
+        assert!(!capabilities.supports_transition("reveal"));
+
+        // 克隆的实例复用同一进程
+        let pid = sidecar
+            .clone()
+            .client
+            .request("pid", serde_json::Value::Null, PROBE_TIMEOUT)
+            .await
+            .expect("请求应成功");
+        let again = sidecar
+            .client
+            .request("pid", serde_json::Value::Null, PROBE_TIMEOUT)
+            .await
+            .expect("请求应成功");
+        assert_eq!(pid, again);
+        sidecar.client.shutdown().await;
     }
 }
@@ -0,0 +1,283 @@
+// 图表部件 XML（c:chartSpace）
+// 数值与分类同时写入公式引用与缓存，PowerPoint 打开时无需重新计算
+
+use super::workbook::range_ref;
+use super::{ChartKind, ChartSpec};
+use crate::escape_xml;
+
+/// 分类轴与数值轴 ID
+const CATEGORY_AXIS_ID: u32 = 111_111_111;
+const VALUE_AXIS_ID: u32 = 222_222_222;
+
+pub(super) fn chart_space_xml(spec: &ChartSpec, workbook_rid: &str) -> String {
+    let title = match &spec.data.title {
+        Some(title) if !title.trim().is_empty() => format!(
+            r#"<c:title><c:tx><c:rich><a:bodyPr/><a:lstStyle/><a:p><a:pPr><a:defRPr/></a:pPr><a:r><a:rPr lang="zh-CN"/><a:t>{}</a:t></a:r></a:p></c:rich></c:tx><c:overlay val="0"/></c:title><c:autoTitleDeleted val="0"/>"#,
+            escape_xml(title.trim())
+        ),
+        _ => r#"<c:autoTitleDeleted val="1"/>"#.to_string(),
+    };
+
+    let show_legend = spec
+        .data
+        .legend
+        .unwrap_or(spec.data.series.len() > 1 || !spec.kind.has_axes());
+    let legend = if show_legend {
+        r#"<c:legend><c:legendPos val="b"/><c:overlay val="0"/></c:legend>"#
+    } else {
+        ""
+    };
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><c:date1904 val="0"/><c:lang val="zh-CN"/><c:roundedCorners val="0"/><c:chart>{}<c:plotArea><c:layout/>{}</c:plotArea>{}<c:plotVisOnly val="1"/><c:dispBlanksAs val="gap"/></c:chart><c:externalData r:id="{}"><c:autoUpdate val="0"/></c:externalData></c:chartSpace>"#,
+        title,
+        plot_area(spec),
+        legend,
+        workbook_rid
+    )
+}
+
+fn plot_area(spec: &ChartSpec) -> String {
+    let axis_ids = format!(
+        r#"<c:axId val="{}"/><c:axId val="{}"/>"#,
+        CATEGORY_AXIS_ID, VALUE_AXIS_ID
+    );
+
+    match spec.kind {
+        ChartKind::Bar | ChartKind::Column => {
+            let (direction, category_position, value_position) = if spec.kind == ChartKind::Bar {
+                ("bar", "l", "b")
+            } else {
+                ("col", "b", "l")
+            };
+            let (grouping, overlap) = if spec.data.stacked {
+                ("stacked", r#"<c:overlap val="100"/>"#)
+            } else {
+                ("clustered", "")
+            };
+            let series: String = (0..spec.data.series.len())
+                .map(|index| {
+                    format!(
+                        r#"<c:ser>{}{}<c:invertIfNegative val="0"/>{}</c:ser>"#,
+                        series_header(spec, index),
+                        fill_properties(series_color(spec, index)),
+                        category_values(spec, index)
+                    )
+                })
+                .collect();
+
+            format!(
+                r#"<c:barChart><c:barDir val="{}"/><c:grouping val="{}"/><c:varyColors val="0"/>{}<c:gapWidth val="80"/>{}{}</c:barChart>{}{}"#,
+                direction,
+                grouping,
+                series,
+                overlap,
+                axis_ids,
+                category_axis(category_position),
+                value_axis(VALUE_AXIS_ID, CATEGORY_AXIS_ID, value_position, true)
+            )
+        }
+        ChartKind::Line => {
+            let series: String = (0..spec.data.series.len())
+                .map(|index| {
+                    format!(
+                        r#"<c:ser>{}{}<c:marker><c:symbol val="circle"/><c:size val="6"/></c:marker>{}<c:smooth val="0"/></c:ser>"#,
+                        series_header(spec, index),
+                        line_properties(series_color(spec, index)),
+                        category_values(spec, index)
+                    )
+                })
+                .collect();
+
+            format!(
+                r#"<c:lineChart><c:grouping val="standard"/><c:varyColors val="0"/>{}<c:marker val="1"/>{}</c:lineChart>{}{}"#,
+                series,
+                axis_ids,
+                category_axis("b"),
+                value_axis(VALUE_AXIS_ID, CATEGORY_AXIS_ID, "l", true)
+            )
+        }
+        ChartKind::Pie | ChartKind::Donut => {
+            // 饼图只使用第一个系列；圆环图每个系列为一环
+            let count = if spec.kind == ChartKind::Pie {
+                1
+            } else {
+                spec.data.series.len()
+            };
+            let series: String = (0..count)
+                .map(|index| {
+                    let points: String = (0..spec.data.categories.len())
+                        .filter_map(|point| {
+                            spec.color(point).map(|color| {
+                                format!(
+                                    r#"<c:dPt><c:idx val="{}"/><c:bubble3D val="0"/>{}</c:dPt>"#,
+                                    point,
+                                    fill_properties(Some(color))
+                                )
+                            })
+                        })
+                        .collect();
+                    format!(
+                        "<c:ser>{}{}{}</c:ser>",
+                        series_header(spec, index),
+                        points,
+                        category_values(spec, index)
+                    )
+                })
+                .collect();
+
+            if spec.kind == ChartKind::Pie {
+                format!(
+                    r#"<c:pieChart><c:varyColors val="1"/>{}<c:firstSliceAng val="0"/></c:pieChart>"#,
+                    series
+                )
+            } else {
+                format!(
+                    r#"<c:doughnutChart><c:varyColors val="1"/>{}<c:firstSliceAng val="0"/><c:holeSize val="50"/></c:doughnutChart>"#,
+                    series
+                )
+            }
+        }
+        ChartKind::Scatter => {
+            let series: String = spec
+                .data
+                .series
+                .iter()
+                .enumerate()
+                .map(|(index, data)| {
+                    let x_column = index * 2;
+                    let last_row = data.values.len() + 1;
+                    let color = series_color(spec, index);
+                    format!(
+                        r#"<c:ser><c:idx val="{0}"/><c:order val="{0}"/>{1}<c:spPr><a:ln w="19050"><a:noFill/></a:ln></c:spPr><c:marker><c:symbol val="circle"/><c:size val="7"/>{2}</c:marker><c:xVal>{3}</c:xVal><c:yVal>{4}</c:yVal><c:smooth val="0"/></c:ser>"#,
+                        index,
+                        series_name(&range_ref(x_column + 1, 1, 1), &data.name),
+                        fill_properties(color),
+                        number_ref(&range_ref(x_column, 2, last_row), &data.x_values),
+                        number_ref(&range_ref(x_column + 1, 2, last_row), &data.values)
+                    )
+                })
+                .collect();
+
+            format!(
+                r#"<c:scatterChart><c:scatterStyle val="lineMarker"/><c:varyColors val="0"/>{}{}</c:scatterChart>{}{}"#,
+                series,
+                axis_ids,
+                value_axis(CATEGORY_AXIS_ID, VALUE_AXIS_ID, "b", false),
+                value_axis(VALUE_AXIS_ID, CATEGORY_AXIS_ID, "l", true)
+            )
+        }
+    }
+}
+
+/// 系列颜色：系列自身颜色优先，其次为图表配色
+fn series_color(spec: &ChartSpec, index: usize) -> Option<String> {
+    spec.data.series[index]
+        .color
+        .as_ref()
+        .map(|color| color.trim().trim_start_matches('#').to_uppercase())
+        .or_else(|| spec.color(index))
+}
+
+/// `c:idx`、`c:order` 与引用第 1 行表头的系列名称
+fn series_header(spec: &ChartSpec, index: usize) -> String {
+    format!(
+        r#"<c:idx val="{0}"/><c:order val="{0}"/>{1}"#,
+        index,
+        series_name(&range_ref(index + 1, 1, 1), &spec.data.series[index].name)
+    )
+}
+
+fn series_name(reference: &str, name: &str) -> String {
+    format!(
+        r#"<c:tx><c:strRef><c:f>{}</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>{}</c:v></c:pt></c:strCache></c:strRef></c:tx>"#,
+        reference,
+        escape_xml(name)
+    )
+}
+
+/// 分类（A 列）与系列数值
+fn category_values(spec: &ChartSpec, index: usize) -> String {
+    let categories = &spec.data.categories;
+    let last_row = categories.len() + 1;
+
+    let points: String = categories
+        .iter()
+        .enumerate()
+        .map(|(point, category)| {
+            format!(
+                r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#,
+                point,
+                escape_xml(category)
+            )
+        })
+        .collect();
+
+    format!(
+        r#"<c:cat><c:strRef><c:f>{}</c:f><c:strCache><c:ptCount val="{}"/>{}</c:strCache></c:strRef></c:cat><c:val>{}</c:val>"#,
+        range_ref(0, 2, last_row),
+        categories.len(),
+        points,
+        number_ref(
+            &range_ref(index + 1, 2, last_row),
+            &spec.data.series[index].values
+        )
+    )
+}
+
+fn number_ref(reference: &str, values: &[f64]) -> String {
+    let points: String = values
+        .iter()
+        .enumerate()
+        .map(|(point, value)| format!(r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, point, value))
+        .collect();
+
+    format!(
+        r#"<c:numRef><c:f>{}</c:f><c:numCache><c:formatCode>General</c:formatCode><c:ptCount val="{}"/>{}</c:numCache></c:numRef>"#,
+        reference,
+        values.len(),
+        points
+    )
+}
+
+fn fill_properties(color: Option<String>) -> String {
+    match color {
+        Some(color) => format!(
+            r#"<c:spPr><a:solidFill><a:srgbClr val="{}"/></a:solidFill></c:spPr>"#,
+            escape_xml(&color)
+        ),
+        None => String::new(),
+    }
+}
+
+fn line_properties(color: Option<String>) -> String {
+    let fill = match color {
+        Some(color) => format!(
+            r#"<a:solidFill><a:srgbClr val="{}"/></a:solidFill>"#,
+            escape_xml(&color)
+        ),
+        None => String::new(),
+    };
+    format!(
+        r#"<c:spPr><a:ln w="28575" cap="rnd">{}<a:round/></a:ln></c:spPr>"#,
+        fill
+    )
+}
+
+fn category_axis(position: &str) -> String {
+    format!(
+        r#"<c:catAx><c:axId val="{}"/><c:scaling><c:orientation val="minMax"/></c:scaling><c:delete val="0"/><c:axPos val="{}"/><c:numFmt formatCode="General" sourceLinked="1"/><c:majorTickMark val="none"/><c:minorTickMark val="none"/><c:tickLblPos val="nextTo"/><c:crossAx val="{}"/><c:crosses val="autoZero"/><c:auto val="1"/><c:lblAlgn val="ctr"/><c:lblOffset val="100"/><c:noMultiLvlLbl val="0"/></c:catAx>"#,
+        CATEGORY_AXIS_ID, position, VALUE_AXIS_ID
+    )
+}
+
+fn value_axis(id: u32, cross_axis: u32, position: &str, gridlines: bool) -> String {
+    format!(
+        r#"<c:valAx><c:axId val="{}"/><c:scaling><c:orientation val="minMax"/></c:scaling><c:delete val="0"/><c:axPos val="{}"/>{}<c:numFmt formatCode="General" sourceLinked="1"/><c:majorTickMark val="none"/><c:minorTickMark val="none"/><c:tickLblPos val="nextTo"/><c:crossAx val="{}"/><c:crosses val="autoZero"/><c:crossBetween val="between"/></c:valAx>"#,
+        id,
+        position,
+        if gridlines { "<c:majorGridlines/>" } else { "" },
+        cross_axis
+    )
+}
@@ -0,0 +1,246 @@
+// 原生图表
+//
+// 根据图表数据生成 c:chartSpace 部件与内嵌的 xlsx 工作簿，
+// 导出的图表在 PowerPoint 中可以直接编辑数据
+
+mod chart_space;
+pub mod workbook;
+
+use crate::{PptxError, Result};
+use serde::Deserialize;
+
+/// 图表类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    /// 条形图（横向）
+    Bar,
+    /// 柱形图（纵向）
+    Column,
+    /// 折线图
+    Line,
+    /// 饼图
+    Pie,
+    /// 圆环图
+    Donut,
+    /// 散点图
+    Scatter,
+}
+
+impl ChartKind {
+    /// 解析图表类型名称（不区分大小写）
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('_', "-").as_str() {
+            "bar" | "horizontal-bar" => Some(Self::Bar),
+            "column" | "vertical-bar" => Some(Self::Column),
+            "line" => Some(Self::Line),
+            "pie" => Some(Self::Pie),
+            "donut" | "doughnut" => Some(Self::Donut),
+            "scatter" => Some(Self::Scatter),
+            _ => None,
+        }
+    }
+
+    /// 是否带坐标轴
+    pub fn has_axes(self) -> bool {
+        !matches!(self, Self::Pie | Self::Donut)
+    }
+}
+
+/// 图表数据
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartData {
+    /// 图表标题
+    #[serde(default)]
+    pub title: Option<String>,
+    /// 分类（散点图不使用）
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// 数据系列
+    pub series: Vec<ChartSeries>,
+    /// 条形图/柱形图是否堆积
+    #[serde(default)]
+    pub stacked: bool,
+    /// 配色（`#RRGGBB`），依次用于各系列；饼图与圆环图用于各扇区
+    #[serde(default)]
+    pub colors: Vec<String>,
+    /// 是否显示图例（默认多系列或饼图时显示）
+    #[serde(default)]
+    pub legend: Option<bool>,
+}
+
+/// 数据系列
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChartSeries {
+    /// 系列名称
+    pub name: String,
+    /// 数值（散点图为 Y 值）
+    pub values: Vec<f64>,
+    /// 散点图的 X 值
+    #[serde(default)]
+    pub x_values: Vec<f64>,
+    /// 系列颜色（`#RRGGBB`），优先于图表配色
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+/// 完整的图表声明
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSpec {
+    pub kind: ChartKind,
+    pub data: ChartData,
+}
+
+impl ChartSpec {
+    /// 由类型名称与 JSON 数据创建图表，并检查数据完整性
+    pub fn from_json(kind: &str, json: &str) -> Result<Self> {
+        let kind = ChartKind::parse(kind)
+            .ok_or_else(|| PptxError::SvgParse(format!("不支持的图表类型: {}", kind)))?;
+        let data: ChartData = serde_json::from_str(json)
+            .map_err(|e| PptxError::SvgParse(format!("图表数据无效: {}", e)))?;
+
+        let spec = Self { kind, data };
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid =
+            |message: String| Err(PptxError::SvgParse(format!("图表数据无效: {}", message)));
+
+        if self.data.series.is_empty() {
+            return invalid("至少需要一个数据系列".to_string());
+        }
+
+        for series in &self.data.series {
+            if self.kind == ChartKind::Scatter {
+                if series.x_values.len() != series.values.len() {
+                    return invalid(format!(
+                        "系列「{}」的 xValues 与 values 数量不一致",
+                        series.name
+                    ));
+                }
+            } else if series.values.len() != self.data.categories.len() {
+                return invalid(format!(
+                    "系列「{}」的数值数量（{}）与分类数量（{}）不一致",
+                    series.name,
+                    series.values.len(),
+                    self.data.categories.len()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 生成图表部件 XML，`workbook_rid` 为图表部件指向内嵌工作簿的关系 ID
+    pub fn to_chart_xml(&self, workbook_rid: &str) -> String {
+        chart_space::chart_space_xml(self, workbook_rid)
+    }
+
+    /// 生成内嵌工作簿
+    pub fn to_workbook(&self) -> Result<Vec<u8>> {
+        workbook::build_workbook(self)
+    }
+
+    /// 系列（或饼图扇区）颜色，不带 `#`
+    fn color(&self, index: usize) -> Option<String> {
+        self.data
+            .colors
+            .get(index)
+            .map(|color| color.trim().trim_start_matches('#').to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    const COLUMN_DATA: &str = r##"{
+        "title": "季度营收",
+        "categories": ["Q1", "Q2", "Q3"],
+        "series": [
+            {"name": "2024", "values": [120, 135.5, 150]},
+            {"name": "2025", "values": [140, 160, 182], "color": "#34A853"}
+        ],
+        "colors": ["#1A73E8"]
+    }"##;
+
+    #[test]
+    fn test_chart_kind_parse() {
+        assert_eq!(ChartKind::parse("Column"), Some(ChartKind::Column));
+        assert_eq!(ChartKind::parse("horizontal_bar"), Some(ChartKind::Bar));
+        assert_eq!(ChartKind::parse("doughnut"), Some(ChartKind::Donut));
+        assert_eq!(ChartKind::parse("radar"), None);
+    }
+
+    #[test]
+    fn test_invalid_chart_data() {
+        let mismatch = r#"{"categories": ["A", "B"], "series": [{"name": "S", "values": [1]}]}"#;
+        assert!(ChartSpec::from_json("column", mismatch).is_err());
+        assert!(ChartSpec::from_json("column", r#"{"series": []}"#).is_err());
+        assert!(ChartSpec::from_json("radar", COLUMN_DATA).is_err());
+    }
+
+    #[test]
+    fn test_column_chart_xml() {
+        let spec = ChartSpec::from_json("column", COLUMN_DATA).expect("解析应成功");
+        let xml = spec.to_chart_xml("rId1");
+
+        assert!(xml.contains(r#"<c:barDir val="col"/><c:grouping val="clustered"/>"#));
+        assert!(xml.contains("<c:f>Sheet1!$C$1</c:f>"));
+        assert!(xml.contains("<c:f>Sheet1!$A$2:$A$4</c:f>"));
+        assert!(xml.contains(r#"<c:pt idx="1"><c:v>135.5</c:v></c:pt>"#));
+        assert!(xml.contains(r#"<a:srgbClr val="1A73E8"/>"#));
+        assert!(xml.contains(r#"<a:srgbClr val="34A853"/>"#));
+        assert!(xml.contains("<a:t>季度营收</a:t>"));
+        assert!(xml.contains(r#"<c:legendPos val="b"/>"#));
+        assert!(xml.contains(r#"<c:externalData r:id="rId1">"#));
+        assert!(xml.find("<c:catAx>") < xml.find("<c:valAx>"));
+    }
+
+    #[test]
+    fn test_pie_and_scatter_xml() {
+        let pie = ChartSpec::from_json(
+            "donut",
+            r##"{"categories": ["A", "B"], "series": [{"name": "占比", "values": [60, 40]}], "colors": ["#FF0000", "#00FF00"]}"##,
+        )
+        .expect("解析应成功");
+        let xml = pie.to_chart_xml("rId1");
+        assert!(xml.contains("<c:doughnutChart>"));
+        assert!(xml.contains(r#"<c:dPt><c:idx val="1"/><c:bubble3D val="0"/>"#));
+        assert!(!xml.contains("<c:catAx>"));
+
+        let scatter = ChartSpec::from_json(
+            "scatter",
+            r#"{"series": [{"name": "样本", "xValues": [1, 2], "values": [3, 4]}]}"#,
+        )
+        .expect("解析应成功");
+        let xml = scatter.to_chart_xml("rId1");
+        assert!(xml.contains(r#"<c:scatterStyle val="lineMarker"/>"#));
+        assert!(xml.contains("<c:xVal><c:numRef><c:f>Sheet1!$A$2:$A$3</c:f>"));
+        assert!(xml.contains("<c:yVal><c:numRef><c:f>Sheet1!$B$2:$B$3</c:f>"));
+        assert_eq!(xml.matches("<c:valAx>").count(), 2);
+    }
+
+    #[test]
+    fn test_workbook_contents() {
+        let spec = ChartSpec::from_json("line", COLUMN_DATA).expect("解析应成功");
+        let data = spec.to_workbook().expect("生成工作簿应成功");
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(data)).unwrap();
+        let mut sheet = String::new();
+        archive
+            .by_name("xl/worksheets/sheet1.xml")
+            .unwrap()
+            .read_to_string(&mut sheet)
+            .unwrap();
+
+        assert!(sheet.contains(r#"<c r="B1" t="inlineStr"><is><t>2024</t></is></c>"#));
+        assert!(sheet.contains(r#"<c r="A3" t="inlineStr"><is><t>Q2</t></is></c>"#));
+        assert!(sheet.contains(r#"<c r="C4"><v>182</v></c>"#));
+        assert!(archive.by_name("xl/workbook.xml").is_ok());
+    }
+}
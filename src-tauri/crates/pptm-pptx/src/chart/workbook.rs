@@ -0,0 +1,172 @@
+// 图表内嵌工作簿
+// 生成只包含一张工作表的最小 xlsx，单元格使用内联字符串，无需共享字符串表
+
+use super::{ChartKind, ChartSpec};
+use crate::{escape_xml, Result};
+use std::io::{Cursor, Write};
+use zip::write::{FileOptions, ZipWriter};
+use zip::CompressionMethod;
+
+/// 工作表名称（图表公式引用）
+pub const SHEET_NAME: &str = "Sheet1";
+
+/// 单元格内容
+#[derive(Clone, Copy)]
+enum Cell<'a> {
+    Text(&'a str),
+    Number(f64),
+}
+
+/// 列号（从 0 开始）转换为列名，如 0 -> A、26 -> AA
+pub fn column_name(index: usize) -> String {
+    let mut name = String::new();
+    let mut index = index + 1;
+    while index > 0 {
+        let rem = (index - 1) % 26;
+        name.insert(0, (b'A' + rem as u8) as char);
+        index = (index - 1) / 26;
+    }
+    name
+}
+
+/// 绝对引用的单元格区域，如 `Sheet1!$B$2:$B$5`
+pub fn range_ref(column: usize, first_row: usize, last_row: usize) -> String {
+    let column = column_name(column);
+    if first_row == last_row {
+        format!("{}!${}${}", SHEET_NAME, column, first_row)
+    } else {
+        format!(
+            "{}!${}${}:${}${}",
+            SHEET_NAME, column, first_row, column, last_row
+        )
+    }
+}
+
+/// 生成图表数据对应的 xlsx
+///
+/// 分类图表：A 列为分类，第 1 行为系列名称；散点图：每个系列占两列（X、Y）
+pub fn build_workbook(spec: &ChartSpec) -> Result<Vec<u8>> {
+    let rows = sheet_rows(spec);
+
+    let mut sheet = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#,
+    );
+    for (row_index, row) in rows.iter().enumerate() {
+        sheet.push_str(&format!(r#"<row r="{}">"#, row_index + 1));
+        for (column, cell) in row.iter().enumerate() {
+            let Some(cell) = cell else {
+                continue;
+            };
+            let reference = format!("{}{}", column_name(column), row_index + 1);
+            match cell {
+                Cell::Text(text) => sheet.push_str(&format!(
+                    r#"<c r="{}" t="inlineStr"><is><t>{}</t></is></c>"#,
+                    reference,
+                    escape_xml(text)
+                )),
+                Cell::Number(value) => {
+                    sheet.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, value))
+                }
+            }
+        }
+        sheet.push_str("</row>");
+    }
+    sheet.push_str("</sheetData></worksheet>");
+
+    let files: [(&str, String); 5] = [
+        (
+            "[Content_Types].xml",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#
+                .to_string(),
+        ),
+        (
+            "_rels/.rels",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#
+                .to_string(),
+        ),
+        (
+            "xl/workbook.xml",
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+                SHEET_NAME
+            ),
+        ),
+        (
+            "xl/_rels/workbook.xml.rels",
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/></Relationships>"#
+                .to_string(),
+        ),
+        ("xl/worksheets/sheet1.xml", sheet),
+    ];
+
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, content) in files {
+        zip.start_file(name, options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+/// 按行排列的单元格
+fn sheet_rows(spec: &ChartSpec) -> Vec<Vec<Option<Cell<'_>>>> {
+    let data = &spec.data;
+
+    if spec.kind == ChartKind::Scatter {
+        let height = data
+            .series
+            .iter()
+            .map(|series| series.values.len())
+            .max()
+            .unwrap_or(0);
+        let mut rows: Vec<Vec<Option<Cell>>> = vec![Vec::new(); height + 1];
+        for series in &data.series {
+            rows[0].push(Some(Cell::Text("X")));
+            rows[0].push(Some(Cell::Text(&series.name)));
+            for (row, row_cells) in rows.iter_mut().enumerate().skip(1) {
+                row_cells.push(series.x_values.get(row - 1).copied().map(Cell::Number));
+                row_cells.push(series.values.get(row - 1).copied().map(Cell::Number));
+            }
+        }
+        return rows;
+    }
+
+    let mut header = vec![None];
+    header.extend(
+        data.series
+            .iter()
+            .map(|series| Some(Cell::Text(series.name.as_str()))),
+    );
+
+    let mut rows = vec![header];
+    for (index, category) in data.categories.iter().enumerate() {
+        let mut row = vec![Some(Cell::Text(category.as_str()))];
+        row.extend(
+            data.series
+                .iter()
+                .map(|series| series.values.get(index).copied().map(Cell::Number)),
+        );
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_name_and_range() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(range_ref(1, 2, 5), "Sheet1!$B$2:$B$5");
+        assert_eq!(range_ref(1, 1, 1), "Sheet1!$B$1");
+    }
+}
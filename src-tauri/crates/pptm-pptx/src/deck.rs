@@ -0,0 +1,464 @@
+// 演示文稿合并与拆分
+// 按给定顺序加载多个项目的幻灯片，截取指定范围，并统一到同一画布尺寸后导出为一个 PPTX
+
+use crate::media::inline_images;
+use crate::{load_slides, PptxConfig, PptxError, Result, Slide, SlideContent};
+use pptm_domain::project_utils::get_project_info;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use std::path::{Path, PathBuf};
+
+/// 长宽比相差不超过该比例时视为同一画布（只需缩放）
+const ASPECT_TOLERANCE: f32 = 0.005;
+
+/// 幻灯片范围（序号从 1 开始，包含两端）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlideRange {
+    pub start: usize,
+    /// None 表示到最后一页
+    pub end: Option<usize>,
+}
+
+impl SlideRange {
+    /// 解析 `1-3,5,8-` 形式的范围列表，空字符串表示全部幻灯片
+    pub fn parse_list(spec: &str) -> Result<Vec<SlideRange>> {
+        spec.split([',', '，'])
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| {
+                let invalid = || PptxError::SlideRange(format!("无效的幻灯片范围: {}", part));
+                let number = |text: &str| -> Result<usize> {
+                    match text.trim().parse::<usize>() {
+                        Ok(number) if number > 0 => Ok(number),
+                        _ => Err(invalid()),
+                    }
+                };
+
+                let range = match part.split_once('-') {
+                    Some((start, end)) if end.trim().is_empty() => SlideRange {
+                        start: number(start)?,
+                        end: None,
+                    },
+                    Some((start, end)) => SlideRange {
+                        start: number(start)?,
+                        end: Some(number(end)?),
+                    },
+                    None => {
+                        let page = number(part)?;
+                        SlideRange {
+                            start: page,
+                            end: Some(page),
+                        }
+                    }
+                };
+
+                match range.end {
+                    Some(end) if end < range.start => Err(invalid()),
+                    _ => Ok(range),
+                }
+            })
+            .collect()
+    }
+}
+
+/// 合并来源：一个项目及要截取的幻灯片范围
+#[derive(Debug, Clone)]
+pub struct DeckSource {
+    pub project_path: PathBuf,
+    /// 为空时取全部幻灯片
+    pub ranges: Vec<SlideRange>,
+}
+
+/// 按范围顺序截取幻灯片，范围超出幻灯片数量时返回错误
+pub fn select_slides(slides: &[Slide], ranges: &[SlideRange]) -> Result<Vec<Slide>> {
+    if ranges.is_empty() {
+        return Ok(slides.to_vec());
+    }
+
+    let mut selected = Vec::new();
+    for range in ranges {
+        let end = range.end.unwrap_or(slides.len());
+        if range.start > slides.len() || end > slides.len() {
+            return Err(PptxError::SlideRange(format!(
+                "范围 {}-{} 超出幻灯片数量 {}",
+                range.start,
+                end,
+                slides.len()
+            )));
+        }
+        selected.extend_from_slice(&slides[range.start - 1..end]);
+    }
+
+    Ok(selected)
+}
+
+/// 项目画布尺寸（由项目目录名中的画布格式确定），未知格式返回 None
+pub fn project_canvas(project_path: &Path) -> Option<(u32, u32)> {
+    get_project_info(project_path)
+        .canvas_info
+        .map(|canvas| (canvas.width, canvas.height))
+}
+
+/// 按顺序加载并合并多个项目的幻灯片
+///
+/// 每个项目按自身画布加载，长宽比与 `config` 画布不同的幻灯片居中等比缩放（留白）；
+/// SVG 中以相对路径引用的图片按所属项目的 `svg_final` 目录内嵌；
+/// 没有分节的幻灯片以项目名称作为分节，合并后重新编号
+pub fn load_merged_slides(sources: &[DeckSource], config: &PptxConfig) -> Result<Vec<Slide>> {
+    let mut merged = Vec::new();
+
+    for source in sources {
+        let (width, height) =
+            project_canvas(&source.project_path).unwrap_or((config.width, config.height));
+        let project_config = PptxConfig {
+            width,
+            height,
+            ..config.clone()
+        };
+
+        let slides = load_slides(&source.project_path, &project_config)?;
+        let project_name = get_project_info(&source.project_path).name;
+        let svg_dir = source.project_path.join("svg_final");
+
+        for mut slide in select_slides(&slides, &source.ranges)? {
+            slide.section.get_or_insert_with(|| project_name.clone());
+            // 合并后不再有单一的项目目录，相对图片路径需在此按来源项目解析
+            if let SlideContent::Svg(svg) = &slide.content {
+                slide.content = SlideContent::Svg(inline_images(svg, &svg_dir)?);
+            }
+            slide.content = fit_to_canvas(slide.content, config.width, config.height)?;
+            slide.source_svg = slide
+                .source_svg
+                .map(|svg| fit_svg_to_canvas(svg, config.width, config.height))
+                .transpose()?;
+            merged.push(slide);
+        }
+    }
+
+    for (index, slide) in merged.iter_mut().enumerate() {
+        slide.number = index + 1;
+    }
+
+    Ok(merged)
+}
+
+/// 将幻灯片内容统一到 `width` x `height` 画布
+///
+/// 长宽比一致时保持原样（导出时按比例缩放）；不一致时 SVG 改写根元素尺寸并居中等比缩放，
+/// PNG 重新绘制到目标画布中央
+pub fn fit_to_canvas(content: SlideContent, width: u32, height: u32) -> Result<SlideContent> {
+    match content {
+        SlideContent::Svg(svg) => Ok(SlideContent::Svg(fit_svg_to_canvas(svg, width, height)?)),
+        SlideContent::Png(png) => {
+            let source = tiny_skia::Pixmap::decode_png(&png)
+                .map_err(|e| PptxError::PngConversion(e.to_string()))?;
+            let size = (source.width() as f32, source.height() as f32);
+            if same_aspect(size, (width as f32, height as f32)) {
+                return Ok(SlideContent::Png(png));
+            }
+            Ok(SlideContent::Png(letterbox_png(&source, width, height)?))
+        }
+    }
+}
+
+/// 将 SVG 统一到 `width` x `height` 画布（长宽比一致时保持原样）
+fn fit_svg_to_canvas(svg: String, width: u32, height: u32) -> Result<String> {
+    match svg_canvas(&svg)? {
+        Some(size) if !same_aspect(size, (width as f32, height as f32)) => {
+            letterbox_svg(&svg, size, width, height)
+        }
+        _ => Ok(svg),
+    }
+}
+
+fn same_aspect(a: (f32, f32), b: (f32, f32)) -> bool {
+    let (a, b) = (a.0 / a.1, b.0 / b.1);
+    ((a - b) / b).abs() <= ASPECT_TOLERANCE
+}
+
+/// SVG 根元素的画布尺寸：width/height 优先，其次为 viewBox
+fn svg_canvas(svg: &str) -> Result<Option<(f32, f32)>> {
+    let Some(root) = root_element(svg)? else {
+        return Ok(None);
+    };
+
+    let width = root_attribute(&root, b"width").and_then(|value| parse_length(&value));
+    let height = root_attribute(&root, b"height").and_then(|value| parse_length(&value));
+    if let Some(size) = width.zip(height) {
+        return Ok(Some(size));
+    }
+
+    Ok(root_attribute(&root, b"viewBox")
+        .and_then(|value| parse_view_box(&value))
+        .map(|[_, _, width, height]| (width, height)))
+}
+
+/// 改写根元素：尺寸设为目标画布，保留原 viewBox 并居中等比缩放
+fn letterbox_svg(svg: &str, size: (f32, f32), width: u32, height: u32) -> Result<String> {
+    let mut reader = Reader::from_str(svg);
+    let mut writer = Writer::new(Vec::with_capacity(svg.len() + 128));
+    let mut rewritten = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+        let event = match event {
+            Event::Eof => break,
+            Event::Start(start) if !rewritten && start.local_name().as_ref() == b"svg" => {
+                rewritten = true;
+                Event::Start(letterbox_root(&start, size, width, height))
+            }
+            Event::Empty(start) if !rewritten && start.local_name().as_ref() == b"svg" => {
+                rewritten = true;
+                Event::Empty(letterbox_root(&start, size, width, height))
+            }
+            other => other,
+        };
+        writer
+            .write_event(event)
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| PptxError::SvgParse(e.to_string()))
+}
+
+fn letterbox_root(
+    start: &BytesStart,
+    size: (f32, f32),
+    width: u32,
+    height: u32,
+) -> BytesStart<'static> {
+    let mut root = BytesStart::new(String::from_utf8_lossy(start.name().as_ref()).into_owned());
+    let mut has_view_box = false;
+
+    for attr in start.attributes().flatten() {
+        match attr.key.as_ref() {
+            b"width" | b"height" | b"preserveAspectRatio" => {}
+            key => {
+                has_view_box |= key == b"viewBox";
+                root.push_attribute(attr);
+            }
+        }
+    }
+
+    let (width, height) = (width.to_string(), height.to_string());
+    root.push_attribute(("width", width.as_str()));
+    root.push_attribute(("height", height.as_str()));
+    if !has_view_box {
+        let view_box = format!("0 0 {} {}", size.0, size.1);
+        root.push_attribute(("viewBox", view_box.as_str()));
+    }
+    root.push_attribute(("preserveAspectRatio", "xMidYMid meet"));
+    root
+}
+
+fn letterbox_png(source: &tiny_skia::Pixmap, width: u32, height: u32) -> Result<Vec<u8>> {
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| PptxError::PngConversion("无法创建 pixmap".to_string()))?;
+
+    let scale = (width as f32 / source.width() as f32).min(height as f32 / source.height() as f32);
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+        (width as f32 - source.width() as f32 * scale) / 2.0,
+        (height as f32 - source.height() as f32 * scale) / 2.0,
+    );
+    let paint = tiny_skia::PixmapPaint {
+        quality: tiny_skia::FilterQuality::Bicubic,
+        ..tiny_skia::PixmapPaint::default()
+    };
+    pixmap.draw_pixmap(0, 0, source.as_ref(), &paint, transform, None);
+
+    pixmap
+        .encode_png()
+        .map_err(|e| PptxError::PngConversion(e.to_string()))
+}
+
+/// 第一个元素（SVG 根元素）
+fn root_element(svg: &str) -> Result<Option<BytesStart<'static>>> {
+    let mut reader = Reader::from_str(svg);
+    loop {
+        match reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?
+        {
+            Event::Start(start) | Event::Empty(start) => return Ok(Some(start.into_owned())),
+            Event::Eof => return Ok(None),
+            _ => {}
+        }
+    }
+}
+
+fn root_attribute(root: &BytesStart, name: &[u8]) -> Option<String> {
+    root.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == name)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.to_string())
+}
+
+/// 解析绝对长度（像素，可带 `px` 后缀），百分比等相对长度返回 None
+fn parse_length(value: &str) -> Option<f32> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value).trim();
+    number.parse::<f32>().ok().filter(|length| *length > 0.0)
+}
+
+fn parse_view_box(value: &str) -> Option<[f32; 4]> {
+    let numbers: Vec<f32> = value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f32>().ok())
+        .collect::<Option<_>>()?;
+
+    match numbers.as_slice() {
+        [x, y, w, h] if *w > 0.0 && *h > 0.0 => Some([*x, *y, *w, *h]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_slide_ranges() {
+        let ranges = SlideRange::parse_list("1-3, 5，8-").expect("解析应成功");
+        assert_eq!(
+            ranges,
+            vec![
+                SlideRange {
+                    start: 1,
+                    end: Some(3)
+                },
+                SlideRange {
+                    start: 5,
+                    end: Some(5)
+                },
+                SlideRange {
+                    start: 8,
+                    end: None
+                },
+            ]
+        );
+        assert!(SlideRange::parse_list("").unwrap().is_empty());
+        assert!(SlideRange::parse_list("3-1").is_err());
+        assert!(SlideRange::parse_list("0").is_err());
+        assert!(SlideRange::parse_list("a-b").is_err());
+    }
+
+    #[test]
+    fn test_fit_svg_to_canvas() {
+        let wide = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"/>"#;
+        match fit_to_canvas(SlideContent::Svg(wide.to_string()), 1920, 1080).unwrap() {
+            SlideContent::Svg(svg) => assert_eq!(svg, wide),
+            SlideContent::Png(_) => unreachable!(),
+        }
+
+        let square = r#"<svg xmlns="http://www.w3.org/2000/svg" width="1080px" height="1080"><rect width="1080" height="1080"/></svg>"#;
+        match fit_to_canvas(SlideContent::Svg(square.to_string()), 1280, 720).unwrap() {
+            SlideContent::Svg(svg) => assert_eq!(
+                svg,
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="1280" height="720" viewBox="0 0 1080 1080" preserveAspectRatio="xMidYMid meet"><rect width="1080" height="1080"/></svg>"#
+            ),
+            SlideContent::Png(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_merge_projects() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let write_project = |dir: &str, slides: &[&str], view_box: &str| {
+            let project = temp_dir.path().join(dir);
+            std::fs::create_dir_all(project.join("svg_final")).unwrap();
+            for name in slides {
+                std::fs::write(
+                    project.join("svg_final").join(format!("{}.svg", name)),
+                    format!(
+                        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{}"/>"#,
+                        view_box
+                    ),
+                )
+                .unwrap();
+            }
+            project
+        };
+
+        let annual = write_project(
+            "annual_ppt169_20260101",
+            &["01_cover", "02_finance", "03_outlook"],
+            "0 0 1280 720",
+        );
+        let appendix = write_project("appendix_ppt43_20260101", &["01_team"], "0 0 1024 768");
+
+        let sources = vec![
+            DeckSource {
+                project_path: annual,
+                ranges: SlideRange::parse_list("2-").unwrap(),
+            },
+            DeckSource {
+                project_path: appendix,
+                ranges: Vec::new(),
+            },
+        ];
+        let slides = load_merged_slides(&sources, &PptxConfig::default()).expect("合并应成功");
+
+        let titles: Vec<_> = slides.iter().map(|slide| slide.title.as_str()).collect();
+        assert_eq!(titles, vec!["02_finance", "03_outlook", "01_team"]);
+        assert_eq!(slides[2].number, 3);
+        assert_eq!(slides[0].section.as_deref(), Some("annual"));
+        assert_eq!(slides[2].section.as_deref(), Some("appendix"));
+        match &slides[2].content {
+            SlideContent::Svg(svg) => {
+                assert!(svg.contains(
+                    r#"viewBox="0 0 1024 768" width="1280" height="720" preserveAspectRatio="xMidYMid meet""#
+                ))
+            }
+            SlideContent::Png(_) => panic!("应保留 SVG"),
+        }
+
+        let out_of_range = vec![DeckSource {
+            project_path: sources[1].project_path.clone(),
+            ranges: SlideRange::parse_list("2").unwrap(),
+        }];
+        assert!(load_merged_slides(&out_of_range, &PptxConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_merge_inlines_images_per_project() {
+        use base64::Engine;
+
+        // 两个项目用同一相对路径引用各自的图片
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let sources: Vec<_> = [b"first".as_slice(), b"second".as_slice()]
+            .iter()
+            .enumerate()
+            .map(|(index, logo)| {
+                let project = temp_dir.path().join(format!("deck{}_ppt169_20260101", index));
+                std::fs::create_dir_all(project.join("svg_final")).unwrap();
+                std::fs::create_dir_all(project.join("images")).unwrap();
+                std::fs::write(project.join("images/logo.png"), logo).unwrap();
+                std::fs::write(
+                    project.join("svg_final/01_logo.svg"),
+                    r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"><image href="../images/logo.png" width="10" height="10"/></svg>"#,
+                )
+                .unwrap();
+                DeckSource {
+                    project_path: project,
+                    ranges: Vec::new(),
+                }
+            })
+            .collect();
+
+        let slides = load_merged_slides(&sources, &PptxConfig::default()).expect("合并应成功");
+        let engine = base64::engine::general_purpose::STANDARD;
+        for (slide, logo) in slides.iter().zip([b"first".as_slice(), b"second"]) {
+            match &slide.content {
+                SlideContent::Svg(svg) => assert!(svg.contains(&format!(
+                    r#"href="data:image/png;base64,{}""#,
+                    engine.encode(logo)
+                ))),
+                SlideContent::Png(_) => panic!("应保留 SVG"),
+            }
+        }
+    }
+}
@@ -0,0 +1,402 @@
+// 替代文字
+//
+// usvg 会丢弃 <title>、<desc> 与 aria-* 属性。解析前先读取每个元素的替代文字，并确保
+// 带替代文字的元素有 ID；转换时写入 cNvPr 的 descr/title。aria-hidden="true" 或
+// role="presentation" 的元素标记为装饰性，读屏软件会跳过
+
+use crate::{escape_xml, PptxError, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::HashMap;
+
+/// 为没有 ID 的元素生成的 ID 前缀
+const GENERATED_ID_PREFIX: &str = "__svg_alt_";
+
+/// Office 2019 的「标记为装饰性」扩展
+const DECORATIVE_EXT: &str = r#"<a:extLst><a:ext uri="{C183D7F6-B498-43B3-948B-1728B52AA6E4}"><adec:decorative xmlns:adec="http://schemas.microsoft.com/office/drawing/2017/decorative" val="1"/></a:ext></a:extLst>"#;
+
+/// 元素的替代文字
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AltText {
+    /// `<title>` 子元素
+    pub title: Option<String>,
+    /// `aria-label` 属性，其次为 `<desc>` 子元素
+    pub description: Option<String>,
+    /// 装饰性元素
+    pub decorative: bool,
+}
+
+impl AltText {
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.description.is_none() && !self.decorative
+    }
+
+    /// 读屏软件朗读的文字：说明优先，其次为标题
+    pub fn text(&self) -> Option<&str> {
+        self.description.as_deref().or(self.title.as_deref())
+    }
+}
+
+/// SVG 中的替代文字
+#[derive(Debug, Clone, Default)]
+pub struct SvgAltText {
+    /// 根元素的标题与说明（描述整页）
+    pub document: AltText,
+    /// 元素 ID 到替代文字的映射
+    pub elements: HashMap<String, AltText>,
+    /// 自身及祖先都没有替代文字的图片（ID、文件名或序号）
+    pub unlabeled_images: Vec<String>,
+}
+
+/// 元素 ID 是否为替代文字提取时生成
+pub fn is_generated_id(id: &str) -> bool {
+    id.starts_with(GENERATED_ID_PREFIX)
+}
+
+/// 第一遍读取到的元素信息（按文档顺序）
+#[derive(Default)]
+struct ElementInfo {
+    parent: Option<usize>,
+    id: Option<String>,
+    is_image: bool,
+    href: Option<String>,
+    alt: AltText,
+}
+
+/// 正在读取的 `<title>` / `<desc>`
+struct PendingText {
+    owner: usize,
+    is_title: bool,
+    depth: usize,
+    text: String,
+}
+
+/// 提取替代文字，返回为带替代文字的元素补齐 ID 后的 SVG
+pub fn extract_alt_text(svg: &str) -> Result<(String, SvgAltText)> {
+    let elements = read_elements(svg)?;
+
+    let mut result = SvgAltText::default();
+    let mut image_count = 0usize;
+    let mut generated = Vec::new();
+
+    for (index, element) in elements.iter().enumerate() {
+        if index == 0 {
+            result.document = element.alt.clone();
+            continue;
+        }
+
+        if !element.alt.is_empty() {
+            let id = match &element.id {
+                Some(id) => id.clone(),
+                None => {
+                    generated.push(index);
+                    format!("{}{}", GENERATED_ID_PREFIX, index)
+                }
+            };
+            result.elements.insert(id, element.alt.clone());
+        }
+
+        if element.is_image {
+            image_count += 1;
+            // 根元素的标题描述整页，不视为图片的替代文字
+            let labeled = std::iter::successors(Some(index), |&i| elements[i].parent)
+                .take_while(|&i| i != 0)
+                .any(|i| !elements[i].alt.is_empty());
+            if !labeled {
+                result
+                    .unlabeled_images
+                    .push(image_label(element, image_count));
+            }
+        }
+    }
+
+    if generated.is_empty() {
+        return Ok((svg.to_string(), result));
+    }
+    Ok((assign_ids(svg, &generated)?, result))
+}
+
+fn image_label(element: &ElementInfo, number: usize) -> String {
+    if let Some(id) = &element.id {
+        return id.clone();
+    }
+    match &element.href {
+        Some(href) if !href.starts_with("data:") => {
+            href.rsplit(['/', '\\']).next().unwrap_or(href).to_string()
+        }
+        _ => format!("<image> #{}", number),
+    }
+}
+
+fn read_elements(svg: &str) -> Result<Vec<ElementInfo>> {
+    let mut reader = Reader::from_str(svg);
+    let mut elements: Vec<ElementInfo> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut pending: Option<PendingText> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+
+        match event {
+            Event::Eof => break,
+            Event::Start(ref start) | Event::Empty(ref start) => {
+                let is_start = matches!(event, Event::Start(_));
+                if let Some(text) = pending.as_mut() {
+                    if is_start {
+                        text.depth += 1;
+                    }
+                    continue;
+                }
+
+                let index = elements.len();
+                elements.push(element_info(start, stack.last().copied())?);
+
+                let name = start.local_name();
+                if is_start {
+                    if let (b"title" | b"desc", Some(&owner)) = (name.as_ref(), stack.last()) {
+                        pending = Some(PendingText {
+                            owner,
+                            is_title: name.as_ref() == b"title",
+                            depth: 1,
+                            text: String::new(),
+                        });
+                        continue;
+                    }
+                    stack.push(index);
+                }
+            }
+            Event::End(_) => {
+                if let Some(text) = pending.as_mut() {
+                    text.depth -= 1;
+                    if text.depth == 0 {
+                        let text = pending.take().expect("正在读取标题或说明");
+                        let content = collapse_whitespace(&text.text);
+                        let alt = &mut elements[text.owner].alt;
+                        if !content.is_empty() {
+                            if text.is_title {
+                                alt.title.get_or_insert(content);
+                            } else if alt.description.is_none() {
+                                alt.description = Some(content);
+                            }
+                        }
+                    }
+                    continue;
+                }
+                stack.pop();
+            }
+            Event::Text(text) => {
+                if let Some(pending) = pending.as_mut() {
+                    let text = text
+                        .unescape()
+                        .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+                    pending.text.push_str(&text);
+                }
+            }
+            Event::CData(data) => {
+                if let Some(pending) = pending.as_mut() {
+                    pending
+                        .text
+                        .push_str(&String::from_utf8_lossy(data.as_ref()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(elements)
+}
+
+fn element_info(start: &BytesStart, parent: Option<usize>) -> Result<ElementInfo> {
+    let mut info = ElementInfo {
+        parent,
+        is_image: start.local_name().as_ref() == b"image",
+        ..ElementInfo::default()
+    };
+
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| PptxError::SvgParse(e.to_string()))?;
+        let value = attr
+            .unescape_value()
+            .map(|value| collapse_whitespace(&value))
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+
+        match attr.key.as_ref() {
+            b"id" if !value.is_empty() => info.id = Some(value),
+            b"href" | b"xlink:href" => info.href = Some(value),
+            // aria-label 优先于 <desc>
+            b"aria-label" if !value.is_empty() => info.alt.description = Some(value),
+            b"aria-hidden" => info.alt.decorative |= value == "true",
+            b"role" => info.alt.decorative |= matches!(value.as_str(), "presentation" | "none"),
+            _ => {}
+        }
+    }
+
+    Ok(info)
+}
+
+/// 为第 `indices` 个元素（文档顺序）补上生成的 ID
+fn assign_ids(svg: &str, indices: &[usize]) -> Result<String> {
+    let mut reader = Reader::from_str(svg);
+    let mut writer = Writer::new(Vec::with_capacity(svg.len() + indices.len() * 24));
+    let mut index = 0usize;
+    let mut skip_depth = 0usize;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+
+        // <title>/<desc> 内部的元素在第一遍中没有编号
+        let event = match event {
+            Event::Eof => break,
+            Event::Start(start) if skip_depth > 0 => {
+                skip_depth += 1;
+                Event::Start(start)
+            }
+            Event::End(end) if skip_depth > 0 => {
+                skip_depth -= 1;
+                Event::End(end)
+            }
+            Event::Start(start) => {
+                if matches!(start.local_name().as_ref(), b"title" | b"desc") && index > 0 {
+                    skip_depth = 1;
+                }
+                index += 1;
+                Event::Start(with_generated_id(start, index - 1, indices))
+            }
+            Event::Empty(start) if skip_depth == 0 => {
+                index += 1;
+                Event::Empty(with_generated_id(start, index - 1, indices))
+            }
+            other => other,
+        };
+
+        writer
+            .write_event(event)
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| PptxError::SvgParse(e.to_string()))
+}
+
+fn with_generated_id<'a>(
+    mut start: BytesStart<'a>,
+    index: usize,
+    indices: &[usize],
+) -> BytesStart<'a> {
+    if indices.binary_search(&index).is_ok() {
+        let id = format!("{}{}", GENERATED_ID_PREFIX, index);
+        start.push_attribute(("id", id.as_str()));
+    }
+    start
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 为片段中的第一个形状（分组时为分组自身）写入替代文字
+///
+/// 已有替代文字的形状保持不变；装饰性元素只写入装饰性标记
+pub fn describe(xml: &str, alt: &AltText) -> String {
+    const OPEN: &str = "<p:cNvPr ";
+
+    let Some(start) = xml.find(OPEN) else {
+        return xml.to_string();
+    };
+    let Some(close) = xml[start..].find('>').map(|offset| start + offset) else {
+        return xml.to_string();
+    };
+    let tag = &xml[start..close];
+    let self_closing = tag.ends_with('/');
+    let content_end = if self_closing {
+        close
+    } else {
+        xml[close..]
+            .find("</p:cNvPr>")
+            .map(|offset| close + offset)
+            .unwrap_or(close)
+    };
+    if tag.contains(" descr=") || xml[close..content_end].contains("adec:decorative") {
+        return xml.to_string();
+    }
+
+    let tag_end = if self_closing { close - 1 } else { close };
+
+    if alt.decorative {
+        let mut result = String::with_capacity(xml.len() + DECORATIVE_EXT.len() + 16);
+        result.push_str(&xml[..tag_end]);
+        if self_closing {
+            result.push('>');
+            result.push_str(DECORATIVE_EXT);
+            result.push_str("</p:cNvPr>");
+            result.push_str(&xml[close + 1..]);
+        } else {
+            // 扩展列表位于 a:hlinkClick 之后
+            result.push_str(&xml[tag_end..content_end]);
+            result.push_str(DECORATIVE_EXT);
+            result.push_str(&xml[content_end..]);
+        }
+        return result;
+    }
+
+    let mut attributes = String::new();
+    if let Some(text) = alt.text() {
+        attributes.push_str(&format!(r#" descr="{}""#, escape_xml(text)));
+    }
+    if let Some(title) = &alt.title {
+        attributes.push_str(&format!(r#" title="{}""#, escape_xml(title)));
+    }
+
+    format!("{}{}{}", &xml[..tag_end], attributes, &xml[tag_end..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_alt_text() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><title>季度回顾</title><g><title>营收
+            趋势</title><desc>营收逐季增长</desc><rect width="10" height="10"/></g><image id="logo" aria-label="公司标志" href="logo.png"/><image xlink:href="assets/photo.jpg"/><g aria-hidden="true"><image href="data:image/png;base64,AAAA"/></g><image href="data:image/png;base64,AAAA"/></svg>"#;
+
+        let (rewritten, alt) = extract_alt_text(svg).expect("提取应成功");
+        assert_eq!(alt.document.title.as_deref(), Some("季度回顾"));
+        assert!(rewritten.contains(r#"<g id="__svg_alt_2"><title>"#));
+        assert!(rewritten.contains(r#"<g aria-hidden="true" id="__svg_alt_"#));
+
+        let chart = &alt.elements["__svg_alt_2"];
+        assert_eq!(chart.title.as_deref(), Some("营收 趋势"));
+        assert_eq!(chart.text(), Some("营收逐季增长"));
+        assert_eq!(alt.elements["logo"].text(), Some("公司标志"));
+        assert_eq!(alt.unlabeled_images, vec!["photo.jpg", "<image> #4"]);
+    }
+
+    #[test]
+    fn test_describe() {
+        let xml =
+            r#"<p:pic><p:nvPicPr><p:cNvPr id="3" name="logo"/><p:cNvPicPr/></p:nvPicPr></p:pic>"#;
+        let alt = AltText {
+            title: Some("标志".to_string()),
+            description: Some("A & B".to_string()),
+            decorative: false,
+        };
+        let described = describe(xml, &alt);
+        assert!(
+            described.contains(r#"<p:cNvPr id="3" name="logo" descr="A &amp; B" title="标志"/>"#)
+        );
+        assert_eq!(describe(&described, &alt), described);
+
+        let decorative = AltText {
+            decorative: true,
+            ..AltText::default()
+        };
+        let linked = r#"<p:sp><p:nvSpPr><p:cNvPr id="4" name="a"><a:hlinkClick r:id="rId2"/></p:cNvPr></p:nvSpPr></p:sp>"#;
+        assert!(describe(linked, &decorative).contains(&format!(
+            r#"<a:hlinkClick r:id="rId2"/>{}</p:cNvPr>"#,
+            DECORATIVE_EXT
+        )));
+    }
+}
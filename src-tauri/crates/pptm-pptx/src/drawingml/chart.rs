@@ -0,0 +1,268 @@
+// 图表声明
+//
+// 幻灯片 SVG 通过带 `data-chart` 属性的分组声明原生图表，图表数据以 JSON 写在分组的
+// `<metadata>` 子元素中：
+//
+// <g data-chart="column" data-chart-bounds="80 120 1120 520">
+//   <metadata>{"categories": ["Q1", "Q2"], "series": [{"name": "营收", "values": [1, 2]}]}</metadata>
+//   ...矢量图形（其他渲染方式使用）...
+// </g>
+//
+// usvg 会丢弃 data-* 属性，解析前先提取声明并确保分组带 ID；转换时分组输出为
+// 图表 graphicFrame，位置取 `data-chart-bounds`（用户坐标），缺省时取分组内图形的范围。
+// 图表数据无效时分组按普通矢量图形转换，并返回警告
+
+use super::geometry::Bounds;
+use crate::chart::ChartSpec;
+use crate::{escape_xml, PptxError, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::HashMap;
+
+/// 为没有 ID 的图表生成的分组 ID 前缀
+const GENERATED_ID_PREFIX: &str = "__svg_chart_";
+
+/// SVG 中声明的图表
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartDeclaration {
+    pub spec: ChartSpec,
+    /// 图表区域（x, y, 宽, 高），使用分组的用户坐标
+    pub bounds: Option<[f32; 4]>,
+}
+
+/// 第 `index` 个图表的关系 ID 占位符
+pub fn chart_placeholder(index: usize) -> String {
+    format!("__chart_{}__", index)
+}
+
+/// 分组 ID 是否为图表提取时生成
+pub fn is_generated_id(id: &str) -> bool {
+    id.starts_with(GENERATED_ID_PREFIX)
+}
+
+/// 图表 graphicFrame，`index` 为图表占位符序号
+pub fn graphic_frame_xml(id: u32, name: &str, bounds: &Bounds, index: usize) -> String {
+    format!(
+        r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="{}" name="{}"/><p:cNvGraphicFramePr/><p:nvPr/></p:nvGraphicFramePr><p:xfrm>{}</p:xfrm><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" r:id="{}"/></a:graphicData></a:graphic></p:graphicFrame>"#,
+        id,
+        escape_xml(name),
+        bounds.to_xfrm_body(),
+        chart_placeholder(index)
+    )
+}
+
+/// 正在读取的图表分组
+struct PendingChart {
+    id: String,
+    kind: String,
+    bounds: Option<[f32; 4]>,
+    /// 分组内的元素深度（分组自身为 1）
+    depth: usize,
+    /// 是否位于直接子元素 `<metadata>` 内
+    in_metadata: bool,
+    data: String,
+}
+
+/// 提取图表声明，返回去掉 `<metadata>` 后的 SVG、「分组 ID -> 图表」映射与警告
+///
+/// 数据无效的图表不会中断导出：分组保留为矢量图形，错误记入警告
+pub fn extract_charts(
+    svg: &str,
+) -> Result<(String, HashMap<String, ChartDeclaration>, Vec<String>)> {
+    let mut charts = HashMap::new();
+    let mut warnings = Vec::new();
+    if !svg.contains("data-chart") {
+        return Ok((svg.to_string(), charts, warnings));
+    }
+
+    let mut reader = Reader::from_str(svg);
+    let mut writer = Writer::new(Vec::with_capacity(svg.len()));
+    let mut pending: Option<PendingChart> = None;
+    let mut generated = 0usize;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+
+        if let Some(chart) = pending.as_mut() {
+            match &event {
+                Event::Start(start) => {
+                    chart.depth += 1;
+                    if chart.depth == 2 && start.name().as_ref() == b"metadata" {
+                        chart.in_metadata = true;
+                    }
+                }
+                Event::End(_) => {
+                    chart.depth -= 1;
+                    if chart.in_metadata && chart.depth == 1 {
+                        chart.in_metadata = false;
+                        continue;
+                    }
+                }
+                Event::Text(text) if chart.in_metadata => {
+                    let text = text
+                        .unescape()
+                        .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+                    chart.data.push_str(&text);
+                }
+                Event::CData(data) if chart.in_metadata => {
+                    chart.data.push_str(&String::from_utf8_lossy(data.as_ref()));
+                }
+                _ => {}
+            }
+
+            if chart.in_metadata {
+                continue;
+            }
+
+            if chart.depth == 0 {
+                let chart = pending.take().expect("图表分组已打开");
+                match ChartSpec::from_json(&chart.kind, &chart.data) {
+                    Ok(spec) => {
+                        charts.insert(
+                            chart.id,
+                            ChartDeclaration {
+                                spec,
+                                bounds: chart.bounds,
+                            },
+                        );
+                    }
+                    Err(e) => warnings.push(format!(
+                        "{} 图表数据无效，已按矢量图形导出: {}",
+                        chart.kind, e
+                    )),
+                }
+            }
+        }
+
+        let event = match event {
+            Event::Eof => break,
+            Event::Start(start) if start.name().as_ref() == b"g" && pending.is_none() => {
+                match chart_group(&start, &mut generated)? {
+                    Some((group, chart)) => {
+                        pending = Some(chart);
+                        Event::Start(group)
+                    }
+                    None => Event::Start(start),
+                }
+            }
+            other => other,
+        };
+
+        writer
+            .write_event(event)
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    }
+
+    let svg =
+        String::from_utf8(writer.into_inner()).map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    Ok((svg, charts, warnings))
+}
+
+/// 解析图表分组的属性，非图表分组返回 None
+fn chart_group(
+    start: &BytesStart,
+    generated: &mut usize,
+) -> Result<Option<(BytesStart<'static>, PendingChart)>> {
+    let mut group = BytesStart::new("g");
+    let mut id = None;
+    let mut kind = None;
+    let mut bounds = None;
+
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| PptxError::SvgParse(e.to_string()))?;
+        let value = || {
+            attr.unescape_value()
+                .map(|value| value.trim().to_string())
+                .map_err(|e| PptxError::SvgParse(e.to_string()))
+        };
+
+        match attr.key.as_ref() {
+            b"data-chart" => kind = Some(value()?),
+            b"data-chart-bounds" => bounds = Some(parse_bounds(&value()?)?),
+            b"id" => {
+                id = Some(value()?);
+                group.push_attribute(attr);
+            }
+            _ => group.push_attribute(attr),
+        }
+    }
+
+    let Some(kind) = kind else {
+        return Ok(None);
+    };
+
+    let id = match id.filter(|id| !id.is_empty()) {
+        Some(id) => id,
+        None => {
+            let id = format!("{}{}", GENERATED_ID_PREFIX, generated);
+            *generated += 1;
+            group.push_attribute(("id", id.as_str()));
+            id
+        }
+    };
+
+    Ok(Some((
+        group,
+        PendingChart {
+            id,
+            kind,
+            bounds,
+            depth: 1,
+            in_metadata: false,
+            data: String::new(),
+        },
+    )))
+}
+
+/// 解析 `x y w h`（空格或逗号分隔）
+fn parse_bounds(value: &str) -> Result<[f32; 4]> {
+    let numbers: Vec<f32> = value
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f32>())
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|_| PptxError::SvgParse(format!("无效的图表区域: {}", value)))?;
+
+    match numbers.as_slice() {
+        [x, y, w, h] if *w > 0.0 && *h > 0.0 => Ok([*x, *y, *w, *h]),
+        _ => Err(PptxError::SvgParse(format!("无效的图表区域: {}", value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chart::ChartKind;
+
+    #[test]
+    fn test_extract_charts() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg"><g data-chart="pie" data-chart-bounds="10, 20, 300 200"><metadata><![CDATA[{"categories": ["A", "B"], "series": [{"name": "S", "values": [1, 2]}]}]]></metadata><rect width="10" height="10"/></g><g id="bars" data-chart="bar"><metadata>{"categories": ["A&amp;B"], "series": [{"name": "S", "values": [3]}]}</metadata></g></svg>"#;
+
+        let (rewritten, charts, warnings) = extract_charts(svg).expect("提取应成功");
+        assert!(warnings.is_empty());
+        assert!(rewritten.contains(r#"<g id="__svg_chart_0"><rect width="10" height="10"/></g>"#));
+        assert!(rewritten.contains(r#"<g id="bars"></g>"#));
+        assert!(!rewritten.contains("metadata"));
+
+        let pie = &charts["__svg_chart_0"];
+        assert_eq!(pie.spec.kind, ChartKind::Pie);
+        assert_eq!(pie.bounds, Some([10.0, 20.0, 300.0, 200.0]));
+        assert_eq!(charts["bars"].spec.data.categories, vec!["A&B"]);
+        assert_eq!(charts["bars"].bounds, None);
+
+        // 数据无效时保留分组中的矢量图形，并给出警告
+        for data in [r#"{"series": []}"#, "{not json"] {
+            let invalid = format!(
+                r#"<svg><g data-chart="line"><metadata>{}</metadata><rect width="10" height="10"/></g></svg>"#,
+                data
+            );
+            let (rewritten, charts, warnings) = extract_charts(&invalid).expect("提取应成功");
+            assert!(charts.is_empty());
+            assert!(rewritten.contains(r#"<rect width="10" height="10"/>"#));
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0].starts_with("line 图表数据无效"));
+        }
+    }
+}
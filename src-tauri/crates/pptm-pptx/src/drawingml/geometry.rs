@@ -0,0 +1,287 @@
+// 几何形状识别与 custGeom 生成
+//
+// usvg 会把 rect / ellipse / line 统一转换为路径，这里按路径段特征还原为
+// DrawingML 预设形状，无法识别的路径输出为自定义几何 (a:custGeom)
+
+use super::px_to_emu;
+use usvg::tiny_skia_path::{Path, PathSegment, Point};
+
+/// 形状在幻灯片坐标系（像素）中的外接矩形
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Bounds {
+    /// 合并两个外接矩形
+    pub fn union(&self, other: &Bounds) -> Bounds {
+        let left = self.x.min(other.x);
+        let top = self.y.min(other.y);
+        let right = (self.x + self.width).max(other.x + other.width);
+        let bottom = (self.y + self.height).max(other.y + other.height);
+
+        Bounds {
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        }
+    }
+
+    /// 生成 `a:off` + `a:ext` 片段
+    pub fn to_xfrm_body(self) -> String {
+        format!(
+            r#"<a:off x="{}" y="{}"/><a:ext cx="{}" cy="{}"/>"#,
+            px_to_emu(self.x),
+            px_to_emu(self.y),
+            px_to_emu(self.width),
+            px_to_emu(self.height)
+        )
+    }
+}
+
+/// 路径识别结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    /// 轴对齐矩形
+    Rect,
+    /// 轴对齐椭圆
+    Ellipse,
+    /// 直线段（方向由翻转标记表示）
+    Line { flip_h: bool, flip_v: bool },
+    /// 自定义几何，内容为 `a:pathLst` 中的 `a:path` 子元素
+    Custom(String),
+}
+
+impl Geometry {
+    /// 预设形状名称（`a:prstGeom` 的 `prst` 属性），自定义几何返回 None
+    pub fn preset_name(&self) -> Option<&'static str> {
+        match self {
+            Geometry::Rect => Some("rect"),
+            Geometry::Ellipse => Some("ellipse"),
+            Geometry::Line { .. } => Some("line"),
+            Geometry::Custom(_) => None,
+        }
+    }
+
+    /// 生成几何定义 XML
+    pub fn to_xml(&self, bounds: &Bounds) -> String {
+        match self {
+            Geometry::Custom(commands) => format!(
+                r#"<a:custGeom><a:avLst/><a:gdLst/><a:ahLst/><a:cxnLst/><a:rect l="0" t="0" r="r" b="b"/><a:pathLst><a:path w="{}" h="{}">{}</a:path></a:pathLst></a:custGeom>"#,
+                px_to_emu(bounds.width).max(1),
+                px_to_emu(bounds.height).max(1),
+                commands
+            ),
+            preset => format!(
+                r#"<a:prstGeom prst="{}"><a:avLst/></a:prstGeom>"#,
+                preset.preset_name().unwrap_or("rect")
+            ),
+        }
+    }
+}
+
+/// 容差（像素），用于判断点是否落在外接矩形边上
+const EPSILON: f32 = 0.01;
+
+/// 识别路径的几何类型
+///
+/// `path` 必须已经变换到幻灯片坐标系
+pub fn classify(path: &Path) -> Option<(Bounds, Geometry)> {
+    let rect = path.bounds();
+    let bounds = Bounds {
+        x: rect.x(),
+        y: rect.y(),
+        width: rect.width(),
+        height: rect.height(),
+    };
+
+    if !bounds.width.is_finite() || !bounds.height.is_finite() {
+        return None;
+    }
+
+    let segments: Vec<PathSegment> = path.segments().collect();
+
+    let geometry = if let Some(line) = as_line(&segments) {
+        line
+    } else if is_rect(&segments, &bounds) {
+        Geometry::Rect
+    } else if is_ellipse(&segments, &bounds) {
+        Geometry::Ellipse
+    } else {
+        Geometry::Custom(custom_commands(&segments, &bounds))
+    };
+
+    Some((bounds, geometry))
+}
+
+fn as_line(segments: &[PathSegment]) -> Option<Geometry> {
+    match segments {
+        [PathSegment::MoveTo(start), PathSegment::LineTo(end)] => Some(Geometry::Line {
+            flip_h: start.x > end.x,
+            flip_v: start.y > end.y,
+        }),
+        _ => None,
+    }
+}
+
+fn is_rect(segments: &[PathSegment], bounds: &Bounds) -> bool {
+    let mut points = Vec::new();
+
+    for (index, segment) in segments.iter().enumerate() {
+        match segment {
+            PathSegment::MoveTo(p) if index == 0 => points.push(*p),
+            PathSegment::LineTo(p) => points.push(*p),
+            PathSegment::Close if index == segments.len() - 1 => {}
+            _ => return false,
+        }
+    }
+
+    // 闭合矩形为 4 个角点，部分写法会重复起点
+    if points.len() == 5 && near(points[0], points[4]) {
+        points.pop();
+    }
+
+    if points.len() != 4 {
+        return false;
+    }
+
+    // 相邻点只能水平或垂直移动，且所有点都落在外接矩形角上
+    let axis_aligned = (0..4).all(|i| {
+        let a = points[i];
+        let b = points[(i + 1) % 4];
+        (a.x - b.x).abs() < EPSILON || (a.y - b.y).abs() < EPSILON
+    });
+
+    axis_aligned && points.iter().all(|p| on_corner(*p, bounds))
+}
+
+fn is_ellipse(segments: &[PathSegment], bounds: &Bounds) -> bool {
+    let mut anchors = Vec::new();
+
+    for (index, segment) in segments.iter().enumerate() {
+        match segment {
+            PathSegment::MoveTo(p) if index == 0 => anchors.push(*p),
+            PathSegment::CubicTo(_, _, p) => anchors.push(*p),
+            PathSegment::Close if index == segments.len() - 1 => {}
+            _ => return false,
+        }
+    }
+
+    // usvg 以 4 段三次贝塞尔曲线表示椭圆，锚点位于四条边的中点
+    if anchors.len() != 5 || !near(anchors[0], anchors[4]) {
+        return false;
+    }
+
+    let cx = bounds.x + bounds.width / 2.0;
+    let cy = bounds.y + bounds.height / 2.0;
+
+    anchors[..4].iter().all(|p| {
+        let on_vertical_edge = (p.y - cy).abs() < EPSILON
+            && ((p.x - bounds.x).abs() < EPSILON
+                || (p.x - bounds.x - bounds.width).abs() < EPSILON);
+        let on_horizontal_edge = (p.x - cx).abs() < EPSILON
+            && ((p.y - bounds.y).abs() < EPSILON
+                || (p.y - bounds.y - bounds.height).abs() < EPSILON);
+        on_vertical_edge || on_horizontal_edge
+    })
+}
+
+fn custom_commands(segments: &[PathSegment], bounds: &Bounds) -> String {
+    let pt = |p: &Point| {
+        format!(
+            r#"<a:pt x="{}" y="{}"/>"#,
+            px_to_emu(p.x - bounds.x),
+            px_to_emu(p.y - bounds.y)
+        )
+    };
+
+    let mut xml = String::new();
+    for segment in segments {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                xml.push_str(&format!("<a:moveTo>{}</a:moveTo>", pt(p)));
+            }
+            PathSegment::LineTo(p) => {
+                xml.push_str(&format!("<a:lnTo>{}</a:lnTo>", pt(p)));
+            }
+            PathSegment::QuadTo(p1, p) => {
+                xml.push_str(&format!("<a:quadBezTo>{}{}</a:quadBezTo>", pt(p1), pt(p)));
+            }
+            PathSegment::CubicTo(p1, p2, p) => {
+                xml.push_str(&format!(
+                    "<a:cubicBezTo>{}{}{}</a:cubicBezTo>",
+                    pt(p1),
+                    pt(p2),
+                    pt(p)
+                ));
+            }
+            PathSegment::Close => xml.push_str("<a:close/>"),
+        }
+    }
+
+    xml
+}
+
+fn near(a: Point, b: Point) -> bool {
+    (a.x - b.x).abs() < EPSILON && (a.y - b.y).abs() < EPSILON
+}
+
+fn on_corner(p: Point, bounds: &Bounds) -> bool {
+    let on_x = (p.x - bounds.x).abs() < EPSILON || (p.x - bounds.x - bounds.width).abs() < EPSILON;
+    let on_y = (p.y - bounds.y).abs() < EPSILON || (p.y - bounds.y - bounds.height).abs() < EPSILON;
+    on_x && on_y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use usvg::tiny_skia_path::{PathBuilder, Rect};
+
+    #[test]
+    fn test_classify_rect() {
+        let rect = Rect::from_xywh(10.0, 20.0, 100.0, 50.0).unwrap();
+        let path = PathBuilder::from_rect(rect);
+
+        let (bounds, geometry) = classify(&path).expect("应能识别矩形");
+        assert_eq!(geometry, Geometry::Rect);
+        assert_eq!(bounds.x, 10.0);
+        assert_eq!(bounds.width, 100.0);
+    }
+
+    #[test]
+    fn test_classify_line_direction() {
+        let mut builder = PathBuilder::new();
+        builder.move_to(100.0, 0.0);
+        builder.line_to(0.0, 50.0);
+        let path = builder.finish().unwrap();
+
+        let (_, geometry) = classify(&path).expect("应能识别直线");
+        assert_eq!(
+            geometry,
+            Geometry::Line {
+                flip_h: true,
+                flip_v: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_classify_custom_path() {
+        let mut builder = PathBuilder::new();
+        builder.move_to(0.0, 0.0);
+        builder.line_to(100.0, 0.0);
+        builder.line_to(50.0, 80.0);
+        builder.close();
+        let path = builder.finish().unwrap();
+
+        let (bounds, geometry) = classify(&path).expect("应能生成自定义几何");
+        let xml = geometry.to_xml(&bounds);
+        assert!(xml.contains("<a:custGeom>"));
+        assert!(xml.contains(r#"<a:path w="952500" h="762000">"#));
+        assert!(xml.contains("<a:close/>"));
+    }
+}
@@ -0,0 +1,231 @@
+// 超链接转换
+//
+// usvg 将 <a> 视为普通分组并丢弃 href。解析前先把 <a> 改写为带 ID 的 <g> 并记录
+// 链接目标，转换时为分组内的形状附加 a:hlinkClick，关系 ID 在写包时回填
+
+use crate::{PptxError, Result};
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::HashMap;
+
+/// 为没有 ID 的链接生成的分组 ID 前缀
+const GENERATED_ID_PREFIX: &str = "__svg_link_";
+
+/// 形状上的超链接
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hyperlink {
+    /// 原始 href，页内跳转以 `#` 开头
+    pub href: String,
+}
+
+impl Hyperlink {
+    /// 页内跳转的目标（去掉 `#`），外部链接返回 None
+    pub fn fragment(&self) -> Option<&str> {
+        self.href.strip_prefix('#')
+    }
+}
+
+/// 第 `index` 个超链接的 `a:hlinkClick` 占位元素
+pub fn click_placeholder(index: usize, link: &Hyperlink) -> String {
+    let action = if link.fragment().is_some() {
+        r#" action="ppaction://hlinksldjump""#
+    } else {
+        ""
+    };
+    format!(r#"<a:hlinkClick r:id="__link_{}__"{}/>"#, index, action)
+}
+
+/// 占位元素中的关系 ID 占位符
+pub fn rid_placeholder(index: usize) -> String {
+    format!("__link_{}__", index)
+}
+
+/// 分组 ID 是否为链接改写时生成
+pub fn is_generated_id(id: &str) -> bool {
+    id.starts_with(GENERATED_ID_PREFIX)
+}
+
+/// 将 `<a>` 改写为 `<g>`，返回改写后的 SVG 与「分组 ID -> href」映射
+///
+/// `<text>` 内的 `<a>` 由 usvg 按 tspan 处理，保持原样（链接不保留）
+pub fn extract_links(svg: &str) -> Result<(String, HashMap<String, String>)> {
+    let mut links = HashMap::new();
+    if !svg.contains("<a") {
+        return Ok((svg.to_string(), links));
+    }
+
+    let mut reader = Reader::from_str(svg);
+    let mut writer = Writer::new(Vec::with_capacity(svg.len()));
+    let mut text_depth = 0usize;
+    // 每个打开的 <a> 是否被改写
+    let mut anchors: Vec<bool> = Vec::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+
+        let event = match event {
+            Event::Eof => break,
+            Event::Start(start) => match start.name().as_ref() {
+                b"text" => {
+                    text_depth += 1;
+                    Event::Start(start)
+                }
+                b"a" => {
+                    let rewrite = text_depth == 0;
+                    anchors.push(rewrite);
+                    if rewrite {
+                        Event::Start(anchor_to_group(&start, &mut links)?)
+                    } else {
+                        Event::Start(start)
+                    }
+                }
+                _ => Event::Start(start),
+            },
+            Event::Empty(start) if start.name().as_ref() == b"a" && text_depth == 0 => {
+                Event::Empty(anchor_to_group(&start, &mut links)?)
+            }
+            Event::End(end) => match end.name().as_ref() {
+                b"text" => {
+                    text_depth = text_depth.saturating_sub(1);
+                    Event::End(end)
+                }
+                b"a" if anchors.pop().unwrap_or(false) => Event::End(BytesEnd::new("g")),
+                _ => Event::End(end),
+            },
+            other => other,
+        };
+
+        writer
+            .write_event(event)
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    }
+
+    let svg =
+        String::from_utf8(writer.into_inner()).map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    Ok((svg, links))
+}
+
+fn anchor_to_group(
+    anchor: &BytesStart,
+    links: &mut HashMap<String, String>,
+) -> Result<BytesStart<'static>> {
+    let mut group = BytesStart::new("g");
+    let mut id = None;
+    let mut href = None;
+
+    for attr in anchor.attributes() {
+        let attr = attr.map_err(|e| PptxError::SvgParse(e.to_string()))?;
+        let value = || {
+            attr.unescape_value()
+                .map(|value| value.trim().to_string())
+                .map_err(|e| PptxError::SvgParse(e.to_string()))
+        };
+
+        match attr.key.as_ref() {
+            b"href" | b"xlink:href" => href = Some(value()?),
+            b"id" => {
+                id = Some(value()?);
+                group.push_attribute(attr);
+            }
+            // target 等链接属性对分组无意义
+            b"target" | b"rel" | b"download" | b"hreflang" | b"type" | b"referrerpolicy"
+            | b"ping" => {}
+            _ => group.push_attribute(attr),
+        }
+    }
+
+    let Some(href) = href.filter(|href| !href.is_empty() && href != "#") else {
+        return Ok(group);
+    };
+
+    let id = match id.filter(|id| !id.is_empty()) {
+        Some(id) => id,
+        None => {
+            let id = format!("{}{}", GENERATED_ID_PREFIX, links.len());
+            group.push_attribute(("id", id.as_str()));
+            id
+        }
+    };
+    links.insert(id, href);
+
+    Ok(group)
+}
+
+/// 为片段中每个尚未带链接的形状附加超链接
+///
+/// 分组自身（`p:cNvGrpSpPr`）不附加：PowerPoint 只响应单个形状上的点击
+pub fn attach_click(xml: &str, click: &str) -> String {
+    const OPEN: &str = "<p:cNvPr ";
+
+    let mut result = String::with_capacity(xml.len() + click.len());
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(OPEN) {
+        let Some(close) = rest[start..].find('>').map(|offset| start + offset) else {
+            break;
+        };
+        let self_closing = rest[..close].ends_with('/');
+        // 非自闭合的 cNvPr 可能只包含装饰性标记等扩展
+        let end = if self_closing {
+            close + 1
+        } else {
+            rest[close..]
+                .find("</p:cNvPr>")
+                .map(|offset| close + offset + "</p:cNvPr>".len())
+                .unwrap_or(close + 1)
+        };
+        let is_group = rest[end..].starts_with("<p:cNvGrpSpPr");
+        let has_click = rest[close..end].contains("<a:hlinkClick");
+
+        if is_group || has_click {
+            result.push_str(&rest[..end]);
+        } else if self_closing {
+            result.push_str(&rest[..close - 1]);
+            result.push('>');
+            result.push_str(click);
+            result.push_str("</p:cNvPr>");
+        } else {
+            // a:hlinkClick 位于 a:extLst 之前
+            result.push_str(&rest[..=close]);
+            result.push_str(click);
+            result.push_str(&rest[close + 1..end]);
+        }
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink"><a href="https://example.com/?a=1&amp;b=2" target="_blank"><rect width="10" height="10"/></a><a id="next" xlink:href="#slide_03"/><text><a href="#x">文字</a></text></svg>"##;
+
+        let (rewritten, links) = extract_links(svg).expect("改写应成功");
+        assert!(rewritten.contains(r#"<g id="__svg_link_0"><rect width="10" height="10"/></g>"#));
+        assert!(rewritten.contains(r#"<g id="next"/>"#));
+        assert!(rewritten.contains(r##"<text><a href="#x">文字</a></text>"##));
+        assert_eq!(links["__svg_link_0"], "https://example.com/?a=1&b=2");
+        assert_eq!(links["next"], "#slide_03");
+        assert_eq!(links.len(), 2);
+    }
+
+    #[test]
+    fn test_attach_click() {
+        let xml = r#"<p:grpSp><p:nvGrpSpPr><p:cNvPr id="2" name="g"/><p:cNvGrpSpPr/></p:nvGrpSpPr><p:sp><p:nvSpPr><p:cNvPr id="3" name="a"/><p:cNvSpPr/></p:nvSpPr></p:sp><p:sp><p:nvSpPr><p:cNvPr id="4" name="b"><a:hlinkClick r:id="rId9"/></p:cNvPr><p:cNvSpPr/></p:nvSpPr></p:sp></p:grpSp>"#;
+        let click = r#"<a:hlinkClick r:id="__link_0__"/>"#;
+
+        let result = attach_click(xml, click);
+        assert!(result.contains(r#"<p:cNvPr id="2" name="g"/>"#));
+        assert!(result
+            .contains(r#"<p:cNvPr id="3" name="a"><a:hlinkClick r:id="__link_0__"/></p:cNvPr>"#));
+        // 内层链接优先
+        assert_eq!(result.matches("__link_0__").count(), 1);
+    }
+}
@@ -0,0 +1,582 @@
+// DrawingML 形状转换
+//
+// 使用 usvg 解析 SVG，将路径、文本、图片与分组转换为 PresentationML 的 p:sp / p:pic / p:grpSp，
+// 声明了图表数据的分组转换为原生图表，标记为表格的分组转换为 a:tbl，
+// 使导出的幻灯片在 PowerPoint 中可直接编辑。形状带有替代文字，按阅读顺序排列，
+// 并标记幻灯片标题
+
+pub mod alt_text;
+pub mod chart;
+pub mod geometry;
+pub mod link;
+pub mod picture;
+pub mod reading;
+pub mod style;
+pub mod table;
+pub mod text;
+
+use crate::chart::ChartSpec;
+use crate::{PptxError, Result};
+use alt_text::AltText;
+use chart::ChartDeclaration;
+use geometry::{Bounds, Geometry};
+use link::Hyperlink;
+use picture::EmbeddedImage;
+use reading::TextInfo;
+use std::collections::{HashMap, HashSet};
+use usvg::{Group, Node, Transform, TreeParsing};
+
+/// 每像素对应的 EMU 数（96 DPI）
+pub const EMU_PER_PX: f32 = 9525.0;
+
+/// 像素转换为 EMU
+pub fn px_to_emu(px: f32) -> i64 {
+    (px * EMU_PER_PX).round() as i64
+}
+
+/// 转换后的形状树片段
+#[derive(Debug, Clone, Default)]
+pub struct ShapeTree {
+    /// 可直接放入 `p:spTree` 的形状 XML
+    pub xml: String,
+    /// 顶层形状的 ID（按绘制顺序），用于入场动画等引用
+    pub shape_ids: Vec<u32>,
+    /// 嵌入图片（按占位符序号），需写入媒体库并回填关系 ID
+    pub images: Vec<EmbeddedImage>,
+    /// 超链接（按占位符序号），需建立关系并回填关系 ID
+    pub links: Vec<Hyperlink>,
+    /// 原生图表（按占位符序号），需写入图表部件并回填关系 ID
+    pub charts: Vec<ChartSpec>,
+    /// 是否已有标题占位符
+    pub has_title: bool,
+    /// 下一个可用的形状 ID
+    pub next_id: u32,
+    /// 转换时跳过的问题（如图表数据无效），由调用方上报
+    pub warnings: Vec<String>,
+}
+
+impl ShapeTree {
+    /// 将图片占位符替换为幻灯片关系 ID，`rids` 与 `images` 一一对应
+    pub fn bind_images(&mut self, rids: &[String]) {
+        for (index, rid) in rids.iter().enumerate() {
+            self.xml = self.xml.replace(&picture::embed_placeholder(index), rid);
+        }
+    }
+
+    /// 将图表占位符替换为幻灯片关系 ID，`rids` 与 `charts` 一一对应
+    pub fn bind_charts(&mut self, rids: &[String]) {
+        for (index, rid) in rids.iter().enumerate() {
+            self.xml = self.xml.replace(&chart::chart_placeholder(index), rid);
+        }
+    }
+
+    /// 没有标题占位符时，在画布外添加以 `title` 为内容的标题占位符
+    pub fn ensure_title(&mut self, title: &str, canvas_width: u32) {
+        if self.has_title || title.trim().is_empty() {
+            return;
+        }
+
+        let xml = reading::hidden_title_xml(self.next_id, title.trim(), canvas_width as f32);
+        self.xml.insert_str(0, &xml);
+        self.next_id += 1;
+        self.has_title = true;
+    }
+
+    /// 回填超链接关系 ID，`rids` 与 `links` 一一对应；无法解析的链接（None）被移除
+    pub fn bind_links(&mut self, rids: &[Option<String>]) {
+        for (index, (link, rid)) in self.links.iter().zip(rids).enumerate() {
+            self.xml = match rid {
+                Some(rid) => self.xml.replace(&link::rid_placeholder(index), rid),
+                None => self.xml.replace(&link::click_placeholder(index, link), ""),
+            };
+        }
+    }
+}
+
+/// 将 SVG 转换为幻灯片形状树片段
+///
+/// SVG 的 viewBox 会被映射到 `width` x `height` 像素的幻灯片画布上。标题取根元素 `<title>`
+/// 对应的文本，没有 `<title>` 时取上半页字号最大的文本
+pub fn svg_to_shapes(svg: &str, width: u32, height: u32) -> Result<ShapeTree> {
+    let (svg, charts, warnings) = chart::extract_charts(svg)?;
+    let (svg, tables) = table::extract_tables(&svg)?;
+    let (svg, links) = link::extract_links(&svg)?;
+    let (svg, alt) = alt_text::extract_alt_text(&svg)?;
+
+    let opt = usvg::Options::default();
+    let mut tree =
+        usvg::Tree::from_str(&svg, &opt).map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    tree.calculate_abs_transforms();
+
+    let canvas = usvg::Size::from_wh(width as f32, height as f32)
+        .ok_or_else(|| PptxError::SvgParse(format!("无效的画布尺寸: {}x{}", width, height)))?;
+    let root_transform =
+        usvg::utils::view_box_to_transform(tree.view_box.rect, tree.view_box.aspect, canvas);
+
+    let mut converter = ShapeConverter {
+        root_transform,
+        next_id: 2,
+        images: Vec::new(),
+        links,
+        hyperlinks: Vec::new(),
+        charts,
+        chart_specs: Vec::new(),
+        tables,
+        alt_text: alt.elements,
+    };
+
+    let mut shapes = converter.convert_children(&tree.root, 1.0);
+
+    // 占位符不能位于分组内，只在顶层文本中选择标题
+    let texts: Vec<_> = shapes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, shape)| shape.text.as_ref().map(|text| (index, &shape.bounds, text)))
+        .collect();
+    let document_title = alt.document.title.as_deref();
+    let title = reading::choose_title(&texts, document_title, height as f32);
+    if let Some(index) = title {
+        shapes[index].xml = reading::mark_title(&shapes[index].xml);
+    }
+
+    let mut tree = ShapeTree {
+        shape_ids: shapes.iter().map(|shape| shape.id).collect(),
+        xml: shapes.into_iter().map(|shape| shape.xml).collect(),
+        images: converter.images,
+        links: converter.hyperlinks,
+        charts: converter.chart_specs,
+        has_title: title.is_some(),
+        next_id: converter.next_id,
+        warnings,
+    };
+    if let Some(document_title) = document_title {
+        tree.ensure_title(document_title, width);
+    }
+
+    Ok(tree)
+}
+
+/// SVG 元素 ID 是否为预处理时生成
+fn is_generated_id(id: &str) -> bool {
+    link::is_generated_id(id)
+        || chart::is_generated_id(id)
+        || table::is_generated_id(id)
+        || alt_text::is_generated_id(id)
+}
+
+/// 形状名称：使用 SVG 元素 ID，没有 ID 或 ID 为预处理时生成时使用 `{kind} {id}`
+pub(crate) fn shape_name(element_id: &str, kind: &str, shape_id: u32) -> String {
+    if element_id.is_empty() || is_generated_id(element_id) {
+        format!("{} {}", kind, shape_id)
+    } else {
+        element_id.to_string()
+    }
+}
+
+/// 已转换的形状
+struct ConvertedShape {
+    id: u32,
+    xml: String,
+    bounds: Bounds,
+    /// 文本框的内容（用于确定标题）
+    text: Option<TextInfo>,
+}
+
+/// SVG 树到形状树的转换器
+struct ShapeConverter {
+    /// viewBox 到幻灯片像素坐标的变换
+    root_transform: Transform,
+    /// 下一个可用的形状 ID（1 保留给 spTree 自身）
+    next_id: u32,
+    /// 已转换图片的数据
+    images: Vec<EmbeddedImage>,
+    /// 由 `<a>` 改写的分组 ID 到链接目标的映射
+    links: HashMap<String, String>,
+    /// 已引用的超链接
+    hyperlinks: Vec<Hyperlink>,
+    /// 分组 ID 到图表声明的映射
+    charts: HashMap<String, ChartDeclaration>,
+    /// 已输出的图表
+    chart_specs: Vec<ChartSpec>,
+    /// 标记为表格的分组 ID
+    tables: HashSet<String>,
+    /// 元素 ID 到替代文字的映射
+    alt_text: HashMap<String, AltText>,
+}
+
+impl ShapeConverter {
+    fn allocate_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// 转换子节点并按阅读顺序排列
+    fn convert_children(&mut self, group: &Group, opacity: f32) -> Vec<ConvertedShape> {
+        let mut shapes = Vec::new();
+
+        for node in &group.children {
+            let first = shapes.len();
+            match node {
+                Node::Group(child) => {
+                    shapes.extend(self.convert_group(child, opacity * child.opacity.get()));
+                }
+                Node::Path(path) => {
+                    if let Some(shape) = self.convert_path(path, opacity) {
+                        shapes.push(shape);
+                    }
+                }
+                Node::Text(text) => {
+                    if let Some(shape) = self.convert_text(text, opacity) {
+                        shapes.push(shape);
+                    }
+                }
+                Node::Image(image) => {
+                    if let Some(shape) = self.convert_image(image, opacity) {
+                        shapes.push(shape);
+                    }
+                }
+            }
+
+            if let Some(alt) = self.alt_text.get(node.id()) {
+                for shape in &mut shapes[first..] {
+                    shape.xml = alt_text::describe(&shape.xml, alt);
+                }
+            }
+        }
+
+        let bounds: Vec<Bounds> = shapes.iter().map(|shape| shape.bounds).collect();
+        let mut shapes: Vec<Option<ConvertedShape>> = shapes.into_iter().map(Some).collect();
+        reading::reading_order(&bounds)
+            .into_iter()
+            .filter_map(|index| shapes[index].take())
+            .collect()
+    }
+
+    /// 转换分组，来自 `<a>` 的分组为其中的每个形状附加超链接
+    fn convert_group(&mut self, group: &Group, opacity: f32) -> Vec<ConvertedShape> {
+        let mut shapes = match self.charts.remove(&group.id) {
+            Some(declaration) => self.convert_chart(group, declaration).into_iter().collect(),
+            None if self.tables.contains(&group.id) => self.convert_table(group, opacity),
+            None => self.convert_group_shapes(group, opacity),
+        };
+
+        if let Some(click) = self.group_click(group) {
+            for shape in &mut shapes {
+                shape.xml = link::attach_click(&shape.xml, &click);
+            }
+        }
+
+        shapes
+    }
+
+    /// 分组对应的 `a:hlinkClick` 占位元素，相同目标共用一个超链接
+    fn group_click(&mut self, group: &Group) -> Option<String> {
+        let href = self.links.get(&group.id)?;
+        let index = match self.hyperlinks.iter().position(|link| &link.href == href) {
+            Some(index) => index,
+            None => {
+                self.hyperlinks.push(Hyperlink { href: href.clone() });
+                self.hyperlinks.len() - 1
+            }
+        };
+
+        Some(link::click_placeholder(index, &self.hyperlinks[index]))
+    }
+
+    /// 图表分组输出为图表 graphicFrame，分组内的矢量图形只用于确定缺省位置
+    fn convert_chart(
+        &mut self,
+        group: &Group,
+        declaration: ChartDeclaration,
+    ) -> Option<ConvertedShape> {
+        let bounds = match declaration.bounds {
+            Some([x, y, width, height]) => picture::map_rect(
+                usvg::NonZeroRect::from_xywh(x, y, width, height)?,
+                self.root_transform.pre_concat(group.abs_transform),
+            )?,
+            None => {
+                let (images, hyperlinks) = (self.images.len(), self.hyperlinks.len());
+                let children = self.convert_children(group, 1.0);
+                self.images.truncate(images);
+                self.hyperlinks.truncate(hyperlinks);
+
+                let first = children.first()?.bounds;
+                children
+                    .iter()
+                    .skip(1)
+                    .fold(first, |acc, shape| acc.union(&shape.bounds))
+            }
+        };
+
+        let id = self.allocate_id();
+        let name = shape_name(&group.id, "Chart", id);
+        let xml = chart::graphic_frame_xml(id, &name, &bounds, self.chart_specs.len());
+        self.chart_specs.push(declaration.spec);
+
+        Some(ConvertedShape {
+            id,
+            xml,
+            bounds,
+            text: None,
+        })
+    }
+
+    /// 表格分组输出为表格 graphicFrame，识别不出网格时按普通分组转换
+    fn convert_table(&mut self, group: &Group, opacity: f32) -> Vec<ConvertedShape> {
+        match table::convert_table(group, self.root_transform, opacity, self.next_id) {
+            Some(table) => {
+                let id = self.allocate_id();
+                vec![ConvertedShape {
+                    id,
+                    xml: table.xml,
+                    bounds: table.bounds,
+                    text: None,
+                }]
+            }
+            None => self.convert_group_shapes(group, opacity),
+        }
+    }
+
+    /// 带 ID 且包含多个形状的分组输出为 p:grpSp，其余分组直接展开
+    ///
+    /// 带替代文字的分组即使没有 ID 也保留为 p:grpSp，替代文字描述整个分组
+    fn convert_group_shapes(&mut self, group: &Group, opacity: f32) -> Vec<ConvertedShape> {
+        if group.id.is_empty()
+            || link::is_generated_id(&group.id)
+            || table::is_generated_id(&group.id)
+        {
+            return self.convert_children(group, opacity);
+        }
+
+        let children = self.convert_children(group, opacity);
+        if children.len() < 2 {
+            return children;
+        }
+
+        // 确定输出 p:grpSp 后再分配 ID，展开的分组不占用 ID
+        let id = self.allocate_id();
+
+        let bounds = children
+            .iter()
+            .skip(1)
+            .fold(children[0].bounds, |acc, shape| acc.union(&shape.bounds));
+        let xfrm = bounds.to_xfrm_body();
+        let child_xfrm = xfrm.replace("a:off", "a:chOff").replace("a:ext", "a:chExt");
+
+        let mut xml = format!(
+            r#"<p:grpSp><p:nvGrpSpPr><p:cNvPr id="{}" name="{}"/><p:cNvGrpSpPr/><p:nvPr/></p:nvGrpSpPr><p:grpSpPr><a:xfrm>{}{}</a:xfrm></p:grpSpPr>"#,
+            id,
+            crate::escape_xml(&shape_name(&group.id, "Group", id)),
+            xfrm,
+            child_xfrm
+        );
+        for child in &children {
+            xml.push_str(&child.xml);
+        }
+        xml.push_str("</p:grpSp>");
+
+        vec![ConvertedShape {
+            id,
+            xml,
+            bounds,
+            text: None,
+        }]
+    }
+
+    fn convert_path(&mut self, path: &usvg::Path, opacity: f32) -> Option<ConvertedShape> {
+        if path.visibility != usvg::Visibility::Visible {
+            return None;
+        }
+
+        let transform = self.root_transform.pre_concat(path.abs_transform);
+        let data = path.data.as_ref().clone().transform(transform)?;
+        let (bounds, geometry) = geometry::classify(&data)?;
+
+        let id = self.allocate_id();
+        let (kind, flip) = match &geometry {
+            Geometry::Rect => ("Rectangle", String::new()),
+            Geometry::Ellipse => ("Oval", String::new()),
+            Geometry::Line { flip_h, flip_v } => (
+                "Straight Connector",
+                format!(
+                    "{}{}",
+                    if *flip_h { r#" flipH="1""# } else { "" },
+                    if *flip_v { r#" flipV="1""# } else { "" }
+                ),
+            ),
+            Geometry::Custom(_) => ("Freeform", String::new()),
+        };
+
+        let name = shape_name(&path.id, kind, id);
+
+        // 直线没有可填充区域
+        let fill = if matches!(geometry, Geometry::Line { .. }) {
+            "<a:noFill/>".to_string()
+        } else {
+            style::fill_xml(path.fill.as_ref(), opacity)
+        };
+
+        let (sx, sy) = transform.get_scale();
+        let stroke = style::stroke_xml(path.stroke.as_ref(), (sx * sy).sqrt(), opacity);
+
+        let xml = format!(
+            r#"<p:sp><p:nvSpPr><p:cNvPr id="{}" name="{}"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm{}>{}</a:xfrm>{}{}{}</p:spPr></p:sp>"#,
+            id,
+            crate::escape_xml(&name),
+            flip,
+            bounds.to_xfrm_body(),
+            geometry.to_xml(&bounds),
+            fill,
+            stroke
+        );
+
+        Some(ConvertedShape {
+            id,
+            xml,
+            bounds,
+            text: None,
+        })
+    }
+
+    fn convert_text(&mut self, text: &usvg::Text, opacity: f32) -> Option<ConvertedShape> {
+        let transform = self.root_transform.pre_concat(text.abs_transform);
+        let id = self.next_id;
+        let text_box = text::convert_text(text, transform, opacity, id)?;
+        self.allocate_id();
+
+        let content = text
+            .chunks
+            .iter()
+            .map(|chunk| chunk.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Some(ConvertedShape {
+            id,
+            xml: text_box.xml,
+            bounds: text_box.bounds,
+            text: Some(TextInfo {
+                font_size: text_box.font_size,
+                content,
+            }),
+        })
+    }
+
+    fn convert_image(&mut self, image: &usvg::Image, opacity: f32) -> Option<ConvertedShape> {
+        let transform = self.root_transform.pre_concat(image.abs_transform);
+        let id = self.next_id;
+        let picture = picture::convert_image(image, transform, opacity, id, self.images.len())?;
+        self.allocate_id();
+        self.images.push(picture.image);
+
+        Some(ConvertedShape {
+            id,
+            xml: picture.xml,
+            bounds: picture.bounds,
+            text: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_svg_to_shapes_basic_elements() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="1280" height="720" viewBox="0 0 1280 720">
+            <rect x="0" y="0" width="1280" height="720" fill="#F5F5F5"/>
+            <ellipse cx="200" cy="200" rx="100" ry="50" fill="#FF0000" fill-opacity="0.5"/>
+            <line x1="0" y1="400" x2="1280" y2="400" stroke="#333333" stroke-width="2"/>
+            <path d="M 10 10 L 60 10 L 35 50 Z" fill="#00FF00"/>
+        </svg>"##;
+
+        let xml = svg_to_shapes(svg, 1280, 720).expect("转换应成功").xml;
+
+        assert!(xml.contains(r#"<a:prstGeom prst="rect">"#));
+        assert!(xml.contains(r#"<a:prstGeom prst="ellipse">"#));
+        assert!(xml.contains(r#"<a:prstGeom prst="line">"#));
+        assert!(xml.contains("<a:custGeom>"));
+        assert!(xml.contains(r#"<a:srgbClr val="FF0000"><a:alpha val="50000"/></a:srgbClr>"#));
+        assert!(xml.contains(r#"<a:ln w="19050" cap="flat">"#));
+        assert!(!xml.contains("TODO"));
+    }
+
+    #[test]
+    fn test_svg_to_shapes_scales_view_box() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 640 360">
+            <rect x="10" y="10" width="100" height="100" fill="#000000"/>
+        </svg>"##;
+
+        let xml = svg_to_shapes(svg, 1280, 720).expect("转换应成功").xml;
+        assert!(xml.contains(r#"<a:off x="190500" y="190500"/><a:ext cx="1905000" cy="1905000"/>"#));
+    }
+
+    #[test]
+    fn test_svg_to_shapes_named_group() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+            <g id="card">
+                <rect x="0" y="0" width="50" height="50" fill="#000000"/>
+                <rect x="50" y="50" width="50" height="50" fill="#FFFFFF"/>
+            </g>
+        </svg>"##;
+
+        let tree = svg_to_shapes(svg, 100, 100).expect("转换应成功");
+        assert_eq!(tree.shape_ids, vec![4]);
+
+        let xml = tree.xml;
+        assert!(xml.starts_with(r#"<p:grpSp><p:nvGrpSpPr><p:cNvPr id="4" name="card"/>"#));
+        assert!(xml.contains(r#"<a:chOff x="0" y="0"/><a:chExt cx="952500" cy="952500"/>"#));
+
+        // 只有一个形状的分组直接展开，不占用形状 ID
+        let single = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+            <g id="solo"><rect x="0" y="0" width="50" height="50" fill="#000000"/></g>
+            <rect x="50" y="50" width="50" height="50" fill="#FFFFFF"/>
+        </svg>"##;
+        let tree = svg_to_shapes(single, 100, 100).expect("转换应成功");
+        assert_eq!(tree.shape_ids, vec![2, 3]);
+        assert!(!tree.xml.contains("<p:grpSp>"));
+    }
+
+    #[test]
+    fn test_svg_to_shapes_accessibility() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720">
+            <title>年度总结</title>
+            <rect width="1280" height="720" fill="#FFFFFF"/>
+            <g><title>增长趋势</title><desc>营收连续四个季度增长</desc>
+                <rect x="700" y="200" width="200" height="200" fill="#1A73E8"/>
+                <rect x="900" y="200" width="200" height="200" fill="#34A853"/>
+            </g>
+            <rect x="100" y="200" width="300" height="200" fill="#EEEEEE" aria-hidden="true"/>
+            <text x="100" y="100" font-size="48">年度总结</text>
+        </svg>"##;
+
+        let tree = svg_to_shapes(svg, 1280, 720).expect("转换应成功");
+        assert!(tree.has_title);
+        // 背景在前；标题、左侧矩形、右侧分组按阅读顺序排列
+        let xml = &tree.xml;
+        let positions: Vec<usize> = [
+            r#"name="Rectangle 2""#,
+            r#"<p:ph type="title"/>"#,
+            "adec:decorative",
+            r#"descr="营收连续四个季度增长" title="增长趋势""#,
+        ]
+        .iter()
+        .map(|needle| xml.find(needle).expect(needle))
+        .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(xml.contains(r#"<p:grpSp><p:nvGrpSpPr><p:cNvPr id="5" name="Group 5" descr="#));
+
+        // 页面上没有与 <title> 对应的文字时，在画布外添加标题占位符
+        let untitled = svg
+            .replace("<text", "<text id=\"heading\" font-weight=\"bold\"")
+            .replace(">年度总结</text>", ">摘要</text>");
+        let tree = svg_to_shapes(&untitled, 1280, 720).expect("转换应成功");
+        assert!(tree
+            .xml
+            .starts_with(r#"<p:sp><p:nvSpPr><p:cNvPr id="8" name="Title 8"/>"#));
+        assert!(tree.xml.contains("<a:t>年度总结</a:t>"));
+        assert!(!tree.shape_ids.contains(&8));
+    }
+}
@@ -0,0 +1,277 @@
+// 图片转换
+//
+// 将 SVG 的 <image>（PNG/JPEG/GIF）转换为 p:pic，图片数据交给媒体库统一写入，
+// 关系 ID 在幻灯片关系建立后回填
+
+use super::geometry::Bounds;
+use crate::escape_xml;
+use crate::package::content_types;
+use std::sync::Arc;
+use usvg::{ImageKind, NonZeroRect, Transform, Visibility};
+
+/// 待写入的嵌入图片
+#[derive(Debug, Clone)]
+pub struct EmbeddedImage {
+    /// 图片文件内容
+    pub data: Arc<Vec<u8>>,
+    /// 文件扩展名
+    pub extension: &'static str,
+    /// 内容类型
+    pub content_type: &'static str,
+}
+
+/// 图片转换结果
+pub struct Picture {
+    /// `p:pic` XML，`r:embed` 为占位符
+    pub xml: String,
+    /// 图片在幻灯片坐标系（像素）中的位置
+    pub bounds: Bounds,
+    /// 图片数据
+    pub image: EmbeddedImage,
+}
+
+/// 第 `index` 张嵌入图片的关系 ID 占位符
+pub fn embed_placeholder(index: usize) -> String {
+    format!("__image_{}__", index)
+}
+
+/// 将 `<image>` 元素转换为图片
+///
+/// `index` 为该图片在本页嵌入图片中的序号；嵌套 SVG 图片不转换
+pub fn convert_image(
+    image: &usvg::Image,
+    transform: Transform,
+    opacity: f32,
+    id: u32,
+    index: usize,
+) -> Option<Picture> {
+    if image.visibility != Visibility::Visible {
+        return None;
+    }
+
+    let (data, extension, content_type) = match &image.kind {
+        ImageKind::PNG(data) => (data, "png", content_types::PNG),
+        ImageKind::JPEG(data) => (data, "jpeg", content_types::JPEG),
+        ImageKind::GIF(data) => (data, "gif", content_types::GIF),
+        ImageKind::SVG(_) => return None,
+    };
+
+    let view = image.view_box.rect;
+    let (frame, crop) = match intrinsic_size(&image.kind) {
+        Some((width, height)) => fit_image(view, width, height, image.view_box.aspect),
+        None => (view, None),
+    };
+    let bounds = map_rect(frame, transform)?;
+
+    let alpha = if opacity < 1.0 {
+        format!(
+            r#"<a:alphaModFix amt="{}"/>"#,
+            (opacity * 100_000.0).round() as i32
+        )
+    } else {
+        String::new()
+    };
+
+    let name = super::shape_name(&image.id, "Picture", id);
+
+    let xml = format!(
+        r#"<p:pic><p:nvPicPr><p:cNvPr id="{}" name="{}"/><p:cNvPicPr><a:picLocks noChangeAspect="1"/></p:cNvPicPr><p:nvPr/></p:nvPicPr><p:blipFill><a:blip r:embed="{}">{}</a:blip>{}<a:stretch><a:fillRect/></a:stretch></p:blipFill><p:spPr><a:xfrm>{}</a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom></p:spPr></p:pic>"#,
+        id,
+        escape_xml(&name),
+        embed_placeholder(index),
+        alpha,
+        crop.unwrap_or_default(),
+        bounds.to_xfrm_body()
+    );
+
+    Some(Picture {
+        xml,
+        bounds,
+        image: EmbeddedImage {
+            data: Arc::clone(data),
+            extension,
+            content_type,
+        },
+    })
+}
+
+/// 按 preserveAspectRatio 计算图片实际占据的区域
+///
+/// `slice` 时图片框为整个视口，超出部分通过 `a:srcRect` 裁剪
+fn fit_image(
+    view: NonZeroRect,
+    width: f32,
+    height: f32,
+    aspect: usvg::AspectRatio,
+) -> (NonZeroRect, Option<String>) {
+    let Some(source) = NonZeroRect::from_xywh(0.0, 0.0, width, height) else {
+        return (view, None);
+    };
+    let ts = usvg::utils::view_box_to_transform(source, aspect, view.size());
+    let Some(placed) = NonZeroRect::from_xywh(
+        view.x() + ts.tx,
+        view.y() + ts.ty,
+        width * ts.sx,
+        height * ts.sy,
+    ) else {
+        return (view, None);
+    };
+
+    if !aspect.slice {
+        return (placed, None);
+    }
+
+    // 裁剪比例以千分之一百分比表示
+    let percent = |value: f32, total: f32| (value / total * 100_000.0).round() as i32;
+    let crop = format!(
+        r#"<a:srcRect l="{}" t="{}" r="{}" b="{}"/>"#,
+        percent(view.x() - placed.x(), placed.width()),
+        percent(view.y() - placed.y(), placed.height()),
+        percent(placed.right() - view.right(), placed.width()),
+        percent(placed.bottom() - view.bottom(), placed.height())
+    );
+    (view, Some(crop))
+}
+
+/// 变换后的外接矩形（旋转按外接矩形近似）
+pub(super) fn map_rect(rect: NonZeroRect, transform: Transform) -> Option<Bounds> {
+    let mut corners = [
+        usvg::tiny_skia_path::Point::from_xy(rect.left(), rect.top()),
+        usvg::tiny_skia_path::Point::from_xy(rect.right(), rect.top()),
+        usvg::tiny_skia_path::Point::from_xy(rect.right(), rect.bottom()),
+        usvg::tiny_skia_path::Point::from_xy(rect.left(), rect.bottom()),
+    ];
+    transform.map_points(&mut corners);
+
+    let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+    let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+    for point in &corners {
+        min_x = min_x.min(point.x);
+        min_y = min_y.min(point.y);
+        max_x = max_x.max(point.x);
+        max_y = max_y.max(point.y);
+    }
+
+    (max_x > min_x && max_y > min_y).then_some(Bounds {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x,
+        height: max_y - min_y,
+    })
+}
+
+/// 从文件头读取图片像素尺寸
+fn intrinsic_size(kind: &ImageKind) -> Option<(f32, f32)> {
+    match kind {
+        ImageKind::PNG(data) => {
+            // 签名（8 字节）+ IHDR 长度与类型（8 字节）之后是宽高
+            if data.len() < 24 || &data[12..16] != b"IHDR" {
+                return None;
+            }
+            let width = u32::from_be_bytes(data[16..20].try_into().ok()?);
+            let height = u32::from_be_bytes(data[20..24].try_into().ok()?);
+            Some((width as f32, height as f32))
+        }
+        ImageKind::GIF(data) => {
+            if data.len() < 10 || !data.starts_with(b"GIF") {
+                return None;
+            }
+            let width = u16::from_le_bytes([data[6], data[7]]);
+            let height = u16::from_le_bytes([data[8], data[9]]);
+            Some((width as f32, height as f32))
+        }
+        ImageKind::JPEG(data) => jpeg_size(data),
+        ImageKind::SVG(_) => None,
+    }
+}
+
+/// 扫描 JPEG 段，读取 SOF 段中的宽高
+fn jpeg_size(data: &[u8]) -> Option<(f32, f32)> {
+    let mut offset = 2;
+    while offset + 9 < data.len() {
+        if data[offset] != 0xFF {
+            return None;
+        }
+        let marker = data[offset + 1];
+        let length = u16::from_be_bytes([data[offset + 2], data[offset + 3]]) as usize;
+
+        // SOF0-SOF15，排除 DHT(C4)、JPG(C8)、DAC(CC)
+        if (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC) {
+            let height = u16::from_be_bytes([data[offset + 5], data[offset + 6]]);
+            let width = u16::from_be_bytes([data[offset + 7], data[offset + 8]]);
+            return Some((width as f32, height as f32));
+        }
+
+        offset += 2 + length;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::svg_to_shapes;
+    use super::*;
+    use base64::Engine;
+
+    /// 指定尺寸 PNG 的文件头（只需 IHDR 即可读取尺寸）
+    fn png_header(width: u32, height: u32) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_intrinsic_size() {
+        let png = ImageKind::PNG(Arc::new(png_header(200, 100)));
+        assert_eq!(intrinsic_size(&png), Some((200.0, 100.0)));
+
+        let gif = ImageKind::GIF(Arc::new(b"GIF89a\x40\x00\x20\x00".to_vec()));
+        assert_eq!(intrinsic_size(&gif), Some((64.0, 32.0)));
+
+        let jpeg = vec![
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00,
+            0x30, 0x00, 0x40, 0x03,
+        ];
+        assert_eq!(jpeg_size(&jpeg), Some((64.0, 48.0)));
+    }
+
+    #[test]
+    fn test_image_to_picture() {
+        let png = base64::engine::general_purpose::STANDARD.encode(png_header(200, 100));
+        let svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" viewBox="0 0 1280 720">
+                <image id="logo" x="100" y="100" width="200" height="200" opacity="0.5" xlink:href="data:image/png;base64,{}"/>
+            </svg>"#,
+            png
+        );
+
+        let tree = svg_to_shapes(&svg, 1280, 720).expect("转换应成功");
+        assert_eq!(tree.images.len(), 1);
+        assert_eq!(tree.images[0].extension, "png");
+
+        // 默认 xMidYMid meet：200x100 的图片居中放入 200x200 的视口
+        let xml = &tree.xml;
+        assert!(xml.contains(r#"name="logo""#));
+        assert!(
+            xml.contains(r#"<a:blip r:embed="__image_0__"><a:alphaModFix amt="50000"/></a:blip>"#)
+        );
+        assert!(xml.contains(r#"<a:off x="952500" y="1428750"/><a:ext cx="1905000" cy="952500"/>"#));
+    }
+
+    #[test]
+    fn test_slice_image_is_cropped() {
+        let view = NonZeroRect::from_xywh(0.0, 0.0, 100.0, 100.0).unwrap();
+        let aspect = usvg::AspectRatio {
+            slice: true,
+            ..usvg::AspectRatio::default()
+        };
+
+        let (frame, crop) = fit_image(view, 200.0, 100.0, aspect);
+        assert_eq!(frame, view);
+        assert_eq!(
+            crop.as_deref(),
+            Some(r#"<a:srcRect l="25000" t="0" r="25000" b="0"/>"#)
+        );
+    }
+}
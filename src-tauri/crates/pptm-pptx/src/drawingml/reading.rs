@@ -0,0 +1,241 @@
+// 阅读顺序与幻灯片标题
+//
+// 读屏软件按 spTree 中的顺序朗读形状，而 spTree 顺序同时决定层叠关系。排序时只交换
+// 互不重叠的形状，保证渲染结果不变；标题占位符（p:ph type="title"）供读屏软件与
+// PowerPoint 辅助功能检查识别幻灯片标题
+
+use super::geometry::Bounds;
+use crate::escape_xml;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+
+/// 判断重叠时向外扩展的距离（像素），覆盖描边等未计入范围的部分
+const OVERLAP_MARGIN: f32 = 2.0;
+
+/// 画布外标题框的高度（像素）
+const HIDDEN_TITLE_HEIGHT: f32 = 60.0;
+
+/// 文本形状的内容，用于确定标题
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextInfo {
+    /// 首行字号（幻灯片像素）
+    pub font_size: f32,
+    /// 纯文本内容
+    pub content: String,
+}
+
+/// 按阅读顺序（从上到下、同一行从左到右）排列形状，返回原下标
+///
+/// 相互重叠的形状保持原有的先后顺序：每个形状要等之前与它重叠的形状都排列后才可选，
+/// 可选的形状中取位置最靠前的。重叠计数只计算一次，整体为 O(n²)
+pub fn reading_order(bounds: &[Bounds]) -> Vec<usize> {
+    // 之前与之重叠、尚未排列的形状数
+    let mut blockers: Vec<usize> = (0..bounds.len())
+        .map(|index| {
+            (0..index)
+                .filter(|&before| overlaps(&bounds[before], &bounds[index]))
+                .count()
+        })
+        .collect();
+    // 按下标顺序保存，位置比较不满足传递性，遍历顺序影响结果
+    let mut ready: BTreeSet<usize> = (0..bounds.len())
+        .filter(|&index| blockers[index] == 0)
+        .collect();
+    let mut order = Vec::with_capacity(bounds.len());
+
+    while let Some(next) = ready
+        .iter()
+        .copied()
+        .min_by(|&a, &b| compare_position(&bounds[a], &bounds[b]).then(a.cmp(&b)))
+    {
+        ready.remove(&next);
+        order.push(next);
+
+        for after in next + 1..bounds.len() {
+            if overlaps(&bounds[next], &bounds[after]) {
+                blockers[after] -= 1;
+                if blockers[after] == 0 {
+                    ready.insert(after);
+                }
+            }
+        }
+    }
+
+    order
+}
+
+fn overlaps(a: &Bounds, b: &Bounds) -> bool {
+    a.x - OVERLAP_MARGIN < b.x + b.width
+        && b.x - OVERLAP_MARGIN < a.x + a.width
+        && a.y - OVERLAP_MARGIN < b.y + b.height
+        && b.y - OVERLAP_MARGIN < a.y + a.height
+}
+
+/// 纵向范围大部分重叠的形状视为同一行，按横坐标排序；否则按纵坐标排序
+fn compare_position(a: &Bounds, b: &Bounds) -> Ordering {
+    let overlap = (a.y + a.height).min(b.y + b.height) - a.y.max(b.y);
+    let same_row = overlap > 0.0 && overlap >= a.height.min(b.height) / 2.0;
+
+    if same_row {
+        a.x.total_cmp(&b.x)
+    } else {
+        a.y.total_cmp(&b.y)
+    }
+}
+
+/// 选择作为幻灯片标题的文本形状
+///
+/// 与 SVG 根元素 `<title>` 文字相同的文本优先；没有 `<title>` 时取上半页字号最大的文本
+/// （字号相同时取靠上的）。声明了 `<title>` 但页面上没有对应文字时返回 None
+pub fn choose_title(
+    texts: &[(usize, &Bounds, &TextInfo)],
+    document_title: Option<&str>,
+    canvas_height: f32,
+) -> Option<usize> {
+    if let Some(title) = document_title {
+        let title = normalize(title);
+        return texts
+            .iter()
+            .find(|(_, _, text)| normalize(&text.content) == title)
+            .map(|(index, _, _)| *index);
+    }
+
+    texts
+        .iter()
+        .filter(|(_, bounds, text)| {
+            bounds.y < canvas_height / 2.0 && !text.content.trim().is_empty()
+        })
+        .max_by(|(_, a_bounds, a), (_, b_bounds, b)| {
+            a.font_size
+                .total_cmp(&b.font_size)
+                .then(b_bounds.y.total_cmp(&a_bounds.y))
+        })
+        .map(|(index, _, _)| *index)
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 将文本框标记为标题占位符
+pub fn mark_title(xml: &str) -> String {
+    xml.replacen(
+        r#"<p:cNvSpPr txBox="1"/><p:nvPr/>"#,
+        r#"<p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr><p:nvPr><p:ph type="title"/></p:nvPr>"#,
+        1,
+    )
+}
+
+/// 位于画布上方（放映时不可见）的标题占位符，供读屏软件识别幻灯片标题
+pub fn hidden_title_xml(id: u32, title: &str, canvas_width: f32) -> String {
+    let bounds = Bounds {
+        x: 0.0,
+        y: -HIDDEN_TITLE_HEIGHT,
+        width: canvas_width,
+        height: HIDDEN_TITLE_HEIGHT,
+    };
+
+    format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="{}" name="Title {}"/><p:cNvSpPr><a:spLocks noGrp="1"/></p:cNvSpPr><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr><p:spPr><a:xfrm>{}</a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:noFill/></p:spPr><p:txBody><a:bodyPr/><a:lstStyle/><a:p><a:r><a:rPr lang="zh-CN" dirty="0"/><a:t>{}</a:t></a:r></a:p></p:txBody></p:sp>"#,
+        id,
+        id,
+        bounds.to_xfrm_body(),
+        escape_xml(title)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Bounds {
+        Bounds {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_reading_order_keeps_stacking() {
+        let bounds = [
+            // 背景覆盖全部形状，必须保持在最前
+            rect(0.0, 0.0, 1280.0, 720.0),
+            // 右侧卡片与其上的文字
+            rect(700.0, 200.0, 400.0, 300.0),
+            rect(720.0, 220.0, 200.0, 40.0),
+            // 左侧卡片（与右侧同一行）
+            rect(100.0, 210.0, 400.0, 300.0),
+            // 标题在最上方，但在 SVG 中最后绘制
+            rect(100.0, 60.0, 600.0, 60.0),
+        ];
+
+        assert_eq!(reading_order(&bounds), vec![0, 4, 3, 1, 2]);
+    }
+
+    #[test]
+    fn test_reading_order_many_shapes() {
+        // 上千个相互交错的形状（如图标、地图的扁平路径）
+        let bounds: Vec<_> = (0..3000u32)
+            .map(|i| {
+                let (x, y) = ((i * 37 % 1280) as f32, (i * 91 % 720) as f32);
+                rect(
+                    x,
+                    y,
+                    20.0 + (i % 7) as f32 * 10.0,
+                    15.0 + (i % 5) as f32 * 8.0,
+                )
+            })
+            .collect();
+
+        let order = reading_order(&bounds);
+        let mut position = vec![0; bounds.len()];
+        for (rank, &index) in order.iter().enumerate() {
+            position[index] = rank;
+        }
+
+        // 每个形状恰好出现一次，重叠的形状保持原有先后顺序
+        let mut sorted = order.clone();
+        sorted.sort_unstable();
+        assert!(sorted.iter().copied().eq(0..bounds.len()));
+        for a in 0..bounds.len() {
+            for b in a + 1..bounds.len() {
+                if overlaps(&bounds[a], &bounds[b]) {
+                    assert!(position[a] < position[b]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_choose_title() {
+        let heading = TextInfo {
+            font_size: 40.0,
+            content: "市场  概览".to_string(),
+        };
+        let body = TextInfo {
+            font_size: 18.0,
+            content: "正文".to_string(),
+        };
+        let footer = TextInfo {
+            font_size: 48.0,
+            content: "2025".to_string(),
+        };
+        let (top, middle, bottom) = (
+            rect(80.0, 40.0, 400.0, 48.0),
+            rect(80.0, 120.0, 400.0, 20.0),
+            rect(80.0, 600.0, 100.0, 50.0),
+        );
+        let texts = [
+            (0, &top, &heading),
+            (1, &middle, &body),
+            (2, &bottom, &footer),
+        ];
+
+        assert_eq!(choose_title(&texts, None, 720.0), Some(0));
+        assert_eq!(choose_title(&texts, Some("市场 概览"), 720.0), Some(0));
+        assert_eq!(choose_title(&texts, Some("其他标题"), 720.0), None);
+        assert_eq!(choose_title(&texts[1..], None, 720.0), Some(1));
+    }
+}
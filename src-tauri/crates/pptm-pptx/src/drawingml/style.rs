@@ -0,0 +1,135 @@
+// 填充与描边样式转换
+//
+// 将 usvg 的 Fill / Stroke 转换为 DrawingML 的 a:solidFill / a:gradFill / a:ln
+
+use super::px_to_emu;
+use usvg::{Color, Fill, LineCap, LineJoin, Paint, Stroke};
+
+/// 生成 `a:srgbClr` 颜色元素，`opacity` 小于 1 时附带 `a:alpha`
+pub fn srgb_color(color: Color, opacity: f32) -> String {
+    let hex = format!("{:02X}{:02X}{:02X}", color.red, color.green, color.blue);
+
+    if opacity < 1.0 {
+        format!(
+            r#"<a:srgbClr val="{}"><a:alpha val="{}"/></a:srgbClr>"#,
+            hex,
+            (opacity.clamp(0.0, 1.0) * 100_000.0).round() as u32
+        )
+    } else {
+        format!(r#"<a:srgbClr val="{}"/>"#, hex)
+    }
+}
+
+/// 生成形状填充，`inherited_opacity` 为祖先分组透明度的累积值
+pub fn fill_xml(fill: Option<&Fill>, inherited_opacity: f32) -> String {
+    match fill {
+        Some(fill) => paint_xml(&fill.paint, fill.opacity.get() * inherited_opacity),
+        None => "<a:noFill/>".to_string(),
+    }
+}
+
+/// 生成形状描边，`scale` 为 SVG 坐标到幻灯片像素的缩放系数
+pub fn stroke_xml(stroke: Option<&Stroke>, scale: f32, inherited_opacity: f32) -> String {
+    let stroke = match stroke {
+        Some(stroke) => stroke,
+        None => return "<a:ln><a:noFill/></a:ln>".to_string(),
+    };
+
+    let cap = match stroke.linecap {
+        LineCap::Butt => "flat",
+        LineCap::Round => "rnd",
+        LineCap::Square => "sq",
+    };
+
+    let join = match stroke.linejoin {
+        LineJoin::Round => "<a:round/>",
+        LineJoin::Bevel => "<a:bevel/>",
+        LineJoin::Miter | LineJoin::MiterClip => "<a:miter/>",
+    };
+
+    let dash = match &stroke.dasharray {
+        Some(dashes) if !dashes.is_empty() => dash_preset(dashes, stroke.width.get()),
+        _ => "",
+    };
+
+    format!(
+        r#"<a:ln w="{}" cap="{}">{}{}{}</a:ln>"#,
+        px_to_emu(stroke.width.get() * scale),
+        cap,
+        paint_xml(&stroke.paint, stroke.opacity.get() * inherited_opacity),
+        dash,
+        join
+    )
+}
+
+fn paint_xml(paint: &Paint, opacity: f32) -> String {
+    match paint {
+        Paint::Color(color) => {
+            format!("<a:solidFill>{}</a:solidFill>", srgb_color(*color, opacity))
+        }
+        Paint::LinearGradient(gradient) => {
+            let angle = (gradient.y2 - gradient.y1)
+                .atan2(gradient.x2 - gradient.x1)
+                .to_degrees()
+                .rem_euclid(360.0);
+            format!(
+                r#"<a:gradFill rotWithShape="1">{}<a:lin ang="{}" scaled="0"/></a:gradFill>"#,
+                gradient_stops(&gradient.stops, opacity),
+                (angle * 60_000.0).round() as u32
+            )
+        }
+        Paint::RadialGradient(gradient) => format!(
+            r#"<a:gradFill rotWithShape="1">{}<a:path path="circle"><a:fillToRect l="50000" t="50000" r="50000" b="50000"/></a:path></a:gradFill>"#,
+            gradient_stops(&gradient.stops, opacity)
+        ),
+        // DrawingML 没有对应的图案填充，退化为无填充
+        Paint::Pattern(_) => "<a:noFill/>".to_string(),
+    }
+}
+
+fn gradient_stops(stops: &[usvg::Stop], opacity: f32) -> String {
+    let mut xml = String::from("<a:gsLst>");
+    for stop in stops {
+        xml.push_str(&format!(
+            r#"<a:gs pos="{}">{}</a:gs>"#,
+            (stop.offset.get() * 100_000.0).round() as u32,
+            srgb_color(stop.color, stop.opacity.get() * opacity)
+        ));
+    }
+    xml.push_str("</a:gsLst>");
+    xml
+}
+
+/// 按虚线段长与线宽的比例选择最接近的预设虚线
+fn dash_preset(dashes: &[f32], width: f32) -> &'static str {
+    let ratio = dashes[0] / width.max(f32::EPSILON);
+
+    if ratio <= 1.5 {
+        r#"<a:prstDash val="sysDot"/>"#
+    } else if ratio <= 4.5 {
+        r#"<a:prstDash val="dash"/>"#
+    } else {
+        r#"<a:prstDash val="lgDash"/>"#
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srgb_color_with_alpha() {
+        let color = Color::new_rgb(255, 0, 128);
+        assert_eq!(srgb_color(color, 1.0), r#"<a:srgbClr val="FF0080"/>"#);
+        assert_eq!(
+            srgb_color(color, 0.5),
+            r#"<a:srgbClr val="FF0080"><a:alpha val="50000"/></a:srgbClr>"#
+        );
+    }
+
+    #[test]
+    fn test_missing_fill_and_stroke() {
+        assert_eq!(fill_xml(None, 1.0), "<a:noFill/>");
+        assert_eq!(stroke_xml(None, 1.0, 1.0), "<a:ln><a:noFill/></a:ln>");
+    }
+}
@@ -0,0 +1,581 @@
+// 表格识别
+//
+// 数据页常用矩形与文本拼出表格。带 `data-table` 属性的分组按其中的矩形、直线与文本
+// 识别网格，输出为 a:tbl 表格：矩形填充成为单元格底色，网格线与矩形描边成为单元格
+// 边框，文本按位置放入单元格并保留字体样式。
+//
+// usvg 会丢弃 data-* 属性，解析前先记录表格分组并确保其带 ID
+
+use super::geometry::{self, Bounds, Geometry};
+use super::{px_to_emu, style, text};
+use crate::{escape_xml, PptxError, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::HashSet;
+use usvg::{Group, Node, Transform};
+
+/// 为没有 ID 的表格生成的分组 ID 前缀
+const GENERATED_ID_PREFIX: &str = "__svg_table_";
+
+/// 网格线位置的合并容差（像素）
+const TOLERANCE: f32 = 2.0;
+
+/// 不超过该厚度（像素）的填充矩形视为网格线
+const RULE_THICKNESS: f32 = 3.0;
+
+/// 识别出的表格
+pub struct Table {
+    /// `p:graphicFrame` XML
+    pub xml: String,
+    /// 表格在幻灯片坐标系（像素）中的位置
+    pub bounds: Bounds,
+}
+
+/// 分组 ID 是否为表格提取时生成
+pub fn is_generated_id(id: &str) -> bool {
+    id.starts_with(GENERATED_ID_PREFIX)
+}
+
+/// 记录带 `data-table` 的分组，返回改写后的 SVG 与表格分组 ID
+pub fn extract_tables(svg: &str) -> Result<(String, HashSet<String>)> {
+    let mut tables = HashSet::new();
+    if !svg.contains("data-table") {
+        return Ok((svg.to_string(), tables));
+    }
+
+    let mut reader = Reader::from_str(svg);
+    let mut writer = Writer::new(Vec::with_capacity(svg.len()));
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+
+        let event = match event {
+            Event::Eof => break,
+            Event::Start(start) if start.name().as_ref() == b"g" => {
+                Event::Start(table_group(&start, &mut tables)?)
+            }
+            Event::Empty(start) if start.name().as_ref() == b"g" => {
+                Event::Empty(table_group(&start, &mut tables)?)
+            }
+            other => other,
+        };
+
+        writer
+            .write_event(event)
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    }
+
+    let svg =
+        String::from_utf8(writer.into_inner()).map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    Ok((svg, tables))
+}
+
+fn table_group(start: &BytesStart, tables: &mut HashSet<String>) -> Result<BytesStart<'static>> {
+    let mut group = BytesStart::new("g");
+    let mut id = None;
+    let mut is_table = false;
+
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| PptxError::SvgParse(e.to_string()))?;
+        match attr.key.as_ref() {
+            b"data-table" => {
+                let value = attr
+                    .unescape_value()
+                    .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+                is_table = value.trim() != "false";
+            }
+            b"id" => {
+                id = Some(
+                    attr.unescape_value()
+                        .map_err(|e| PptxError::SvgParse(e.to_string()))?
+                        .trim()
+                        .to_string(),
+                );
+                group.push_attribute(attr);
+            }
+            _ => group.push_attribute(attr),
+        }
+    }
+
+    if is_table {
+        let id = match id.filter(|id| !id.is_empty()) {
+            Some(id) => id,
+            None => {
+                let id = format!("{}{}", GENERATED_ID_PREFIX, tables.len());
+                group.push_attribute(("id", id.as_str()));
+                id
+            }
+        };
+        tables.insert(id);
+    }
+
+    Ok(group)
+}
+
+/// 填充矩形（单元格底色）
+struct FilledRect {
+    bounds: Bounds,
+    fill: String,
+}
+
+/// 水平或垂直的网格线段
+struct Rule {
+    horizontal: bool,
+    /// 水平线为 y，垂直线为 x
+    position: f32,
+    start: f32,
+    end: f32,
+    /// `a:ln` XML
+    line: String,
+}
+
+/// 从分组中收集的表格元素
+#[derive(Default)]
+struct Elements {
+    rects: Vec<FilledRect>,
+    rules: Vec<Rule>,
+    texts: Vec<text::TextLayout>,
+}
+
+/// 将表格分组转换为表格 graphicFrame，无法识别出网格时返回 None
+pub fn convert_table(
+    group: &Group,
+    root_transform: Transform,
+    opacity: f32,
+    id: u32,
+) -> Option<Table> {
+    let mut elements = Elements::default();
+    collect(group, root_transform, opacity, &mut elements);
+
+    let (columns, rows) = grid(&elements)?;
+    let bounds = Bounds {
+        x: columns[0],
+        y: rows[0],
+        width: columns[columns.len() - 1] - columns[0],
+        height: rows[rows.len() - 1] - rows[0],
+    };
+
+    // 文本按中心点放入单元格
+    let mut cell_texts: Vec<Vec<&text::TextLayout>> = (0..(columns.len() - 1) * (rows.len() - 1))
+        .map(|_| Vec::new())
+        .collect();
+    for layout in &elements.texts {
+        let center_x = layout.bounds.x + layout.bounds.width / 2.0;
+        let center_y = layout.bounds.y + layout.bounds.height / 2.0;
+        if let (Some(column), Some(row)) = (locate(&columns, center_x), locate(&rows, center_y)) {
+            cell_texts[row * (columns.len() - 1) + column].push(layout);
+        }
+    }
+
+    let grid_xml: String = columns
+        .windows(2)
+        .map(|edge| format!(r#"<a:gridCol w="{}"/>"#, px_to_emu(edge[1] - edge[0])))
+        .collect();
+
+    let mut rows_xml = String::new();
+    for row in 0..rows.len() - 1 {
+        rows_xml.push_str(&format!(
+            r#"<a:tr h="{}">"#,
+            px_to_emu(rows[row + 1] - rows[row])
+        ));
+        for column in 0..columns.len() - 1 {
+            let cell = Bounds {
+                x: columns[column],
+                y: rows[row],
+                width: columns[column + 1] - columns[column],
+                height: rows[row + 1] - rows[row],
+            };
+            rows_xml.push_str(&cell_xml(
+                &cell,
+                &cell_texts[row * (columns.len() - 1) + column],
+                &elements,
+            ));
+        }
+        rows_xml.push_str("</a:tr>");
+    }
+
+    let name = super::shape_name(&group.id, "Table", id);
+
+    let xml = format!(
+        r#"<p:graphicFrame><p:nvGraphicFramePr><p:cNvPr id="{}" name="{}"/><p:cNvGraphicFramePr><a:graphicFrameLocks noGrp="1"/></p:cNvGraphicFramePr><p:nvPr/></p:nvGraphicFramePr><p:xfrm>{}</p:xfrm><a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table"><a:tbl><a:tblPr/><a:tblGrid>{}</a:tblGrid>{}</a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#,
+        id,
+        escape_xml(&name),
+        bounds.to_xfrm_body(),
+        grid_xml,
+        rows_xml
+    );
+
+    Some(Table { xml, bounds })
+}
+
+fn collect(group: &Group, root_transform: Transform, opacity: f32, elements: &mut Elements) {
+    for node in &group.children {
+        match node {
+            Node::Group(child) => collect(
+                child,
+                root_transform,
+                opacity * child.opacity.get(),
+                elements,
+            ),
+            Node::Path(path) => {
+                if path.visibility == usvg::Visibility::Visible {
+                    collect_path(path, root_transform, opacity, elements);
+                }
+            }
+            Node::Text(node) => {
+                let transform = root_transform.pre_concat(node.abs_transform);
+                if let Some(layout) = text::layout_text(node, transform, opacity) {
+                    elements.texts.push(layout);
+                }
+            }
+            // 表格中的图片不保留
+            Node::Image(_) => {}
+        }
+    }
+}
+
+fn collect_path(
+    path: &usvg::Path,
+    root_transform: Transform,
+    opacity: f32,
+    elements: &mut Elements,
+) {
+    let transform = root_transform.pre_concat(path.abs_transform);
+    let Some(data) = path.data.as_ref().clone().transform(transform) else {
+        return;
+    };
+    let Some((bounds, geometry)) = geometry::classify(&data) else {
+        return;
+    };
+    let (sx, sy) = transform.get_scale();
+    let scale = (sx * sy).sqrt();
+
+    match geometry {
+        Geometry::Line { .. } => {
+            if path.stroke.is_none() {
+                return;
+            }
+            let line = style::stroke_xml(path.stroke.as_ref(), scale, opacity);
+            if bounds.height < TOLERANCE {
+                elements.rules.push(Rule {
+                    horizontal: true,
+                    position: bounds.y + bounds.height / 2.0,
+                    start: bounds.x,
+                    end: bounds.x + bounds.width,
+                    line,
+                });
+            } else if bounds.width < TOLERANCE {
+                elements.rules.push(Rule {
+                    horizontal: false,
+                    position: bounds.x + bounds.width / 2.0,
+                    start: bounds.y,
+                    end: bounds.y + bounds.height,
+                    line,
+                });
+            }
+        }
+        Geometry::Rect => {
+            let thickness = bounds.width.min(bounds.height);
+
+            // 细长的填充矩形常用作分隔线
+            if thickness <= RULE_THICKNESS && path.fill.is_some() {
+                let horizontal = bounds.width > bounds.height;
+                let line = format!(
+                    r#"<a:ln w="{}">{}</a:ln>"#,
+                    px_to_emu(thickness),
+                    style::fill_xml(path.fill.as_ref(), opacity)
+                );
+                elements.rules.push(if horizontal {
+                    Rule {
+                        horizontal,
+                        position: bounds.y + bounds.height / 2.0,
+                        start: bounds.x,
+                        end: bounds.x + bounds.width,
+                        line,
+                    }
+                } else {
+                    Rule {
+                        horizontal,
+                        position: bounds.x + bounds.width / 2.0,
+                        start: bounds.y,
+                        end: bounds.y + bounds.height,
+                        line,
+                    }
+                });
+                return;
+            }
+
+            if path.fill.is_some() {
+                elements.rects.push(FilledRect {
+                    bounds,
+                    fill: style::fill_xml(path.fill.as_ref(), opacity),
+                });
+            }
+
+            if path.stroke.is_some() {
+                let line = style::stroke_xml(path.stroke.as_ref(), scale, opacity);
+                let (left, top) = (bounds.x, bounds.y);
+                let (right, bottom) = (bounds.x + bounds.width, bounds.y + bounds.height);
+                for (horizontal, position, start, end) in [
+                    (true, top, left, right),
+                    (true, bottom, left, right),
+                    (false, left, top, bottom),
+                    (false, right, top, bottom),
+                ] {
+                    elements.rules.push(Rule {
+                        horizontal,
+                        position,
+                        start,
+                        end,
+                        line: line.clone(),
+                    });
+                }
+            }
+        }
+        // 其他图形不参与表格
+        _ => {}
+    }
+}
+
+/// 计算列与行的分隔位置
+///
+/// 分隔位置来自矩形边与网格线；只有一列（或一行）时按文本的分布补充分隔
+fn grid(elements: &Elements) -> Option<(Vec<f32>, Vec<f32>)> {
+    let mut xs = Vec::new();
+    let mut ys = Vec::new();
+
+    for rect in &elements.rects {
+        xs.extend([rect.bounds.x, rect.bounds.x + rect.bounds.width]);
+        ys.extend([rect.bounds.y, rect.bounds.y + rect.bounds.height]);
+    }
+    for rule in &elements.rules {
+        let (along, across) = if rule.horizontal {
+            (&mut xs, &mut ys)
+        } else {
+            (&mut ys, &mut xs)
+        };
+        across.push(rule.position);
+        along.extend([rule.start, rule.end]);
+    }
+
+    let mut columns = cluster(xs);
+    let mut rows = cluster(ys);
+    if columns.len() < 2 || rows.len() < 2 {
+        return None;
+    }
+
+    if columns.len() == 2 {
+        columns = split_by_text(&columns, elements, |bounds| (bounds.x, bounds.width));
+    }
+    if rows.len() == 2 {
+        rows = split_by_text(&rows, elements, |bounds| (bounds.y, bounds.height));
+    }
+
+    Some((columns, rows))
+}
+
+/// 排序并合并相近的位置
+fn cluster(mut values: Vec<f32>) -> Vec<f32> {
+    values.retain(|value| value.is_finite());
+    values.sort_by(|a, b| a.total_cmp(b));
+
+    let mut clusters: Vec<(f32, usize)> = Vec::new();
+    for value in values {
+        match clusters.last_mut() {
+            Some((sum, count)) if value - *sum / *count as f32 <= TOLERANCE => {
+                *sum += value;
+                *count += 1;
+            }
+            _ => clusters.push((value, 1)),
+        }
+    }
+
+    clusters
+        .into_iter()
+        .map(|(sum, count)| sum / count as f32)
+        .collect()
+}
+
+/// 在两条外边之间按文本分组补充分隔：互不重叠的文本区间之间取中点
+fn split_by_text(
+    edges: &[f32],
+    elements: &Elements,
+    axis: impl Fn(&Bounds) -> (f32, f32),
+) -> Vec<f32> {
+    let (first, last) = (edges[0], edges[edges.len() - 1]);
+
+    let mut spans: Vec<(f32, f32)> = elements
+        .texts
+        .iter()
+        .map(|layout| {
+            let (start, length) = axis(&layout.bounds);
+            (start, start + length)
+        })
+        .filter(|(start, end)| {
+            let center = (start + end) / 2.0;
+            center > first && center < last
+        })
+        .collect();
+    spans.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut groups: Vec<(f32, f32)> = Vec::new();
+    for (start, end) in spans {
+        match groups.last_mut() {
+            Some(group) if start < group.1 => group.1 = group.1.max(end),
+            _ => groups.push((start, end)),
+        }
+    }
+
+    let mut result = vec![first];
+    result.extend(groups.windows(2).map(|pair| (pair[0].1 + pair[1].0) / 2.0));
+    result.push(last);
+    result
+}
+
+/// 位置所在的区间序号
+fn locate(edges: &[f32], value: f32) -> Option<usize> {
+    edges
+        .windows(2)
+        .position(|edge| value >= edge[0] && value < edge[1])
+}
+
+fn cell_xml(cell: &Bounds, texts: &[&text::TextLayout], elements: &Elements) -> String {
+    let (left, top) = (cell.x, cell.y);
+    let (right, bottom) = (cell.x + cell.width, cell.y + cell.height);
+    let (center_x, center_y) = (left + cell.width / 2.0, top + cell.height / 2.0);
+
+    // 后绘制的矩形在上层
+    let fill = elements
+        .rects
+        .iter()
+        .rev()
+        .find(|rect| {
+            center_x >= rect.bounds.x
+                && center_x <= rect.bounds.x + rect.bounds.width
+                && center_y >= rect.bounds.y
+                && center_y <= rect.bounds.y + rect.bounds.height
+        })
+        .map(|rect| rect.fill.as_str())
+        .unwrap_or("<a:noFill/>");
+
+    let border = |tag: &str, horizontal: bool, position: f32, middle: f32| {
+        let line = elements
+            .rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                rule.horizontal == horizontal
+                    && (rule.position - position).abs() <= TOLERANCE
+                    && middle >= rule.start - TOLERANCE
+                    && middle <= rule.end + TOLERANCE
+            })
+            .map(|rule| rule.line.as_str())
+            .unwrap_or("<a:ln><a:noFill/></a:ln>");
+        format!(
+            "<{}{}</{}>",
+            tag,
+            &line["<a:ln".len()..line.len() - "</a:ln>".len()],
+            tag
+        )
+    };
+
+    let borders = [
+        border("a:lnL", false, left, center_y),
+        border("a:lnR", false, right, center_y),
+        border("a:lnT", true, top, center_x),
+        border("a:lnB", true, bottom, center_x),
+    ]
+    .concat();
+
+    // 边距与垂直对齐按首段文本在单元格内的位置还原
+    let (mut margin_left, mut margin_right, mut margin_top) = (0.0, 0.0, 0.0);
+    let mut anchor = "ctr";
+    if let Some(first) = texts.first() {
+        match first.align {
+            "l" => margin_left = (first.bounds.x - left).max(0.0),
+            "r" => margin_right = (right - first.bounds.x - first.bounds.width).max(0.0),
+            _ => {}
+        }
+        let text_center = first.bounds.y + first.bounds.height / 2.0;
+        if (text_center - center_y).abs() > cell.height * 0.2 {
+            anchor = "t";
+            margin_top = (first.bounds.y - top).max(0.0);
+        }
+    }
+
+    let paragraphs = if texts.is_empty() {
+        r#"<a:p><a:endParaRPr lang="zh-CN"/></a:p>"#.to_string()
+    } else {
+        texts
+            .iter()
+            .map(|layout| layout.paragraphs.as_str())
+            .collect()
+    };
+
+    format!(
+        r#"<a:tc><a:txBody><a:bodyPr/><a:lstStyle/>{}</a:txBody><a:tcPr marL="{}" marR="{}" marT="{}" marB="0" anchor="{}">{}{}</a:tcPr></a:tc>"#,
+        paragraphs,
+        px_to_emu(margin_left),
+        px_to_emu(margin_right),
+        px_to_emu(margin_top),
+        anchor,
+        borders,
+        fill
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::svg_to_shapes;
+    use super::*;
+
+    #[test]
+    fn test_extract_tables() {
+        let svg = r#"<svg><g data-table="true"><rect/></g><g id="scores" data-table=""/><g data-table="false"/></svg>"#;
+
+        let (rewritten, tables) = extract_tables(svg).expect("提取应成功");
+        assert!(rewritten.contains(r#"<g id="__svg_table_0"><rect/></g>"#));
+        assert!(rewritten.contains(r#"<g id="scores"/>"#));
+        assert!(!rewritten.contains("data-table"));
+        assert_eq!(tables.len(), 2);
+    }
+
+    #[test]
+    fn test_table_grid_to_tbl() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 400 200">
+            <g id="scores" data-table="">
+                <rect x="0" y="0" width="400" height="40" fill="#1A73E8"/>
+                <rect x="0" y="40" width="400" height="40" fill="#F1F3F4"/>
+                <line x1="0" y1="80" x2="400" y2="80" stroke="#DADCE0" stroke-width="1"/>
+                <rect x="0" y="80" width="400" height="40" fill="#FFFFFF"/>
+                <text x="10" y="26" font-size="16" font-weight="bold" fill="#FFFFFF">地区</text>
+                <text x="390" y="26" font-size="16" text-anchor="end" fill="#FFFFFF">销量</text>
+                <text x="10" y="66" font-size="14">华东</text>
+                <text x="390" y="66" font-size="14" text-anchor="end">1,280</text>
+                <text x="10" y="106" font-size="14">华南</text>
+                <text x="390" y="106" font-size="14" text-anchor="end">960</text>
+            </g>
+        </svg>"##;
+
+        let tree = svg_to_shapes(svg, 400, 200).expect("转换应成功");
+        let xml = tree.xml;
+        assert_eq!(tree.shape_ids, vec![2]);
+        assert!(xml.starts_with("<p:graphicFrame>"));
+        assert!(xml.contains(r#"name="scores""#));
+        assert_eq!(xml.matches("<a:gridCol ").count(), 2);
+        assert_eq!(xml.matches("<a:tr ").count(), 3);
+        assert!(xml.contains(r#"<a:tr h="381000">"#));
+        // 表头底色与加粗白字
+        assert!(xml.contains(r#"<a:solidFill><a:srgbClr val="1A73E8"/></a:solidFill></a:tcPr>"#));
+        assert!(xml.contains(r#"sz="1200" b="1""#));
+        assert!(xml.contains("<a:t>1,280</a:t>"));
+        // 第二行与第三行之间的分隔线
+        assert!(xml.contains(
+            r#"<a:lnB w="9525" cap="flat"><a:solidFill><a:srgbClr val="DADCE0"/></a:solidFill>"#
+        ));
+        assert!(xml.contains(r#"<a:tcPr marL="95250" marR="0""#));
+        assert!(!xml.contains("<p:sp>"));
+    }
+}
@@ -0,0 +1,336 @@
+// 文本框转换
+//
+// 将 SVG 的 <text>/<tspan> 转换为带 p:txBody 的文本框，保留字体、字号、
+// 字重、颜色、对齐方式与字间距，使文字在 PowerPoint 中可直接修改
+
+use super::geometry::Bounds;
+use super::style;
+use crate::escape_xml;
+use usvg::{FontStyle, Text, TextAnchor, TextChunk, TextSpan, Transform, Visibility};
+
+/// 像素转换为百分之一磅（96 DPI 下 1px = 0.75pt）
+pub fn px_to_centipoints(px: f32) -> i32 {
+    (px * 75.0).round() as i32
+}
+
+/// 首行基线到行顶的距离（相对字号）
+const ASCENT_RATIO: f32 = 0.88;
+/// 未指定行距时的默认行高（相对字号）
+const LINE_HEIGHT_RATIO: f32 = 1.2;
+
+/// 文本框转换结果
+pub struct TextBox {
+    /// `p:sp` XML
+    pub xml: String,
+    /// 文本框在幻灯片坐标系（像素）中的位置
+    pub bounds: Bounds,
+    /// 首行字号（幻灯片像素）
+    pub font_size: f32,
+}
+
+/// 排版后的文本：位置与段落，可放入文本框或表格单元格
+pub struct TextLayout {
+    /// 文本在幻灯片坐标系（像素）中的估算范围
+    pub bounds: Bounds,
+    /// 首行的水平对齐（`l` / `ctr` / `r`）
+    pub align: &'static str,
+    /// `a:p` 段落 XML
+    pub paragraphs: String,
+    /// 首行字号（幻灯片像素）
+    pub font_size: f32,
+}
+
+/// 单行文本（对应一个 text chunk）
+struct Line<'a> {
+    chunk: &'a TextChunk,
+    /// 基线位置（SVG 用户坐标）
+    x: f32,
+    y: f32,
+}
+
+/// 将 `<text>` 元素转换为文本框
+///
+/// `transform` 为 SVG 用户坐标到幻灯片像素坐标的完整变换；文本路径（textPath）
+/// 与竖排文本按普通横排处理
+pub fn convert_text(text: &Text, transform: Transform, opacity: f32, id: u32) -> Option<TextBox> {
+    let layout = layout_text(text, transform, opacity)?;
+
+    let name = super::shape_name(&text.id, "TextBox", id);
+
+    let xml = format!(
+        r#"<p:sp><p:nvSpPr><p:cNvPr id="{}" name="{}"/><p:cNvSpPr txBox="1"/><p:nvPr/></p:nvSpPr><p:spPr><a:xfrm>{}</a:xfrm><a:prstGeom prst="rect"><a:avLst/></a:prstGeom><a:noFill/></p:spPr><p:txBody><a:bodyPr wrap="none" lIns="0" tIns="0" rIns="0" bIns="0" rtlCol="0" anchor="t"><a:spAutoFit/></a:bodyPr><a:lstStyle/>{}</p:txBody></p:sp>"#,
+        id,
+        escape_xml(&name),
+        layout.bounds.to_xfrm_body(),
+        layout.paragraphs
+    );
+
+    Some(TextBox {
+        xml,
+        bounds: layout.bounds,
+        font_size: layout.font_size,
+    })
+}
+
+/// 计算文本范围并生成段落，没有可见文字时返回 None
+pub fn layout_text(text: &Text, transform: Transform, opacity: f32) -> Option<TextLayout> {
+    let lines = layout_lines(text);
+    let first = lines.first()?;
+    if lines
+        .iter()
+        .all(|line| visible_spans(line.chunk).next().is_none())
+    {
+        return None;
+    }
+
+    let (sx, sy) = transform.get_scale();
+    let scale = (sx * sy).sqrt();
+    let font_size = first_font_size(first.chunk) * scale;
+
+    // 相邻行的基线间距，作为段落行距
+    let line_spacing = if lines.len() > 1 {
+        ((lines[lines.len() - 1].y - first.y) / (lines.len() - 1) as f32 * scale).abs()
+    } else {
+        font_size * LINE_HEIGHT_RATIO
+    };
+    let line_spacing = if line_spacing > 0.0 {
+        line_spacing
+    } else {
+        font_size * LINE_HEIGHT_RATIO
+    };
+
+    let width = lines
+        .iter()
+        .map(|line| estimate_width(line.chunk) * scale)
+        .fold(0.0_f32, f32::max)
+        .max(1.0);
+    let height = line_spacing * (lines.len() - 1) as f32 + font_size * LINE_HEIGHT_RATIO;
+
+    // 文本锚点决定文本框相对首行锚点的水平位置
+    let mut anchor = usvg::tiny_skia_path::Point::from_xy(first.x, first.y);
+    transform.map_point(&mut anchor);
+    let left = match first.chunk.anchor {
+        TextAnchor::Start => anchor.x,
+        TextAnchor::Middle => anchor.x - width / 2.0,
+        TextAnchor::End => anchor.x - width,
+    };
+    let bounds = Bounds {
+        x: left,
+        y: anchor.y - font_size * ASCENT_RATIO,
+        width,
+        height,
+    };
+
+    let mut paragraphs = String::new();
+    for line in &lines {
+        paragraphs.push_str(&paragraph_xml(line.chunk, scale, line_spacing, opacity));
+    }
+
+    Some(TextLayout {
+        bounds,
+        align: alignment(first.chunk.anchor),
+        paragraphs,
+        font_size,
+    })
+}
+
+/// 计算每个 text chunk 的基线位置，未指定 x/y 的 chunk 沿用上一行并叠加 dx/dy
+fn layout_lines(text: &Text) -> Vec<Line<'_>> {
+    let mut lines = Vec::with_capacity(text.chunks.len());
+    let (mut x, mut y) = (0.0, 0.0);
+    let mut char_offset = 0;
+
+    for chunk in &text.chunks {
+        let dx = text.dx.get(char_offset).copied().unwrap_or(0.0);
+        let dy = text.dy.get(char_offset).copied().unwrap_or(0.0);
+
+        x = chunk.x.unwrap_or(x) + dx;
+        y = chunk.y.unwrap_or(y) + dy;
+        char_offset += chunk.text.chars().count();
+
+        lines.push(Line { chunk, x, y });
+    }
+
+    lines
+}
+
+fn visible_spans(chunk: &TextChunk) -> impl Iterator<Item = &TextSpan> {
+    chunk
+        .spans
+        .iter()
+        .filter(|span| span.visibility == Visibility::Visible)
+}
+
+fn first_font_size(chunk: &TextChunk) -> f32 {
+    chunk
+        .spans
+        .first()
+        .map(|span| span.font_size.get())
+        .unwrap_or(16.0)
+}
+
+/// 按字符类别估算行宽（像素，SVG 用户坐标）
+///
+/// 文本框使用 spAutoFit，宽度只用于按锚点定位，PowerPoint 打开后会按实际字形调整
+fn estimate_width(chunk: &TextChunk) -> f32 {
+    chunk
+        .spans
+        .iter()
+        .map(|span| {
+            let text = &chunk.text[span.start..span.end];
+            let size = span.font_size.get();
+            text.chars()
+                .map(|c| char_width_ratio(c) * size + span.letter_spacing)
+                .sum::<f32>()
+        })
+        .sum()
+}
+
+fn char_width_ratio(c: char) -> f32 {
+    if c as u32 >= 0x2E80 {
+        1.0 // 中日韩文字与全角符号
+    } else if c.is_ascii_uppercase() {
+        0.65
+    } else if c == ' ' {
+        0.3
+    } else {
+        0.55
+    }
+}
+
+fn alignment(anchor: TextAnchor) -> &'static str {
+    match anchor {
+        TextAnchor::Start => "l",
+        TextAnchor::Middle => "ctr",
+        TextAnchor::End => "r",
+    }
+}
+
+fn paragraph_xml(chunk: &TextChunk, scale: f32, line_spacing: f32, opacity: f32) -> String {
+    let align = alignment(chunk.anchor);
+
+    let mut runs = String::new();
+    for span in visible_spans(chunk) {
+        let text = &chunk.text[span.start..span.end];
+        if text.is_empty() {
+            continue;
+        }
+        runs.push_str(&format!(
+            "<a:r>{}<a:t>{}</a:t></a:r>",
+            run_properties(span, scale, opacity),
+            escape_xml(text)
+        ));
+    }
+
+    format!(
+        r#"<a:p><a:pPr algn="{}"><a:lnSpc><a:spcPts val="{}"/></a:lnSpc></a:pPr>{}</a:p>"#,
+        align,
+        px_to_centipoints(line_spacing),
+        runs
+    )
+}
+
+fn run_properties(span: &TextSpan, scale: f32, opacity: f32) -> String {
+    let mut attrs = format!(
+        r#" lang="zh-CN" sz="{}""#,
+        px_to_centipoints(span.font_size.get() * scale)
+    );
+    if span.font.weight >= 600 {
+        attrs.push_str(r#" b="1""#);
+    }
+    if matches!(span.font.style, FontStyle::Italic | FontStyle::Oblique) {
+        attrs.push_str(r#" i="1""#);
+    }
+    if span.decoration.underline.is_some() {
+        attrs.push_str(r#" u="sng""#);
+    }
+    if span.decoration.line_through.is_some() {
+        attrs.push_str(r#" strike="sngStrike""#);
+    }
+    if span.letter_spacing != 0.0 {
+        attrs.push_str(&format!(
+            r#" spc="{}""#,
+            px_to_centipoints(span.letter_spacing * scale)
+        ));
+    }
+
+    // 文本没有填充时（仅描边）退化为无填充，与形状保持一致
+    let fill = style::fill_xml(span.fill.as_ref(), opacity);
+
+    let fonts = match font_family(span) {
+        Some(family) => {
+            let family = escape_xml(family);
+            format!(
+                r#"<a:latin typeface="{0}"/><a:ea typeface="{0}"/><a:cs typeface="{0}"/>"#,
+                family
+            )
+        }
+        None => String::new(),
+    };
+
+    format!("<a:rPr{}>{}{}</a:rPr>", attrs, fill, fonts)
+}
+
+/// 首个非通用字体族名称，通用族（如 sans-serif）交给主题字体
+fn font_family(span: &TextSpan) -> Option<&str> {
+    const GENERIC: &[&str] = &[
+        "serif",
+        "sans-serif",
+        "monospace",
+        "cursive",
+        "fantasy",
+        "system-ui",
+    ];
+
+    span.font
+        .families
+        .iter()
+        .map(|family| family.trim().trim_matches(|c| c == '\'' || c == '"'))
+        .find(|family| !family.is_empty() && !GENERIC.contains(&family.to_lowercase().as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::svg_to_shapes;
+    use super::*;
+
+    #[test]
+    fn test_px_to_centipoints() {
+        assert_eq!(px_to_centipoints(16.0), 1200);
+        assert_eq!(px_to_centipoints(32.0), 2400);
+    }
+
+    #[test]
+    fn test_text_to_text_box() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720">
+            <text id="title" x="640" y="100" text-anchor="middle" font-family="Microsoft YaHei, sans-serif" font-size="48" font-weight="bold" fill="#1A73E8" letter-spacing="2">季度<tspan font-style="italic" fill="#FF0000">报告</tspan></text>
+        </svg>"##;
+
+        let tree = svg_to_shapes(svg, 1280, 720).expect("转换应成功");
+        assert_eq!(tree.shape_ids, vec![2]);
+
+        let xml = tree.xml;
+        // 唯一的文本位于上半页，标记为标题占位符
+        assert!(xml.contains(r#"<p:nvPr><p:ph type="title"/></p:nvPr>"#));
+        assert!(tree.has_title);
+        assert!(xml.contains(r#"name="title""#));
+        assert!(xml.contains(r#"<a:pPr algn="ctr">"#));
+        assert!(xml.contains(r#"sz="3600" b="1" spc="150""#));
+        assert!(xml.contains(r#"<a:latin typeface="Microsoft YaHei"/>"#));
+        assert!(xml.contains(r#"<a:srgbClr val="1A73E8"/>"#));
+        assert!(xml.contains(r#" i="1""#));
+        assert!(xml.contains("<a:t>季度</a:t>"));
+        assert!(xml.contains("<a:t>报告</a:t>"));
+    }
+
+    #[test]
+    fn test_tspan_lines_become_paragraphs() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720">
+            <text x="100" y="200" font-size="20" text-anchor="end"><tspan x="100" y="200">第一行</tspan><tspan x="100" y="240">第二行</tspan></text>
+        </svg>"##;
+
+        let xml = svg_to_shapes(svg, 1280, 720).expect("转换应成功").xml;
+        assert_eq!(xml.matches("<a:p>").count(), 2);
+        assert!(xml.contains(r#"<a:pPr algn="r"><a:lnSpc><a:spcPts val="3000"/></a:lnSpc>"#));
+    }
+}
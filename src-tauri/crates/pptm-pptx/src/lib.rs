@@ -1,10 +1,31 @@
 // PPTX 导出模块
 // 支持双后端：pptxgen_sidecar (Node.js) 和 native_ooxml (Rust)
 
+pub mod accessibility;
+pub mod animation;
 pub mod backend;
-
+pub mod chart;
+pub mod deck;
+pub mod drawingml;
+pub mod media;
+pub mod notes;
+pub mod package;
+pub mod reader;
+pub mod registry;
+pub mod section;
+pub mod sidecar;
+pub mod theme;
+pub mod validate;
+
+use animation::{AnimationSpec, TransitionSpec};
+use serde::Serialize;
+use std::future::Future;
 use std::path::Path;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use theme::Theme;
 use thiserror::Error;
+use tokio_util::sync::CancellationToken;
 
 /// PPTX 导出错误
 #[derive(Debug, Error)]
@@ -26,6 +47,15 @@ pub enum PptxError {
 
     #[error("Zip 错误: {0}")]
     Zip(#[from] zip::result::ZipError),
+
+    #[error("包结构错误: {0}")]
+    Package(String),
+
+    #[error("幻灯片范围错误: {0}")]
+    SlideRange(String),
+
+    #[error("导出已取消")]
+    Cancelled,
 }
 
 pub type Result<T> = std::result::Result<T, PptxError>;
@@ -50,6 +80,14 @@ pub struct Slide {
     pub content: SlideContent,
     /// 演讲备注（Markdown 格式）
     pub notes: Option<String>,
+    /// 单页切换效果（覆盖全局设置）
+    pub transition: Option<TransitionSpec>,
+    /// 单页入场动画（覆盖全局设置）
+    pub animation: Option<AnimationSpec>,
+    /// 所属分节名称（None 表示不分节）
+    pub section: Option<String>,
+    /// 回退为 PNG 前的原始 SVG（图片已内嵌），供位图与文档导出器按目标分辨率重新渲染
+    pub source_svg: Option<String>,
 }
 
 /// PPTX 导出配置
@@ -63,6 +101,14 @@ pub struct PptxConfig {
     pub enable_transitions: bool,
     /// 切换效果类型（如 "fade", "push" 等）
     pub transition_type: Option<String>,
+    /// 切换持续时间（秒）
+    pub transition_duration: f32,
+    /// 自动翻页间隔（秒），None 表示手动翻页
+    pub auto_advance: Option<f32>,
+    /// 全局入场动画（None 表示不添加）
+    pub animation: Option<AnimationSpec>,
+    /// 演示文稿主题（None 表示使用 Office 默认主题）
+    pub theme: Option<Theme>,
 }
 
 impl Default for PptxConfig {
@@ -72,10 +118,69 @@ impl Default for PptxConfig {
             height: 720,
             enable_transitions: true,
             transition_type: Some("fade".to_string()),
+            transition_duration: 0.5,
+            auto_advance: None,
+            animation: None,
+            theme: None,
+        }
+    }
+}
+
+impl PptxConfig {
+    /// 解析幻灯片最终使用的切换效果（单页设置优先于全局设置）
+    pub fn slide_transition(&self, slide: &Slide) -> Option<TransitionSpec> {
+        if !self.enable_transitions {
+            return None;
         }
+
+        slide.transition.clone().or_else(|| {
+            self.transition_type.as_ref().map(|effect| TransitionSpec {
+                effect: effect.clone(),
+                duration: self.transition_duration,
+                advance_after: self.auto_advance,
+            })
+        })
     }
+
+    /// 解析幻灯片最终使用的入场动画（单页设置优先于全局设置）
+    pub fn slide_animation(&self, slide: &Slide) -> Option<AnimationSpec> {
+        slide.animation.clone().or_else(|| self.animation.clone())
+    }
+}
+
+/// 导出结果摘要
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSummary {
+    /// 幻灯片数量
+    pub slide_count: usize,
+    /// 去重后写入的媒体文件数
+    pub media_count: usize,
+    /// 媒体去重节省的字节数（未压缩）
+    pub media_bytes_saved: u64,
+    /// 无障碍检查结果
+    pub accessibility: accessibility::AccessibilityReport,
+}
+
+/// 导出进度上报接口（供 Tauri 层转发为任务事件）
+pub trait ProgressSink: Send + Sync {
+    fn report_progress(&self, current: usize, total: usize, message: String);
+    fn log(&self, level: &str, message: String);
+}
+
+/// 忽略所有进度的空实现
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn report_progress(&self, _current: usize, _total: usize, _message: String) {}
+
+    fn log(&self, _level: &str, _message: String) {}
 }
 
+/// 后端导出返回的 future
+pub type ExportFuture<'a> = Pin<Box<dyn Future<Output = Result<ExportSummary>> + Send + 'a>>;
+
 /// PPTX 后端 trait
 ///
 /// 定义统一的 PPTX 导出接口，支持不同的实现后端
@@ -86,7 +191,16 @@ pub trait PptxBackend: Send + Sync {
     /// - `slides`: 幻灯片列表
     /// - `output_path`: 输出文件路径
     /// - `config`: 导出配置
-    fn export(&self, slides: &[Slide], output_path: &Path, config: &PptxConfig) -> Result<()>;
+    /// - `progress`: 逐页进度上报
+    /// - `cancel`: 取消令牌，取消后返回 [`PptxError::Cancelled`]
+    fn export<'a>(
+        &'a self,
+        slides: &'a [Slide],
+        output_path: &'a Path,
+        config: &'a PptxConfig,
+        progress: &'a dyn ProgressSink,
+        cancel: &'a CancellationToken,
+    ) -> ExportFuture<'a>;
 
     /// 获取后端名称
     fn name(&self) -> &str;
@@ -95,28 +209,108 @@ pub trait PptxBackend: Send + Sync {
     fn is_available(&self) -> bool;
 }
 
+/// 逐页导出检查点：响应取消、上报进度并让出执行权
+pub(crate) async fn slide_checkpoint(
+    slides: &[Slide],
+    index: usize,
+    progress: &dyn ProgressSink,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    if cancel.is_cancelled() {
+        return Err(PptxError::Cancelled);
+    }
+
+    progress.report_progress(
+        index + 1,
+        slides.len(),
+        format!(
+            "正在导出第 {}/{} 页: {}",
+            index + 1,
+            slides.len(),
+            slides[index].title
+        ),
+    );
+    tokio::task::yield_now().await;
+    Ok(())
+}
+
+/// 在阻塞线程池中执行渲染、编码等 CPU 密集的工作
+///
+/// 导出任务运行在异步工作线程上，长时间占用会阻塞进度事件与取消
+pub(crate) async fn run_blocking<T, F>(work: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| PptxError::Backend(format!("渲染任务异常结束: {}", e)))?
+}
+
+/// 文本渲染使用的系统字体库（首次使用时加载）
+fn font_database() -> &'static usvg::fontdb::Database {
+    static FONT_DATABASE: OnceLock<usvg::fontdb::Database> = OnceLock::new();
+
+    FONT_DATABASE.get_or_init(|| {
+        let mut database = usvg::fontdb::Database::new();
+        database.load_system_fonts();
+        database
+    })
+}
+
 /// SVG 转 PNG 工具
 ///
-/// 使用 resvg 将 SVG 转换为 PNG，用于不兼容的 SVG 内容
+/// 使用 resvg 将 SVG 转换为 PNG，用于不兼容的 SVG 内容以及原生 SVG 的后备图。
+/// SVG 会被缩放到 `width` x `height`，文本使用系统字体转换为路径后渲染
 pub fn svg_to_png(svg_content: &str, width: u32, height: u32) -> Result<Vec<u8>> {
-    use usvg::TreeParsing;
+    render_svg(svg_content, width, height)?
+        .encode_png()
+        .map_err(|e| PptxError::PngConversion(e.to_string()))
+}
+
+/// 使用 resvg 将 SVG 渲染为 `width` x `height` 的位图（预乘 alpha 的 RGBA）
+pub(crate) fn render_svg(svg_content: &str, width: u32, height: u32) -> Result<tiny_skia::Pixmap> {
+    use usvg::{TreeParsing, TreePostProc};
 
     // 解析 SVG
     let opt = usvg::Options::default();
-    let tree =
+    let mut tree =
         usvg::Tree::from_str(svg_content, &opt).map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    tree.postprocess(usvg::PostProcessingSteps::default(), font_database());
 
     // 创建 pixmap
     let mut pixmap = tiny_skia::Pixmap::new(width, height)
         .ok_or_else(|| PptxError::PngConversion("无法创建 pixmap".to_string()))?;
 
     // 渲染 SVG
-    resvg::render(&tree, usvg::Transform::default(), &mut pixmap.as_mut());
+    let transform = usvg::Transform::from_scale(
+        width as f32 / tree.size.width(),
+        height as f32 / tree.size.height(),
+    );
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
 
-    // 编码为 PNG
-    pixmap
-        .encode_png()
-        .map_err(|e| PptxError::PngConversion(e.to_string()))
+    Ok(pixmap)
+}
+
+/// 将预乘 alpha 的位图合成到白色背景上，返回 RGB 像素（PDF、JPEG 不支持透明）
+pub(crate) fn pixmap_to_rgb(pixmap: &tiny_skia::Pixmap) -> Vec<u8> {
+    let mut rgb = Vec::with_capacity(pixmap.data().len() / 4 * 3);
+    for pixel in pixmap.data().chunks_exact(4) {
+        // 预乘 alpha：与白色合成即 c + (255 - a)
+        let background = 255 - pixel[3];
+        rgb.extend(pixel[..3].iter().map(|&c| c.saturating_add(background)));
+    }
+    rgb
+}
+
+/// 幻灯片演讲备注的候选文件名（按读取优先顺序）
+pub(crate) fn notes_file_names(slide_number: usize, slide_title: &str) -> [String; 4] {
+    [
+        format!("{:02}_{}.md", slide_number, slide_title),
+        format!("{}_{}.md", slide_number, slide_title),
+        format!("{:02}.md", slide_number),
+        format!("{}.md", slide_number),
+    ]
 }
 
 /// 读取演讲备注
@@ -128,15 +322,8 @@ pub fn read_notes(
     slide_title: &str,
 ) -> Result<Option<String>> {
     // 尝试多种文件名格式
-    let patterns = vec![
-        format!("{:02}_{}.md", slide_number, slide_title),
-        format!("{}_{}.md", slide_number, slide_title),
-        format!("{:02}.md", slide_number),
-        format!("{}.md", slide_number),
-    ];
-
-    for pattern in patterns {
-        let path = notes_dir.join(&pattern);
+    for name in notes_file_names(slide_number, slide_title) {
+        let path = notes_dir.join(&name);
         if path.exists() {
             let content = std::fs::read_to_string(&path)?;
             return Ok(Some(content));
@@ -148,7 +335,8 @@ pub fn read_notes(
 
 /// 加载项目的所有幻灯片
 ///
-/// 从 svg_final 目录加载 SVG 文件，从 notes 目录加载演讲备注
+/// 从 svg_final 目录加载 SVG 文件，从 notes 目录加载演讲备注，
+/// 并按项目元数据或文件名前缀设置分节
 pub fn load_slides(project_path: &Path, config: &PptxConfig) -> Result<Vec<Slide>> {
     let svg_dir = project_path.join("svg_final");
     let notes_dir = project_path.join("notes");
@@ -161,23 +349,8 @@ pub fn load_slides(project_path: &Path, config: &PptxConfig) -> Result<Vec<Slide
     }
 
     let mut slides = Vec::new();
-    let mut entries: Vec<_> = std::fs::read_dir(&svg_dir)?
-        .filter_map(|e| e.ok())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|s| s.to_str())
-                .map(|s| s.eq_ignore_ascii_case("svg"))
-                .unwrap_or(false)
-        })
-        .collect();
-
-    // 按文件名排序
-    entries.sort_by_key(|e| e.file_name());
-
-    for (index, entry) in entries.iter().enumerate() {
-        let path = entry.path();
-        let svg_content = std::fs::read_to_string(&path)?;
+    for (index, path) in list_svg_files(&svg_dir)?.iter().enumerate() {
+        let svg_content = std::fs::read_to_string(path)?;
 
         // 提取标题（从文件名）
         let title = path
@@ -195,13 +368,17 @@ pub fn load_slides(project_path: &Path, config: &PptxConfig) -> Result<Vec<Slide
             None
         };
 
+        // 读取 SVG 根元素上声明的单页切换效果与入场动画
+        let (transition, animation) = animation::read_slide_effects(&svg_content);
+
         // 尝试使用 SVG，如果失败则转换为 PNG
-        let content = match validate_svg(&svg_content) {
-            Ok(_) => SlideContent::Svg(svg_content),
+        let (content, source_svg) = match validate_svg(&svg_content) {
+            Ok(_) => (SlideContent::Svg(svg_content), None),
             Err(_) => {
-                // SVG 不兼容，转换为 PNG
+                // SVG 不兼容，转换为 PNG（相对路径图片先内嵌，否则渲染时缺失）
+                let svg_content = media::inline_images(&svg_content, &svg_dir)?;
                 let png_data = svg_to_png(&svg_content, config.width, config.height)?;
-                SlideContent::Png(png_data)
+                (SlideContent::Png(png_data), Some(svg_content))
             }
         };
 
@@ -210,12 +387,36 @@ pub fn load_slides(project_path: &Path, config: &PptxConfig) -> Result<Vec<Slide
             title,
             content,
             notes,
+            transition,
+            animation,
+            section: None,
+            source_svg,
         });
     }
 
+    section::assign_sections(&mut slides, &section::load_manifest_sections(project_path));
+
     Ok(slides)
 }
 
+/// 列出目录中的 SVG 文件，按文件名排序（即幻灯片顺序）
+pub fn list_svg_files(svg_dir: &Path) -> Result<Vec<std::path::PathBuf>> {
+    let mut paths: Vec<_> = std::fs::read_dir(svg_dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|s| s.to_str())
+                .map(|s| s.eq_ignore_ascii_case("svg"))
+                .unwrap_or(false)
+        })
+        .collect();
+
+    // 按文件名排序
+    paths.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+    Ok(paths)
+}
+
 /// 验证 SVG 是否兼容 PPTX
 ///
 /// 检查 SVG 是否包含 PPTX 不支持的特性
@@ -244,3 +445,12 @@ fn validate_svg(svg_content: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// 转义 XML 文本与属性值中的特殊字符
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,384 @@
+// 媒体资源去重与内嵌
+// 按内容哈希（SHA-256）登记图片，相同内容只写入一个 ppt/media 部件；
+// SVG 中以相对路径引用的本地图片可内嵌为 data URI，脱离项目目录后仍能显示
+
+use crate::package::OoxmlPackage;
+use crate::{PptxError, Result};
+use base64::Engine;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::{FileOptions, ZipWriter};
+use zip::CompressionMethod;
+
+/// 媒体部件所在目录
+const MEDIA_DIR: &str = "ppt/media/";
+
+/// 已登记的媒体资源
+#[derive(Debug, Clone)]
+pub struct MediaAsset {
+    /// 部件名，例如 `ppt/media/image1.png`
+    pub part_name: String,
+    /// 内容类型
+    pub content_type: String,
+    /// 文件内容
+    pub data: Vec<u8>,
+}
+
+impl MediaAsset {
+    /// 从幻灯片部件引用该资源的相对路径
+    pub fn slide_target(&self) -> String {
+        format!("../media/{}", self.file_name())
+    }
+
+    /// 文件名
+    pub fn file_name(&self) -> &str {
+        self.part_name.trim_start_matches(MEDIA_DIR)
+    }
+}
+
+/// 去重统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MediaStats {
+    /// 去重后的媒体文件数
+    pub media_count: usize,
+    /// 节省的字节数（未压缩）
+    pub bytes_saved: u64,
+}
+
+/// 媒体资源库
+#[derive(Debug, Default)]
+pub struct MediaStore {
+    assets: Vec<MediaAsset>,
+    by_hash: HashMap<[u8; 32], usize>,
+    bytes_saved: u64,
+}
+
+impl MediaStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记图片，返回对应的资源；内容相同的图片返回已登记的资源
+    pub fn insert(&mut self, data: &[u8], extension: &str, content_type: &str) -> &MediaAsset {
+        let hash: [u8; 32] = Sha256::digest(data).into();
+
+        let index = match self.by_hash.get(&hash) {
+            Some(&index) => {
+                self.bytes_saved += data.len() as u64;
+                index
+            }
+            None => {
+                let index = self.assets.len();
+                self.assets.push(MediaAsset {
+                    part_name: format!("{}image{}.{}", MEDIA_DIR, index + 1, extension),
+                    content_type: content_type.to_string(),
+                    data: data.to_vec(),
+                });
+                self.by_hash.insert(hash, index);
+                index
+            }
+        };
+
+        &self.assets[index]
+    }
+
+    /// 已登记的唯一资源
+    pub fn assets(&self) -> &[MediaAsset] {
+        &self.assets
+    }
+
+    /// 去重统计
+    pub fn stats(&self) -> MediaStats {
+        MediaStats {
+            media_count: self.assets.len(),
+            bytes_saved: self.bytes_saved,
+        }
+    }
+
+    /// 将全部资源写入包
+    pub fn write_into(self, package: &mut OoxmlPackage) {
+        for asset in self.assets {
+            package.add_part(asset.part_name, &asset.content_type, asset.data);
+        }
+    }
+}
+
+/// 对已写入的 PPTX 做媒体去重
+///
+/// 用于无法在生成过程中去重的后端（如 PptxGenJS）：重复的 `ppt/media` 部件被删除，
+/// 所有关系改为指向保留的部件
+pub fn deduplicate_package(path: &Path) -> Result<MediaStats> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+
+    let mut entries: Vec<(String, Vec<u8>)> = Vec::with_capacity(archive.len());
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        if file.is_dir() {
+            continue;
+        }
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        entries.push((file.name().to_string(), data));
+    }
+    drop(archive);
+
+    // 重复文件名 -> 保留的文件名
+    let mut by_hash: HashMap<[u8; 32], String> = HashMap::new();
+    let mut replacements: Vec<(String, String)> = Vec::new();
+    let mut bytes_saved = 0u64;
+
+    for (name, data) in &entries {
+        let Some(file_name) = name.strip_prefix(MEDIA_DIR) else {
+            continue;
+        };
+        let hash: [u8; 32] = Sha256::digest(data).into();
+        match by_hash.get(&hash) {
+            Some(kept) => {
+                replacements.push((file_name.to_string(), kept.clone()));
+                bytes_saved += data.len() as u64;
+            }
+            None => {
+                by_hash.insert(hash, file_name.to_string());
+            }
+        }
+    }
+
+    let stats = MediaStats {
+        media_count: by_hash.len(),
+        bytes_saved,
+    };
+    if replacements.is_empty() {
+        return Ok(stats);
+    }
+
+    let removed: Vec<String> = replacements
+        .iter()
+        .map(|(duplicate, _)| format!("{}{}", MEDIA_DIR, duplicate))
+        .collect();
+
+    let temp_path = path.with_extension("dedup.tmp");
+    {
+        let mut zip = ZipWriter::new(File::create(&temp_path)?);
+        let options = FileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+
+        for (name, data) in entries {
+            if removed.contains(&name) {
+                continue;
+            }
+
+            let data = if name.ends_with(".rels") {
+                let mut xml = String::from_utf8_lossy(&data).into_owned();
+                for (duplicate, kept) in &replacements {
+                    // 以引号结尾匹配完整文件名，避免 image1.png 误匹配 image11.png
+                    xml = xml.replace(
+                        &format!("media/{}\"", duplicate),
+                        &format!("media/{}\"", kept),
+                    );
+                }
+                xml.into_bytes()
+            } else if name == "[Content_Types].xml" {
+                let mut xml = String::from_utf8_lossy(&data).into_owned();
+                for part in &removed {
+                    xml = remove_override(&xml, part);
+                }
+                xml.into_bytes()
+            } else {
+                data
+            };
+
+            zip.start_file(name, options)?;
+            zip.write_all(&data)?;
+        }
+        zip.finish()?;
+    }
+
+    std::fs::rename(&temp_path, path)?;
+    Ok(stats)
+}
+
+/// 删除指定部件的 Override 声明
+fn remove_override(xml: &str, part_name: &str) -> String {
+    let needle = format!("PartName=\"/{}\"", part_name);
+    let Some(position) = xml.find(&needle) else {
+        return xml.to_string();
+    };
+
+    let start = xml[..position].rfind('<').unwrap_or(position);
+    let end = xml[position..]
+        .find("/>")
+        .map(|offset| position + offset + 2)
+        .unwrap_or(xml.len());
+
+    format!("{}{}", &xml[..start], &xml[end..])
+}
+
+/// 将 `<image>` 引用的本地图片内嵌为 data URI（找不到的文件保持原样）
+pub fn inline_images(svg: &str, base_dir: &Path) -> Result<String> {
+    let mut reader = Reader::from_str(svg);
+    let mut writer = Writer::new(Vec::with_capacity(svg.len()));
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+        let event = match event {
+            Event::Eof => break,
+            Event::Start(start) if start.local_name().as_ref() == b"image" => {
+                Event::Start(inline_href(start, base_dir))
+            }
+            Event::Empty(start) if start.local_name().as_ref() == b"image" => {
+                Event::Empty(inline_href(start, base_dir))
+            }
+            other => other,
+        };
+        writer
+            .write_event(event)
+            .map_err(|e| PptxError::SvgParse(e.to_string()))?;
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|e| PptxError::SvgParse(e.to_string()))
+}
+
+fn inline_href<'a>(start: BytesStart<'a>, base_dir: &Path) -> BytesStart<'a> {
+    let href = start.attributes().flatten().find_map(|attr| {
+        matches!(attr.key.as_ref(), b"href" | b"xlink:href")
+            .then(|| attr.unescape_value().ok().map(|value| value.into_owned()))
+            .flatten()
+    });
+    let Some(data_uri) = href
+        .as_deref()
+        .and_then(|href| image_data_uri(href, base_dir))
+    else {
+        return start;
+    };
+
+    let mut image = BytesStart::new(String::from_utf8_lossy(start.name().as_ref()).into_owned());
+    for attr in start.attributes().flatten() {
+        if !matches!(attr.key.as_ref(), b"href" | b"xlink:href") {
+            image.push_attribute(attr);
+        }
+    }
+    image.push_attribute(("href", data_uri.as_str()));
+    image
+}
+
+/// 读取本地图片并编码为 data URI；data URI 与网络地址返回 None
+fn image_data_uri(href: &str, base_dir: &Path) -> Option<String> {
+    if href.starts_with("data:") || href.contains("://") {
+        return None;
+    }
+
+    let path = base_dir.join(href);
+    let mime = match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("svg") => "image/svg+xml",
+        _ => return None,
+    };
+    let data = std::fs::read(path).ok()?;
+
+    Some(format!(
+        "data:{};base64,{}",
+        mime,
+        base64::engine::general_purpose::STANDARD.encode(data)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::content_types;
+
+    #[test]
+    fn test_media_store_deduplicates() {
+        let mut store = MediaStore::new();
+        let logo = vec![1u8; 100];
+
+        let first = store
+            .insert(&logo, "png", content_types::PNG)
+            .part_name
+            .clone();
+        let second = store
+            .insert(&logo, "png", content_types::PNG)
+            .part_name
+            .clone();
+        let other = store
+            .insert(&[2u8; 10], "png", content_types::PNG)
+            .part_name
+            .clone();
+
+        assert_eq!(first, "ppt/media/image1.png");
+        assert_eq!(first, second);
+        assert_eq!(other, "ppt/media/image2.png");
+        assert_eq!(
+            store.stats(),
+            MediaStats {
+                media_count: 2,
+                bytes_saved: 100
+            }
+        );
+    }
+
+    #[test]
+    fn test_deduplicate_package() {
+        let mut package = OoxmlPackage::new();
+        for slide in 1..=2 {
+            let part = format!("ppt/slides/slide{}.xml", slide);
+            package.add_part(part.as_str(), content_types::SLIDE, "<p:sld/>");
+            package.add_part(
+                format!("ppt/media/image{}.png", slide),
+                content_types::PNG,
+                vec![7u8; 64],
+            );
+            package.add_relationship(
+                &part,
+                crate::package::rel_types::IMAGE,
+                &format!("../media/image{}.png", slide),
+            );
+        }
+
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+        package.write(&output).expect("写入应成功");
+
+        let stats = deduplicate_package(&output).expect("去重应成功");
+        assert_eq!(stats.media_count, 1);
+        assert_eq!(stats.bytes_saved, 64);
+        let stats = deduplicate_package(&output).expect("再次去重应成功");
+        assert_eq!(stats.bytes_saved, 0);
+
+        let mut archive = zip::ZipArchive::new(File::open(&output).unwrap()).unwrap();
+        assert!(archive.by_name("ppt/media/image2.png").is_err());
+
+        let mut rels = String::new();
+        archive
+            .by_name("ppt/slides/_rels/slide2.xml.rels")
+            .unwrap()
+            .read_to_string(&mut rels)
+            .unwrap();
+        assert!(rels.contains(r#"Target="../media/image1.png""#));
+    }
+
+    #[test]
+    fn test_remove_override() {
+        let xml = r#"<Types><Override PartName="/ppt/media/a.png" ContentType="image/png"/><Default Extension="xml" ContentType="application/xml"/></Types>"#;
+        assert_eq!(
+            remove_override(xml, "ppt/media/a.png"),
+            r#"<Types><Default Extension="xml" ContentType="application/xml"/></Types>"#
+        );
+    }
+}
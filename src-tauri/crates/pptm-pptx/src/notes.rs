@@ -0,0 +1,736 @@
+// 演讲备注渲染
+// 将 Markdown 备注转换为 DrawingML 段落（标题、段落、项目符号、编号列表、粗体/斜体），
+// 并可从备注页读回 Markdown
+
+use crate::{escape_xml, PptxError, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+/// 列表每级缩进（EMU）
+const LIST_INDENT: i64 = 342_900;
+/// 项目符号悬挂缩进（EMU）
+const BULLET_HANGING: i64 = 228_600;
+
+/// Markdown 块级元素
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Block {
+    /// 标题（级别 1-6）
+    Heading(usize, String),
+    /// 普通段落，保留原文换行
+    Paragraph(Vec<String>),
+    /// 无序列表项（缩进级别）
+    Bullet(usize, String),
+    /// 有序列表项（缩进级别、本列表起始编号）
+    Numbered(usize, u32, String),
+}
+
+/// 行内文本片段
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Run {
+    text: String,
+    bold: bool,
+    italic: bool,
+    code: bool,
+}
+
+/// 将 Markdown 备注渲染为 `a:p` 段落序列，可直接放入 `p:txBody`
+pub fn markdown_to_paragraphs(markdown: &str) -> String {
+    let blocks = parse_blocks(markdown);
+    if blocks.is_empty() {
+        return r#"<a:p><a:endParaRPr lang="zh-CN" dirty="0"/></a:p>"#.to_string();
+    }
+
+    blocks.iter().map(block_xml).collect()
+}
+
+/// 纯文本备注行（用于 PDF 等不支持 DrawingML 的输出）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteLine {
+    /// 去掉行内标记后的文字，列表项带 `•` 或编号前缀
+    pub text: String,
+    /// 列表缩进级别
+    pub level: usize,
+    /// 是否为标题
+    pub heading: bool,
+}
+
+/// 将 Markdown 备注转换为纯文本行
+pub fn markdown_to_lines(markdown: &str) -> Vec<NoteLine> {
+    let mut lines = Vec::new();
+    // 各级有序列表的（起始编号, 下一个编号）
+    let mut numbers: Vec<Option<(u32, u32)>> = Vec::new();
+    let plain =
+        |text: &str| -> String { parse_inline(text).into_iter().map(|run| run.text).collect() };
+
+    for block in parse_blocks(markdown) {
+        match block {
+            Block::Heading(_, text) => {
+                numbers.clear();
+                lines.push(NoteLine {
+                    text: plain(&text),
+                    level: 0,
+                    heading: true,
+                });
+            }
+            Block::Paragraph(paragraph) => {
+                numbers.clear();
+                lines.extend(paragraph.iter().map(|line| NoteLine {
+                    text: plain(line),
+                    level: 0,
+                    heading: false,
+                }));
+            }
+            Block::Bullet(level, text) => {
+                numbers.truncate(level);
+                lines.push(NoteLine {
+                    text: format!("• {}", plain(&text)),
+                    level,
+                    heading: false,
+                });
+            }
+            Block::Numbered(level, start, text) => {
+                numbers.resize(numbers.len().max(level + 1), None);
+                numbers.truncate(level + 1);
+                let number = numbers[level]
+                    .filter(|(list_start, _)| *list_start == start)
+                    .map_or(start, |(_, next)| next);
+                numbers[level] = Some((start, number + 1));
+                lines.push(NoteLine {
+                    text: format!("{}. {}", number, plain(&text)),
+                    level,
+                    heading: false,
+                });
+            }
+        }
+    }
+
+    lines
+}
+
+fn parse_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    // 当前有序列表各级的起始编号
+    let mut list_starts: Vec<Option<u32>> = Vec::new();
+
+    let flush = |paragraph: &mut Vec<String>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(std::mem::take(paragraph)));
+        }
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        let indent = line.len() - line.trim_start().len();
+        let level = indent / 2;
+
+        if trimmed.is_empty() {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        // 分隔线不输出
+        if trimmed.len() >= 3 && trimmed.chars().all(|c| matches!(c, '-' | '*' | '_')) {
+            flush(&mut paragraph, &mut blocks);
+            list_starts.clear();
+            continue;
+        }
+
+        if let Some((heading_level, text)) = heading(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+            list_starts.clear();
+            blocks.push(Block::Heading(heading_level, text.to_string()));
+        } else if let Some(text) = bullet(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+            // 同级无序列表项会打断有序列表
+            list_starts.truncate(level);
+            blocks.push(Block::Bullet(level, text.to_string()));
+        } else if let Some((number, text)) = numbered(trimmed) {
+            flush(&mut paragraph, &mut blocks);
+            list_starts.resize(list_starts.len().max(level + 1), None);
+            list_starts.truncate(level + 1);
+            let start = *list_starts[level].get_or_insert(number);
+            blocks.push(Block::Numbered(level, start, text.to_string()));
+        } else {
+            let text = trimmed.trim_start_matches('>').trim();
+            if paragraph.is_empty() {
+                list_starts.clear();
+            }
+            paragraph.push(text.to_string());
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+
+    blocks
+}
+
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then(|| (level, text.trim()))
+}
+
+fn bullet(line: &str) -> Option<&str> {
+    ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .map(str::trim)
+}
+
+fn numbered(line: &str) -> Option<(u32, &str)> {
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return None;
+    }
+
+    let rest = &line[digits..];
+    let text = rest
+        .strip_prefix(". ")
+        .or_else(|| rest.strip_prefix(") "))
+        .or_else(|| rest.strip_prefix("、"))?;
+    Some((line[..digits].parse().ok()?, text.trim()))
+}
+
+fn block_xml(block: &Block) -> String {
+    match block {
+        Block::Heading(level, text) => {
+            let size = match level {
+                1 => 1600,
+                2 => 1400,
+                _ => 1200,
+            };
+            format!(
+                r#"<a:p><a:pPr><a:spcBef><a:spcPts val="600"/></a:spcBef></a:pPr>{}</a:p>"#,
+                runs_xml(&parse_inline(text), Some(size), true)
+            )
+        }
+        Block::Paragraph(lines) => {
+            let body: Vec<String> = lines
+                .iter()
+                .map(|line| runs_xml(&parse_inline(line), None, false))
+                .collect();
+            format!(
+                r#"<a:p><a:pPr><a:spcAft><a:spcPts val="600"/></a:spcAft></a:pPr>{}</a:p>"#,
+                body.join(r#"<a:br><a:rPr lang="zh-CN" dirty="0"/></a:br>"#)
+            )
+        }
+        Block::Bullet(level, text) => format!(
+            r#"<a:p><a:pPr marL="{}" lvl="{}" indent="-{}"><a:buFont typeface="Arial"/><a:buChar char="•"/></a:pPr>{}</a:p>"#,
+            LIST_INDENT * (*level as i64 + 1),
+            level,
+            BULLET_HANGING,
+            runs_xml(&parse_inline(text), None, false)
+        ),
+        Block::Numbered(level, start, text) => {
+            let start_at = if *start == 1 {
+                String::new()
+            } else {
+                format!(r#" startAt="{}""#, start)
+            };
+            format!(
+                r#"<a:p><a:pPr marL="{}" lvl="{}" indent="-{}"><a:buFont typeface="+mj-lt"/><a:buAutoNum type="arabicPeriod"{}/></a:pPr>{}</a:p>"#,
+                LIST_INDENT * (*level as i64 + 1),
+                level,
+                BULLET_HANGING,
+                start_at,
+                runs_xml(&parse_inline(text), None, false)
+            )
+        }
+    }
+}
+
+fn runs_xml(runs: &[Run], size: Option<u32>, force_bold: bool) -> String {
+    runs.iter()
+        .filter(|run| !run.text.is_empty())
+        .map(|run| {
+            let mut attrs = String::from(r#" lang="zh-CN""#);
+            if let Some(size) = size {
+                attrs.push_str(&format!(r#" sz="{}""#, size));
+            }
+            if run.bold || force_bold {
+                attrs.push_str(r#" b="1""#);
+            }
+            if run.italic {
+                attrs.push_str(r#" i="1""#);
+            }
+            attrs.push_str(r#" dirty="0""#);
+
+            let font = if run.code {
+                r#"<a:latin typeface="Consolas"/><a:cs typeface="Consolas"/>"#
+            } else {
+                ""
+            };
+
+            if font.is_empty() {
+                format!(
+                    "<a:r><a:rPr{}/><a:t>{}</a:t></a:r>",
+                    attrs,
+                    escape_xml(&run.text)
+                )
+            } else {
+                format!(
+                    "<a:r><a:rPr{}>{}</a:rPr><a:t>{}</a:t></a:r>",
+                    attrs,
+                    font,
+                    escape_xml(&run.text)
+                )
+            }
+        })
+        .collect()
+}
+
+/// 解析行内格式：`**粗体**`、`__粗体__`、`*斜体*`、`_斜体_`、`` `代码` ``、`[文字](链接)`
+///
+/// 没有闭合的标记按原文保留
+fn parse_inline(text: &str) -> Vec<Run> {
+    let mut runs = Vec::new();
+    parse_inline_into(text, false, false, &mut runs);
+    runs
+}
+
+fn parse_inline_into(text: &str, bold: bool, italic: bool, runs: &mut Vec<Run>) {
+    let mut plain = String::new();
+    let mut rest = text;
+
+    let push_plain = |plain: &mut String, runs: &mut Vec<Run>| {
+        if !plain.is_empty() {
+            runs.push(Run {
+                text: std::mem::take(plain),
+                bold,
+                italic,
+                code: false,
+            });
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        // 行内代码
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                push_plain(&mut plain, runs);
+                runs.push(Run {
+                    text: rest[1..1 + end].to_string(),
+                    bold,
+                    italic,
+                    code: true,
+                });
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+
+        // 链接只保留文字
+        if c == '[' {
+            if let Some((label, after)) = link(rest) {
+                push_plain(&mut plain, runs);
+                parse_inline_into(label, bold, italic, runs);
+                rest = after;
+                continue;
+            }
+        }
+
+        if c == '*' || c == '_' {
+            let marker = if rest[1..].starts_with(c) {
+                &rest[..2]
+            } else {
+                &rest[..1]
+            };
+            // 单词内的下划线（如 snake_case）不是强调标记
+            let inside_word = c == '_' && plain.chars().last().is_some_and(char::is_alphanumeric);
+
+            if !inside_word {
+                if let Some(end) = rest[marker.len()..].find(marker).filter(|&end| end > 0) {
+                    push_plain(&mut plain, runs);
+                    let inner = &rest[marker.len()..marker.len() + end];
+                    if marker.len() == 2 {
+                        parse_inline_into(inner, true, italic, runs);
+                    } else {
+                        parse_inline_into(inner, bold, true, runs);
+                    }
+                    rest = &rest[marker.len() * 2 + end..];
+                    continue;
+                }
+            }
+        }
+
+        plain.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    push_plain(&mut plain, runs);
+}
+
+/// 解析 `[文字](链接)`，返回文字与剩余文本
+fn link(text: &str) -> Option<(&str, &str)> {
+    let close = text.find("](")?;
+    let end = text[close + 2..].find(')')?;
+    Some((&text[1..close], &text[close + 2 + end + 1..]))
+}
+
+/// 读回的段落类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParagraphKind {
+    Plain,
+    Bullet,
+    /// 编号列表项（`startAt`）
+    Numbered(Option<u32>),
+}
+
+/// 读回的段落
+#[derive(Debug, Default)]
+struct ReadParagraph {
+    kind: Option<ParagraphKind>,
+    level: usize,
+    /// 段前间距（标题的标志）
+    space_before: bool,
+    /// 按 `a:br` 分行的文本片段
+    lines: Vec<Vec<(Run, Option<u32>)>>,
+}
+
+/// 从备注页 XML 读回 Markdown
+///
+/// 只读取正文占位符（`type="body"`）中的文字；标题、项目符号、编号列表与
+/// 粗体/斜体/代码按 [`markdown_to_paragraphs`] 的写法还原，其余格式按纯文本处理
+pub fn notes_to_markdown(notes_xml: &str) -> Result<String> {
+    let mut reader = Reader::from_str(notes_xml);
+    let mut paragraphs: Vec<ReadParagraph> = Vec::new();
+    let mut shape: Vec<ReadParagraph> = Vec::new();
+    let mut is_body = false;
+    let mut paragraph: Option<ReadParagraph> = None;
+    let mut run: Option<(Run, Option<u32>)> = None;
+    let mut in_text = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| PptxError::Package(format!("备注页 XML 解析失败: {}", e)))?;
+
+        match event {
+            Event::Eof => break,
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"sp" => {
+                shape.clear();
+                is_body = false;
+            }
+            Event::End(e) if e.local_name().as_ref() == b"sp" && is_body => {
+                paragraphs = std::mem::take(&mut shape);
+                break;
+            }
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"ph" => is_body = attribute(&e, b"type").as_deref() == Some("body"),
+                b"p" => {
+                    paragraph = Some(ReadParagraph {
+                        lines: vec![Vec::new()],
+                        ..ReadParagraph::default()
+                    })
+                }
+                b"pPr" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.level = attribute(&e, b"lvl")
+                            .and_then(|lvl| lvl.parse().ok())
+                            .unwrap_or(0);
+                    }
+                }
+                b"buChar" => set_kind(&mut paragraph, ParagraphKind::Bullet),
+                b"buAutoNum" => set_kind(
+                    &mut paragraph,
+                    ParagraphKind::Numbered(
+                        attribute(&e, b"startAt").and_then(|start| start.parse().ok()),
+                    ),
+                ),
+                b"spcBef" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.space_before = true;
+                    }
+                }
+                b"r" | b"fld" => {
+                    run = Some((
+                        Run {
+                            text: String::new(),
+                            bold: false,
+                            italic: false,
+                            code: false,
+                        },
+                        None,
+                    ))
+                }
+                b"rPr" => {
+                    if let Some((run, size)) = run.as_mut() {
+                        run.bold = attribute(&e, b"b").as_deref() == Some("1");
+                        run.italic = attribute(&e, b"i").as_deref() == Some("1");
+                        *size = attribute(&e, b"sz").and_then(|sz| sz.parse().ok());
+                    }
+                }
+                b"latin" => {
+                    if let Some((run, _)) = run.as_mut() {
+                        run.code = attribute(&e, b"typeface").as_deref() == Some("Consolas");
+                    }
+                }
+                b"t" => in_text = run.is_some(),
+                b"br" => {
+                    if let Some(paragraph) = paragraph.as_mut() {
+                        paragraph.lines.push(Vec::new());
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) if in_text => {
+                if let Some((run, _)) = run.as_mut() {
+                    let text = text
+                        .unescape()
+                        .map_err(|e| PptxError::Package(format!("备注页 XML 解析失败: {}", e)))?;
+                    run.text.push_str(&text);
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"r" | b"fld" => {
+                    if let (Some(finished), Some(paragraph)) = (run.take(), paragraph.as_mut()) {
+                        if let Some(line) = paragraph.lines.last_mut() {
+                            line.push(finished);
+                        }
+                    }
+                }
+                b"p" => {
+                    if let Some(finished) = paragraph.take() {
+                        shape.push(finished);
+                    }
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    Ok(paragraphs_to_markdown(&paragraphs))
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == name)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.to_string())
+}
+
+fn set_kind(paragraph: &mut Option<ReadParagraph>, kind: ParagraphKind) {
+    if let Some(paragraph) = paragraph.as_mut() {
+        paragraph.kind = Some(kind);
+    }
+}
+
+fn paragraphs_to_markdown(paragraphs: &[ReadParagraph]) -> String {
+    let mut markdown = String::new();
+    let mut previous_list = false;
+    // 各级编号列表当前的序号
+    let mut numbers: Vec<u32> = Vec::new();
+
+    for paragraph in paragraphs {
+        let runs = || paragraph.lines.iter().flatten();
+        if runs().all(|(run, _)| run.text.trim().is_empty()) {
+            continue;
+        }
+
+        let kind = paragraph.kind.unwrap_or(ParagraphKind::Plain);
+        let is_list = kind != ParagraphKind::Plain;
+        let indent = "  ".repeat(paragraph.level);
+        let text = |plain: bool| -> Vec<String> {
+            paragraph
+                .lines
+                .iter()
+                .map(|line| inline_markdown(line, plain))
+                .collect()
+        };
+
+        let block = match kind {
+            ParagraphKind::Bullet => {
+                numbers.truncate(paragraph.level);
+                format!("{}- {}", indent, text(false).join(" "))
+            }
+            ParagraphKind::Numbered(start) => {
+                numbers.truncate(paragraph.level + 1);
+                let number = match numbers.get_mut(paragraph.level) {
+                    Some(number) => {
+                        *number += 1;
+                        *number
+                    }
+                    None => {
+                        numbers.resize(paragraph.level, 1);
+                        numbers.push(start.unwrap_or(1));
+                        start.unwrap_or(1)
+                    }
+                };
+                format!("{}{}. {}", indent, number, text(false).join(" "))
+            }
+            ParagraphKind::Plain => {
+                numbers.clear();
+                match heading_level(paragraph) {
+                    Some(level) => format!("{} {}", "#".repeat(level), text(true).join(" ")),
+                    None => text(false).join("\n"),
+                }
+            }
+        };
+
+        if !markdown.is_empty() {
+            markdown.push_str(if is_list && previous_list {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+        markdown.push_str(block.trim_end());
+        previous_list = is_list;
+    }
+
+    markdown
+}
+
+/// 标题段落：带段前间距、全部加粗且使用标题字号
+fn heading_level(paragraph: &ReadParagraph) -> Option<usize> {
+    if !paragraph.space_before {
+        return None;
+    }
+
+    let mut runs = paragraph.lines.iter().flatten();
+    let (_, size) = runs.clone().next()?;
+    let level = match size {
+        Some(1600) => 1,
+        Some(1400) => 2,
+        Some(1200) => 3,
+        _ => return None,
+    };
+
+    runs.all(|(run, run_size)| run.bold && run_size == size)
+        .then_some(level)
+}
+
+/// 将一行文本片段还原为行内 Markdown，`plain` 为 true 时忽略粗体
+fn inline_markdown(runs: &[(Run, Option<u32>)], plain: bool) -> String {
+    // 合并格式相同的相邻片段
+    let mut merged: Vec<Run> = Vec::new();
+    for (run, _) in runs {
+        let bold = run.bold && !plain;
+        match merged.last_mut() {
+            Some(last)
+                if last.bold == bold && last.italic == run.italic && last.code == run.code =>
+            {
+                last.text.push_str(&run.text)
+            }
+            _ => merged.push(Run {
+                bold,
+                ..run.clone()
+            }),
+        }
+    }
+
+    merged
+        .iter()
+        .map(|run| {
+            if run.code {
+                format!("`{}`", run.text)
+            } else if run.text.trim().is_empty() {
+                run.text.clone()
+            } else {
+                let marker = match (run.bold, run.italic) {
+                    (true, true) => "***",
+                    (true, false) => "**",
+                    (false, true) => "*",
+                    (false, false) => "",
+                };
+                format!("{}{}{}", marker, run.text, marker)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(text: &str, bold: bool, italic: bool) -> Run {
+        Run {
+            text: text.to_string(),
+            bold,
+            italic,
+            code: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_inline() {
+        assert_eq!(
+            parse_inline("先说**核心结论**，再*展开*"),
+            vec![
+                run("先说", false, false),
+                run("核心结论", true, false),
+                run("，再", false, false),
+                run("展开", false, true),
+            ]
+        );
+        assert_eq!(
+            parse_inline("未闭合 **标记 与 snake_case_name"),
+            vec![run("未闭合 **标记 与 snake_case_name", false, false)]
+        );
+        assert_eq!(
+            parse_inline("见[附录](https://example.com)"),
+            vec![run("见", false, false), run("附录", false, false)]
+        );
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let markdown = "# 开场\n\n大家好，\n今天介绍三点：\n\n1. 背景\n2. 方案\n   - 细节\n3. 计划\n\n---\n\n- 结尾";
+        assert_eq!(
+            parse_blocks(markdown),
+            vec![
+                Block::Heading(1, "开场".to_string()),
+                Block::Paragraph(vec!["大家好，".to_string(), "今天介绍三点：".to_string()]),
+                Block::Numbered(0, 1, "背景".to_string()),
+                Block::Numbered(0, 1, "方案".to_string()),
+                Block::Bullet(1, "细节".to_string()),
+                Block::Numbered(0, 1, "计划".to_string()),
+                Block::Bullet(0, "结尾".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_paragraphs() {
+        let xml = markdown_to_paragraphs("## 要点\n\n- **增长** 12%\n\n3. 第三项");
+
+        assert!(xml.contains(r#"<a:rPr lang="zh-CN" sz="1400" b="1" dirty="0"/><a:t>要点</a:t>"#));
+        assert!(xml.contains(r#"<a:buChar char="•"/>"#));
+        assert!(xml.contains(r#"<a:rPr lang="zh-CN" b="1" dirty="0"/><a:t>增长</a:t>"#));
+        assert!(xml.contains(r#"<a:buAutoNum type="arabicPeriod" startAt="3"/>"#));
+        assert!(!xml.contains("**"));
+        assert_eq!(
+            markdown_to_paragraphs("  \n"),
+            r#"<a:p><a:endParaRPr lang="zh-CN" dirty="0"/></a:p>"#
+        );
+    }
+
+    #[test]
+    fn test_markdown_to_lines() {
+        let lines =
+            markdown_to_lines("# 开场\n\n今天介绍**三点**：\n\n3. 背景\n   - 细节\n4. 方案");
+        let texts: Vec<_> = lines.iter().map(|line| line.text.as_str()).collect();
+
+        assert_eq!(
+            texts,
+            vec!["开场", "今天介绍三点：", "3. 背景", "• 细节", "4. 方案"]
+        );
+        assert!(lines[0].heading);
+        assert_eq!(lines[3].level, 1);
+    }
+
+    #[test]
+    fn test_notes_round_trip() {
+        let markdown = "# 开场\n\n大家好，\n今天介绍**三点**：\n\n1. 背景\n2. *方案*\n  - 细节\n3. 计划\n\n运行 `cargo test` 即可";
+        let notes = format!(
+            r#"<p:notes xmlns:a="a" xmlns:p="p"><p:cSld><p:spTree><p:sp><p:nvSpPr><p:nvPr><p:ph type="sldImg"/></p:nvPr></p:nvSpPr></p:sp><p:sp><p:nvSpPr><p:cNvPr id="3" name="Notes"/><p:nvPr><p:ph type="body" idx="1"/></p:nvPr></p:nvSpPr><p:txBody><a:bodyPr/>{}</p:txBody></p:sp></p:spTree></p:cSld></p:notes>"#,
+            markdown_to_paragraphs(markdown)
+        );
+
+        assert_eq!(notes_to_markdown(&notes).expect("解析应成功"), markdown);
+    }
+}
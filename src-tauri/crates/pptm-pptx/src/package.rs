@@ -0,0 +1,469 @@
+// OOXML 包构建
+// 统一管理部件、关系与内容类型：只为实际写入的部件声明关系和内容类型
+
+use crate::{escape_xml, PptxError, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::write::{FileOptions, ZipWriter};
+use zip::CompressionMethod;
+
+/// 关系类型
+pub mod rel_types {
+    pub const OFFICE_DOCUMENT: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
+    pub const CORE_PROPERTIES: &str =
+        "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties";
+    pub const EXTENDED_PROPERTIES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties";
+    pub const SLIDE: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slide";
+    pub const SLIDE_LAYOUT: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideLayout";
+    pub const SLIDE_MASTER: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/slideMaster";
+    pub const NOTES_SLIDE: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesSlide";
+    pub const NOTES_MASTER: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/notesMaster";
+    pub const THEME: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme";
+    pub const IMAGE: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/image";
+    pub const HYPERLINK: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink";
+    pub const CHART: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart";
+    pub const PACKAGE: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/package";
+    pub const PRES_PROPS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/presProps";
+    pub const VIEW_PROPS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/viewProps";
+    pub const TABLE_STYLES: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/tableStyles";
+}
+
+/// 部件内容类型
+pub mod content_types {
+    pub const RELATIONSHIPS: &str = "application/vnd.openxmlformats-package.relationships+xml";
+    pub const XML: &str = "application/xml";
+    pub const PRESENTATION: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation.main+xml";
+    pub const SLIDE: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.slide+xml";
+    pub const SLIDE_LAYOUT: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.slideLayout+xml";
+    pub const SLIDE_MASTER: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.slideMaster+xml";
+    pub const NOTES_SLIDE: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.notesSlide+xml";
+    pub const NOTES_MASTER: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.notesMaster+xml";
+    pub const THEME: &str = "application/vnd.openxmlformats-officedocument.theme+xml";
+    pub const PRES_PROPS: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.presProps+xml";
+    pub const VIEW_PROPS: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.viewProps+xml";
+    pub const TABLE_STYLES: &str =
+        "application/vnd.openxmlformats-officedocument.presentationml.tableStyles+xml";
+    pub const CORE_PROPERTIES: &str = "application/vnd.openxmlformats-package.core-properties+xml";
+    pub const EXTENDED_PROPERTIES: &str =
+        "application/vnd.openxmlformats-officedocument.extended-properties+xml";
+    pub const PNG: &str = "image/png";
+    pub const SVG: &str = "image/svg+xml";
+    pub const JPEG: &str = "image/jpeg";
+    pub const GIF: &str = "image/gif";
+    pub const CHART: &str = "application/vnd.openxmlformats-officedocument.drawingml.chart+xml";
+    pub const XLSX: &str = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+}
+
+/// 单条关系
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Relationship {
+    /// 关系 ID，例如 `rId1`
+    pub id: String,
+    /// 关系类型 URI
+    pub rel_type: String,
+    /// 目标（内部关系为相对于源部件目录的路径）
+    pub target: String,
+    /// 是否为外部关系（`TargetMode="External"`）
+    pub external: bool,
+}
+
+/// 包内部件
+#[derive(Debug, Clone)]
+struct Part {
+    name: String,
+    content_type: String,
+    data: Vec<u8>,
+}
+
+/// OOXML 包
+///
+/// 部件名不带前导 `/`，例如 `ppt/slides/slide1.xml`；包级关系的源部件为空字符串
+#[derive(Debug, Default)]
+pub struct OoxmlPackage {
+    parts: Vec<Part>,
+    relationships: BTreeMap<String, Vec<Relationship>>,
+}
+
+impl OoxmlPackage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加部件
+    pub fn add_part(
+        &mut self,
+        name: impl Into<String>,
+        content_type: &str,
+        data: impl Into<Vec<u8>>,
+    ) {
+        self.parts.push(Part {
+            name: name.into(),
+            content_type: content_type.to_string(),
+            data: data.into(),
+        });
+    }
+
+    /// 部件是否存在
+    pub fn has_part(&self, name: &str) -> bool {
+        self.parts.iter().any(|part| part.name == name)
+    }
+
+    /// 获取部件内容
+    pub fn part_data(&self, name: &str) -> Option<&[u8]> {
+        self.parts
+            .iter()
+            .find(|part| part.name == name)
+            .map(|part| part.data.as_slice())
+    }
+
+    /// 添加内部关系，返回分配的关系 ID
+    ///
+    /// `target` 相对于源部件所在目录，例如幻灯片引用图片时为 `../media/image1.png`
+    pub fn add_relationship(&mut self, source: &str, rel_type: &str, target: &str) -> String {
+        self.push_relationship(source, rel_type, target, false)
+    }
+
+    /// 添加外部关系（例如超链接），返回分配的关系 ID
+    pub fn add_external_relationship(
+        &mut self,
+        source: &str,
+        rel_type: &str,
+        target: &str,
+    ) -> String {
+        self.push_relationship(source, rel_type, target, true)
+    }
+
+    fn push_relationship(
+        &mut self,
+        source: &str,
+        rel_type: &str,
+        target: &str,
+        external: bool,
+    ) -> String {
+        let relationships = self.relationships.entry(source.to_string()).or_default();
+        let id = format!("rId{}", relationships.len() + 1);
+
+        relationships.push(Relationship {
+            id: id.clone(),
+            rel_type: rel_type.to_string(),
+            target: target.to_string(),
+            external,
+        });
+
+        id
+    }
+
+    /// 获取源部件的全部关系
+    pub fn relationships(&self, source: &str) -> &[Relationship] {
+        self.relationships
+            .get(source)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// 生成 `[Content_Types].xml`
+    ///
+    /// 图片、内嵌工作簿等二进制部件按扩展名声明 Default，其余 XML 部件逐个声明 Override
+    pub fn content_types_xml(&self) -> String {
+        let mut defaults: BTreeMap<String, String> = BTreeMap::new();
+        defaults.insert("rels".to_string(), content_types::RELATIONSHIPS.to_string());
+        defaults.insert("xml".to_string(), content_types::XML.to_string());
+
+        let mut overrides = String::new();
+        for part in &self.parts {
+            let extension = part
+                .name
+                .rsplit('.')
+                .next()
+                .unwrap_or_default()
+                .to_lowercase();
+
+            if part.content_type.starts_with("image/") || part.content_type == content_types::XLSX {
+                defaults.insert(extension, part.content_type.clone());
+            } else if part.content_type != content_types::XML {
+                overrides.push_str(&format!(
+                    r#"  <Override PartName="/{}" ContentType="{}"/>
+"#,
+                    escape_xml(&part.name),
+                    part.content_type
+                ));
+            }
+        }
+
+        let mut xml = String::from(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+"#,
+        );
+        for (extension, content_type) in &defaults {
+            xml.push_str(&format!(
+                r#"  <Default Extension="{}" ContentType="{}"/>
+"#,
+                extension, content_type
+            ));
+        }
+        xml.push_str(&overrides);
+        xml.push_str("</Types>");
+        xml
+    }
+
+    /// 检查所有内部关系的目标部件都存在
+    pub fn check_relationships(&self) -> Result<()> {
+        let mut missing = Vec::new();
+
+        for (source, relationships) in &self.relationships {
+            for rel in relationships.iter().filter(|rel| !rel.external) {
+                let resolved = resolve_target(source, &rel.target);
+                if !self.has_part(&resolved) {
+                    missing.push(format!("{} -> {}", rels_part_name(source), resolved));
+                }
+            }
+        }
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(PptxError::Package(format!(
+                "关系目标不存在: {}",
+                missing.join(", ")
+            )))
+        }
+    }
+
+    /// 写入 PPTX 文件，写入完成后检查关系目标
+    pub fn write(&self, output_path: &Path) -> Result<()> {
+        let file = File::create(output_path)?;
+        let mut zip = ZipWriter::new(file);
+        let options = FileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+
+        zip.start_file("[Content_Types].xml", options)?;
+        zip.write_all(self.content_types_xml().as_bytes())?;
+
+        for (source, relationships) in &self.relationships {
+            zip.start_file(rels_part_name(source), options)?;
+            zip.write_all(relationships_xml(relationships).as_bytes())?;
+        }
+
+        for part in &self.parts {
+            zip.start_file(part.name.as_str(), options)?;
+            zip.write_all(&part.data)?;
+        }
+
+        zip.finish()?;
+
+        self.check_relationships()
+    }
+}
+
+/// 源部件对应的关系部件名，例如 `ppt/slides/_rels/slide1.xml.rels`
+pub fn rels_part_name(source: &str) -> String {
+    match source.rsplit_once('/') {
+        Some((dir, file)) => format!("{}/_rels/{}.rels", dir, file),
+        None => format!("_rels/{}.rels", source),
+    }
+}
+
+/// 将相对于源部件的关系目标解析为包内部件名
+pub fn resolve_target(source: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_string();
+    }
+
+    let mut segments: Vec<&str> = match source.rsplit_once('/') {
+        Some((dir, _)) => dir.split('/').collect(),
+        None => Vec::new(),
+    };
+
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            other => segments.push(other),
+        }
+    }
+
+    segments.join("/")
+}
+
+/// 生成关系部件 XML
+pub fn relationships_xml(relationships: &[Relationship]) -> String {
+    let mut xml = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+"#,
+    );
+
+    for rel in relationships {
+        xml.push_str(&format!(
+            r#"  <Relationship Id="{}" Type="{}" Target="{}"{}/>
+"#,
+            rel.id,
+            rel.rel_type,
+            escape_xml(&rel.target),
+            if rel.external {
+                r#" TargetMode="External""#
+            } else {
+                ""
+            }
+        ));
+    }
+
+    xml.push_str("</Relationships>");
+    xml
+}
+
+/// 解析关系部件 XML
+pub fn parse_relationships(xml: &str) -> Result<Vec<Relationship>> {
+    let mut reader = Reader::from_str(xml);
+    let mut relationships = Vec::new();
+
+    loop {
+        match reader
+            .read_event()
+            .map_err(|e| PptxError::Package(format!("关系部件解析失败: {}", e)))?
+        {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"Relationship" => {
+                let mut rel = Relationship {
+                    id: String::new(),
+                    rel_type: String::new(),
+                    target: String::new(),
+                    external: false,
+                };
+                for attr in e.attributes().flatten() {
+                    let value = attr
+                        .unescape_value()
+                        .map_err(|e| PptxError::Package(format!("关系部件解析失败: {}", e)))?
+                        .to_string();
+                    match attr.key.as_ref() {
+                        b"Id" => rel.id = value,
+                        b"Type" => rel.rel_type = value,
+                        b"Target" => rel.target = value,
+                        b"TargetMode" => rel.external = value == "External",
+                        _ => {}
+                    }
+                }
+                relationships.push(rel);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(relationships)
+}
+
+/// 读取 zip 包中的全部部件（不含目录）
+pub fn read_parts(path: &Path) -> Result<HashMap<String, Vec<u8>>> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)?;
+
+    let mut parts = HashMap::new();
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        if file.is_dir() {
+            continue;
+        }
+
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+        parts.insert(file.name().to_string(), data);
+    }
+
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_target() {
+        assert_eq!(
+            resolve_target("ppt/slides/slide1.xml", "../media/image1.png"),
+            "ppt/media/image1.png"
+        );
+        assert_eq!(
+            resolve_target("ppt/presentation.xml", "slides/slide1.xml"),
+            "ppt/slides/slide1.xml"
+        );
+        assert_eq!(
+            resolve_target("", "ppt/presentation.xml"),
+            "ppt/presentation.xml"
+        );
+    }
+
+    #[test]
+    fn test_rels_part_name() {
+        assert_eq!(rels_part_name(""), "_rels/.rels");
+        assert_eq!(
+            rels_part_name("ppt/slides/slide1.xml"),
+            "ppt/slides/_rels/slide1.xml.rels"
+        );
+    }
+
+    #[test]
+    fn test_content_types_only_for_existing_parts() {
+        let mut package = OoxmlPackage::new();
+        package.add_part("ppt/presentation.xml", content_types::PRESENTATION, "<p/>");
+        package.add_part("ppt/media/image1.png", content_types::PNG, vec![0u8]);
+
+        let xml = package.content_types_xml();
+        assert!(xml.contains(r#"<Default Extension="png" ContentType="image/png"/>"#));
+        assert!(!xml.contains(r#"Extension="svg""#));
+        assert!(xml.contains(r#"PartName="/ppt/presentation.xml""#));
+        assert!(!xml.contains("notesSlide"));
+    }
+
+    #[test]
+    fn test_check_relationships_reports_missing_target() {
+        let mut package = OoxmlPackage::new();
+        package.add_part("ppt/slides/slide1.xml", content_types::SLIDE, "<p:sld/>");
+        package.add_relationship(
+            "ppt/slides/slide1.xml",
+            rel_types::IMAGE,
+            "../media/image1.png",
+        );
+        package.add_external_relationship(
+            "ppt/slides/slide1.xml",
+            rel_types::IMAGE,
+            "https://example.com/logo.png",
+        );
+
+        let error = package.check_relationships().unwrap_err().to_string();
+        assert!(error.contains("ppt/media/image1.png"));
+        assert!(!error.contains("example.com"));
+
+        package.add_part("ppt/media/image1.png", content_types::PNG, vec![0u8]);
+        assert!(package.check_relationships().is_ok());
+    }
+}
@@ -0,0 +1,630 @@
+// PPTX 导入
+// 打开已有的 .pptx，按演示文稿中的顺序读回幻灯片标题、演讲备注、分节与整页 SVG/PNG 图片，
+// 使客户在 PowerPoint 中修改过的演示文稿可以同步回项目
+
+use crate::package::{parse_relationships, read_parts, rel_types, resolve_target, Relationship};
+use crate::{notes, PptxError, Result, Slide, SlideContent};
+use base64::Engine;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 未指定幻灯片尺寸时使用的画布（像素）
+const DEFAULT_CANVAS: (u32, u32) = (1280, 720);
+
+/// 图片覆盖幻灯片的比例不低于该值时视为整页图片
+const FULL_SLIDE_RATIO: f64 = 0.98;
+
+/// 导入结果
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportSummary {
+    /// 演示文稿中的幻灯片数量
+    pub slide_count: usize,
+    /// 写入 svg_final 的幻灯片数量
+    pub slides_written: usize,
+    /// 写入 notes 的备注数量
+    pub notes_written: usize,
+    /// 删除的旧备注文件数量（对应幻灯片现已没有备注）
+    pub notes_removed: usize,
+}
+
+/// presentation.xml 中的信息
+#[derive(Debug, Default)]
+struct PresentationInfo {
+    /// 按顺序排列的幻灯片（幻灯片 ID、关系 ID）
+    slides: Vec<(u32, String)>,
+    /// 幻灯片 ID 到分节名称
+    sections: HashMap<u32, String>,
+    /// 幻灯片尺寸（EMU）
+    size: Option<(i64, i64)>,
+}
+
+/// 幻灯片中的图片
+#[derive(Debug, Default)]
+struct PictureInfo {
+    /// `a:blip` 与 `asvg:svgBlip` 引用的关系 ID
+    rids: Vec<String>,
+    /// 图片尺寸（EMU）
+    size: Option<(i64, i64)>,
+}
+
+/// 幻灯片 XML 中的信息
+#[derive(Debug, Default)]
+struct SlideInfo {
+    /// 标题占位符中的文字
+    title: Option<String>,
+    pictures: Vec<PictureInfo>,
+}
+
+/// 读取 PPTX 中的幻灯片
+///
+/// 标题取标题占位符的文字，没有时为 `slide_{序号}`；内容取铺满整页的图片（SVG 优先），
+/// 没有整页图片的幻灯片（如原生形状）使用空白 SVG 画布
+pub fn read_pptx(path: &Path) -> Result<Vec<Slide>> {
+    let parts = read_parts(path)?;
+
+    let root_rels = relationships(&parts, "")?;
+    let presentation = root_rels
+        .iter()
+        .find(|rel| rel.rel_type == rel_types::OFFICE_DOCUMENT)
+        .map(|rel| resolve_target("", &rel.target))
+        .ok_or_else(|| PptxError::Package("缺少指向演示文稿的 officeDocument 关系".to_string()))?;
+
+    let info = parse_presentation(&text_part(&parts, &presentation)?)?;
+    let presentation_rels = relationships(&parts, &presentation)?;
+
+    let mut slides = Vec::with_capacity(info.slides.len());
+    for (index, (slide_id, rid)) in info.slides.iter().enumerate() {
+        let number = index + 1;
+        let slide_part = presentation_rels
+            .iter()
+            .find(|rel| &rel.id == rid)
+            .map(|rel| resolve_target(&presentation, &rel.target))
+            .ok_or_else(|| {
+                PptxError::Package(format!("幻灯片 {} 引用了不存在的关系 {}", number, rid))
+            })?;
+
+        let slide_info = parse_slide(&text_part(&parts, &slide_part)?)?;
+        let slide_rels = relationships(&parts, &slide_part)?;
+
+        let notes = match slide_rels
+            .iter()
+            .find(|rel| rel.rel_type == rel_types::NOTES_SLIDE)
+        {
+            Some(rel) => {
+                let notes_part = resolve_target(&slide_part, &rel.target);
+                let markdown = notes::notes_to_markdown(&text_part(&parts, &notes_part)?)?;
+                (!markdown.is_empty()).then_some(markdown)
+            }
+            None => None,
+        };
+
+        slides.push(Slide {
+            number,
+            title: slide_info
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("slide_{:02}", number)),
+            content: slide_content(&parts, &slide_part, &slide_rels, &slide_info, info.size),
+            notes,
+            transition: None,
+            animation: None,
+            section: info.sections.get(slide_id).cloned(),
+            source_svg: None,
+        });
+    }
+
+    Ok(slides)
+}
+
+/// 将 PPTX 同步回项目
+///
+/// 备注写入 `notes/`，文件名与 `svg_final` 中同序号的幻灯片对应，导出时即可读回；
+/// 该幻灯片原有的备注文件先删除，备注已清空的幻灯片导出时不再读到旧备注。
+/// `replace_slides` 为 true 时先将原 `svg_final` 与 `notes` 改名备份，再写入 PPTX 中的幻灯片图片
+/// （PNG 包装为 SVG）
+pub fn import_into_project(
+    pptx_path: &Path,
+    project_path: &Path,
+    replace_slides: bool,
+) -> Result<ImportSummary> {
+    let slides = read_pptx(pptx_path)?;
+    let svg_dir = project_path.join("svg_final");
+    let notes_dir = project_path.join("notes");
+
+    let mut summary = ImportSummary {
+        slide_count: slides.len(),
+        ..ImportSummary::default()
+    };
+
+    let stems: Vec<String> = if replace_slides {
+        // 幻灯片文件名随之改变，旧备注无法再对应到页面，一并备份
+        let timestamp = chrono::Local::now().format("%Y%m%d%H%M%S").to_string();
+        for (dir, name) in [(&svg_dir, "svg_final"), (&notes_dir, "notes")] {
+            if dir.exists() {
+                std::fs::rename(
+                    dir,
+                    project_path.join(format!("{}_backup_{}", name, timestamp)),
+                )?;
+            }
+        }
+        std::fs::create_dir_all(&svg_dir)?;
+
+        let mut stems = Vec::with_capacity(slides.len());
+        for slide in &slides {
+            let stem = slide_stem(slide, slides.len());
+            std::fs::write(svg_dir.join(format!("{}.svg", stem)), slide_svg(slide))?;
+            summary.slides_written += 1;
+            stems.push(stem);
+        }
+        stems
+    } else {
+        let existing = if svg_dir.exists() {
+            crate::list_svg_files(&svg_dir)?
+        } else {
+            Vec::new()
+        };
+        slides
+            .iter()
+            .enumerate()
+            .map(|(index, slide)| {
+                existing
+                    .get(index)
+                    .and_then(|path| path.file_stem())
+                    .and_then(|stem| stem.to_str())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| slide_stem(slide, slides.len()))
+            })
+            .collect()
+    };
+
+    for (slide, stem) in slides.iter().zip(&stems) {
+        // 删除该页原有的备注文件（任何一种可被读取的文件名）
+        let mut removed = false;
+        for name in crate::notes_file_names(slide.number, stem) {
+            let path = notes_dir.join(name);
+            if path.is_file() {
+                std::fs::remove_file(&path)?;
+                removed = true;
+            }
+        }
+
+        let Some(notes) = &slide.notes else {
+            if removed {
+                summary.notes_removed += 1;
+            }
+            continue;
+        };
+        std::fs::create_dir_all(&notes_dir)?;
+        std::fs::write(
+            notes_dir.join(format!("{:02}_{}.md", slide.number, stem)),
+            format!("{}\n", notes),
+        )?;
+        summary.notes_written += 1;
+    }
+
+    Ok(summary)
+}
+
+/// 幻灯片在 svg_final 中的文件名（不含扩展名）
+///
+/// 按文件名排序决定页序，因此总是加上序号前缀（至少两位，超过 99 页时按总页数补零），
+/// 即使标题本身以数字或 `slide_` 开头
+fn slide_stem(slide: &Slide, slide_count: usize) -> String {
+    let title: String = slide
+        .title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect();
+
+    let width = slide_count.to_string().len().max(2);
+    format!("{:0width$}_{}", slide.number, title)
+}
+
+fn slide_svg(slide: &Slide) -> String {
+    match &slide.content {
+        SlideContent::Svg(svg) => svg.clone(),
+        SlideContent::Png(png) => {
+            let (width, height) = png_size(png).unwrap_or(DEFAULT_CANVAS);
+            format!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{0}" height="{1}" viewBox="0 0 {0} {1}"><image width="{0}" height="{1}" xlink:href="data:image/png;base64,{2}"/></svg>"#,
+                width,
+                height,
+                base64::engine::general_purpose::STANDARD.encode(png)
+            )
+        }
+    }
+}
+
+/// PNG 头中的宽高
+fn png_size(data: &[u8]) -> Option<(u32, u32)> {
+    if data.len() < 24 || &data[..8] != b"\x89PNG\r\n\x1a\n" {
+        return None;
+    }
+    let read = |offset: usize| u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap());
+    Some((read(16), read(20)))
+}
+
+fn text_part(parts: &HashMap<String, Vec<u8>>, name: &str) -> Result<String> {
+    parts
+        .get(name)
+        .map(|data| String::from_utf8_lossy(data).into_owned())
+        .ok_or_else(|| PptxError::Package(format!("缺少部件: {}", name)))
+}
+
+/// 部件的关系，没有关系部件时为空
+fn relationships(parts: &HashMap<String, Vec<u8>>, source: &str) -> Result<Vec<Relationship>> {
+    match parts.get(&crate::package::rels_part_name(source)) {
+        Some(data) => parse_relationships(&String::from_utf8_lossy(data)),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn attribute(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == name)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.to_string())
+}
+
+fn xml_error(part: &str, error: quick_xml::Error) -> PptxError {
+    PptxError::Package(format!("{} 解析失败: {}", part, error))
+}
+
+fn parse_presentation(xml: &str) -> Result<PresentationInfo> {
+    let mut reader = Reader::from_str(xml);
+    let mut info = PresentationInfo::default();
+    let mut section: Option<String> = None;
+
+    loop {
+        match reader
+            .read_event()
+            .map_err(|e| xml_error("presentation.xml", e))?
+        {
+            Event::Eof => break,
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"section" => section = attribute(&e, b"name"),
+                b"sldId" => {
+                    // r:id 的前缀由生成工具决定，按局部名匹配
+                    let mut id = None;
+                    let mut rid = None;
+                    for attr in e.attributes().flatten() {
+                        let value = attr.unescape_value().map(|value| value.to_string());
+                        match (attr.key.prefix().is_some(), attr.key.local_name().as_ref()) {
+                            (false, b"id") => id = value.ok().and_then(|id| id.parse::<u32>().ok()),
+                            (true, b"id") => rid = value.ok(),
+                            _ => {}
+                        }
+                    }
+
+                    match (&section, id, rid) {
+                        (Some(name), Some(id), _) => {
+                            info.sections.insert(id, name.clone());
+                        }
+                        (None, Some(id), Some(rid)) => info.slides.push((id, rid)),
+                        _ => {}
+                    }
+                }
+                b"sldSz" => {
+                    let cx = attribute(&e, b"cx").and_then(|cx| cx.parse().ok());
+                    let cy = attribute(&e, b"cy").and_then(|cy| cy.parse().ok());
+                    info.size = cx.zip(cy);
+                }
+                _ => {}
+            },
+            Event::End(e) if e.local_name().as_ref() == b"section" => section = None,
+            _ => {}
+        }
+    }
+
+    Ok(info)
+}
+
+fn parse_slide(xml: &str) -> Result<SlideInfo> {
+    let mut reader = Reader::from_str(xml);
+    let mut info = SlideInfo::default();
+
+    // 当前形状是否为标题占位符及其文字
+    let mut shape: Option<(bool, Vec<String>)> = None;
+    let mut in_text = false;
+    let mut picture: Option<PictureInfo> = None;
+
+    loop {
+        match reader.read_event().map_err(|e| xml_error("幻灯片", e))? {
+            Event::Eof => break,
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"sp" => shape = Some((false, Vec::new())),
+                b"ph" => {
+                    if let Some((is_title, _)) = shape.as_mut() {
+                        *is_title = matches!(
+                            attribute(&e, b"type").as_deref(),
+                            Some("title" | "ctrTitle")
+                        );
+                    }
+                }
+                b"p" => {
+                    if let Some((_, paragraphs)) = shape.as_mut() {
+                        paragraphs.push(String::new());
+                    }
+                }
+                b"t" => in_text = shape.is_some(),
+                b"pic" => picture = Some(PictureInfo::default()),
+                b"blip" | b"svgBlip" => {
+                    if let (Some(picture), Some(rid)) = (picture.as_mut(), attribute(&e, b"embed"))
+                    {
+                        picture.rids.push(rid);
+                    }
+                }
+                // 扩展列表中的 a:ext 没有 cx/cy
+                b"ext" => {
+                    if let Some(picture) = picture.as_mut() {
+                        let cx = attribute(&e, b"cx").and_then(|cx| cx.parse().ok());
+                        let cy = attribute(&e, b"cy").and_then(|cy| cy.parse().ok());
+                        if let Some(size) = cx.zip(cy) {
+                            picture.size.get_or_insert(size);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Event::Text(text) if in_text => {
+                if let Some((_, paragraphs)) = shape.as_mut() {
+                    let text = text.unescape().map_err(|e| xml_error("幻灯片", e))?;
+                    if let Some(paragraph) = paragraphs.last_mut() {
+                        paragraph.push_str(&text);
+                    }
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"sp" => {
+                    if let Some((true, paragraphs)) = shape.take() {
+                        let title = paragraphs
+                            .iter()
+                            .map(|paragraph| paragraph.trim())
+                            .filter(|paragraph| !paragraph.is_empty())
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        if info.title.is_none() && !title.is_empty() {
+                            info.title = Some(title);
+                        }
+                    }
+                }
+                b"pic" => info.pictures.extend(picture.take()),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    Ok(info)
+}
+
+/// 幻灯片内容：铺满整页的图片，SVG 优先于 PNG
+fn slide_content(
+    parts: &HashMap<String, Vec<u8>>,
+    slide_part: &str,
+    slide_rels: &[Relationship],
+    info: &SlideInfo,
+    slide_size: Option<(i64, i64)>,
+) -> SlideContent {
+    let covers_slide = |picture: &&PictureInfo| match (picture.size, slide_size) {
+        (Some((cx, cy)), Some((width, height))) => {
+            cx as f64 >= width as f64 * FULL_SLIDE_RATIO
+                && cy as f64 >= height as f64 * FULL_SLIDE_RATIO
+        }
+        _ => true,
+    };
+
+    let media: Vec<(String, &Vec<u8>)> = info
+        .pictures
+        .iter()
+        .find(covers_slide)
+        .map(|picture| {
+            picture
+                .rids
+                .iter()
+                .filter_map(|rid| slide_rels.iter().find(|rel| &rel.id == rid))
+                .filter(|rel| rel.rel_type == rel_types::IMAGE && !rel.external)
+                .filter_map(|rel| {
+                    let name = resolve_target(slide_part, &rel.target);
+                    parts.get(&name).map(|data| (name.to_lowercase(), data))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some((_, data)) = media.iter().find(|(name, _)| name.ends_with(".svg")) {
+        return SlideContent::Svg(String::from_utf8_lossy(data).into_owned());
+    }
+    if let Some((_, data)) = media.iter().find(|(name, _)| name.ends_with(".png")) {
+        return SlideContent::Png(data.to_vec());
+    }
+
+    let (width, height) = slide_size
+        .map(|(cx, cy)| ((cx / 9525) as u32, (cy / 9525) as u32))
+        .unwrap_or(DEFAULT_CANVAS);
+    SlideContent::Svg(format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}"/>"#,
+        width, height
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{NativeOoxml, SvgMode};
+    use crate::{NoProgress, PptxBackend, PptxConfig};
+    use tokio_util::sync::CancellationToken;
+
+    const SVG: &str = r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 1280 720"><rect width="1280" height="720" fill="#1A73E8"/></svg>"##;
+
+    fn deck() -> Vec<Slide> {
+        (1..=3)
+            .map(|number| Slide {
+                number,
+                title: format!("slide_{:02}", number),
+                content: SlideContent::Svg(SVG.replace("1A73E8", &format!("00000{}", number))),
+                notes: (number != 2).then(|| format!("## 第 {} 页\n\n- **要点**", number)),
+                transition: None,
+                animation: None,
+                section: Some(if number == 1 { "开场" } else { "正文" }.to_string()),
+                source_svg: None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_read_exported_deck() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+        NativeOoxml::new()
+            .with_svg_mode(SvgMode::SvgBlip)
+            .export(
+                &deck(),
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        let slides = read_pptx(&output).expect("读取应成功");
+        assert_eq!(slides.len(), 3);
+        // 导出时为整页图片添加的标题占位符
+        assert_eq!(slides[1].title, "slide 02");
+        assert_eq!(slides[0].notes.as_deref(), Some("## 第 1 页\n\n- **要点**"));
+        assert_eq!(slides[1].notes, None);
+        assert_eq!(slides[2].section.as_deref(), Some("正文"));
+        match &slides[2].content {
+            SlideContent::Svg(svg) => assert!(svg.contains("000003")),
+            SlideContent::Png(_) => panic!("应优先读取 SVG"),
+        }
+    }
+
+    #[test]
+    fn test_parse_slide_title_and_pictures() {
+        let xml = r#"<p:sld xmlns:a="a" xmlns:p="p" xmlns:r="r"><p:cSld><p:spTree><p:sp><p:nvSpPr><p:nvPr><p:ph type="ctrTitle"/></p:nvPr></p:nvSpPr><p:txBody><a:p><a:r><a:t>季度</a:t></a:r><a:r><a:t>回顾</a:t></a:r></a:p><a:p><a:r><a:t>2025</a:t></a:r></a:p></p:txBody></p:sp><p:pic><p:blipFill><a:blip r:embed="rId2"><a:extLst><a:ext uri="{96DAC541}"><asvg:svgBlip xmlns:asvg="s" r:embed="rId3"/></a:ext></a:extLst></a:blip></p:blipFill><p:spPr><a:xfrm><a:off x="0" y="0"/><a:ext cx="12192000" cy="6858000"/></a:xfrm></p:spPr></p:pic></p:spTree></p:cSld></p:sld>"#;
+
+        let info = parse_slide(xml).expect("解析应成功");
+        assert_eq!(info.title.as_deref(), Some("季度回顾 2025"));
+        assert_eq!(info.pictures.len(), 1);
+        assert_eq!(info.pictures[0].rids, vec!["rId2", "rId3"]);
+        assert_eq!(info.pictures[0].size, Some((12192000, 6858000)));
+    }
+
+    #[tokio::test]
+    async fn test_replace_slides_keeps_order() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let project = temp_dir.path();
+        let output = project.join("deck.pptx");
+
+        // 有标题、无标题（读回为 "slide 02"）与以数字开头的标题（取自页面文字）混排
+        let mut slides = deck();
+        slides[0].title = "01_Intro".to_string();
+        slides[2].content = SlideContent::Svg(SVG.replace(
+            "</svg>",
+            r#"<text x="80" y="100" font-size="48">2025 Review</text></svg>"#,
+        ));
+        NativeOoxml::new()
+            .export(
+                &slides,
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        // 旧项目第 2 页有备注，替换后不应再读到
+        std::fs::create_dir_all(project.join("notes")).unwrap();
+        std::fs::write(project.join("notes/02.md"), "旧备注").unwrap();
+
+        let summary = import_into_project(&output, project, true).expect("导入应成功");
+        assert_eq!(summary.slides_written, 3);
+        let backups = std::fs::read_dir(project)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with("notes_backup_")
+            })
+            .count();
+        assert_eq!(backups, 1);
+
+        // 按文件名重新加载后页序不变
+        let reloaded = crate::load_slides(project, &PptxConfig::default()).expect("加载应成功");
+        let titles: Vec<_> = reloaded.iter().map(|slide| slide.title.as_str()).collect();
+        assert_eq!(titles, ["01_Intro", "02_slide_02", "03_2025_Review"]);
+        let notes: Vec<_> = reloaded
+            .iter()
+            .map(|slide| slide.notes.as_deref())
+            .collect();
+        assert!(notes[0].unwrap_or_default().starts_with("## 第 1 页"));
+        assert_eq!(notes[1], None);
+        assert!(notes[2].unwrap_or_default().starts_with("## 第 3 页"));
+
+        let many = Slide {
+            number: 7,
+            ..slides[0].clone()
+        };
+        assert_eq!(slide_stem(&many, 120), "007_01_Intro");
+    }
+
+    #[tokio::test]
+    async fn test_import_notes_into_project() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let project = temp_dir.path();
+        let output = project.join("deck.pptx");
+        NativeOoxml::new()
+            .export(
+                &deck(),
+                &output,
+                &PptxConfig::default(),
+                &NoProgress,
+                &CancellationToken::new(),
+            )
+            .await
+            .expect("导出应成功");
+
+        std::fs::create_dir_all(project.join("svg_final")).unwrap();
+        for name in ["01_cover", "02_agenda", "03_summary"] {
+            std::fs::write(project.join("svg_final").join(format!("{}.svg", name)), SVG).unwrap();
+        }
+
+        // 第 2 页原有备注，但 PPTX 中已删除；第 3 页的旧备注应被覆盖
+        std::fs::create_dir_all(project.join("notes")).unwrap();
+        std::fs::write(project.join("notes/02.md"), "旧备注").unwrap();
+        std::fs::write(project.join("notes/3.md"), "旧备注").unwrap();
+
+        let summary = import_into_project(&output, project, false).expect("导入应成功");
+        assert_eq!(summary.slide_count, 3);
+        assert_eq!(summary.slides_written, 0);
+        assert_eq!(summary.notes_written, 2);
+        assert_eq!(summary.notes_removed, 1);
+        assert_eq!(
+            crate::read_notes(&project.join("notes"), 2, "02_agenda").unwrap(),
+            None
+        );
+        assert!(!project.join("notes/3.md").exists());
+
+        let notes = crate::read_notes(&project.join("notes"), 3, "03_summary")
+            .unwrap()
+            .expect("应能读回备注");
+        assert!(notes.starts_with("## 第 3 页"));
+        assert!(!project.join("notes").join("02_02_agenda.md").exists());
+    }
+}
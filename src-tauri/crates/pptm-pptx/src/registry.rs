@@ -0,0 +1,376 @@
+// 导出器注册表
+// 后端在此登记名称、能力描述、可用性检测与创建方式，
+// 命令层按名称列出、描述和选择导出器，新增后端无需修改命令层
+
+use crate::backend::{
+    HtmlSlideshow, ImageFormat, ImageSequence, NativeOoxml, Odp, PdfExport, PptxGenSidecar, SvgMode,
+};
+use crate::{PptxBackend, PptxError, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// 未指定导出器时使用的默认导出器
+pub const DEFAULT_EXPORTER: &str = "pptxgen";
+
+/// 导出器创建参数
+///
+/// `values` 为请求中的后端专属选项（camelCase 键名），
+/// `base_dir` 用于解析 SVG 中以相对路径引用的图片
+#[derive(Debug, Clone, Default)]
+pub struct ExporterOptions {
+    values: Map<String, Value>,
+    base_dir: Option<PathBuf>,
+}
+
+impl ExporterOptions {
+    pub fn new(values: Map<String, Value>) -> Self {
+        Self {
+            values,
+            base_dir: None,
+        }
+    }
+
+    /// 设置解析相对图片路径的目录（通常为项目的 `svg_final` 目录）
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = Some(base_dir.into());
+        self
+    }
+
+    pub fn base_dir(&self) -> Option<&Path> {
+        self.base_dir.as_deref()
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.values.get(key).and_then(Value::as_str)
+    }
+
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.values.get(key).and_then(Value::as_bool)
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.values.get(key).and_then(Value::as_f64)
+    }
+
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.values.get(key).and_then(Value::as_u64)
+    }
+}
+
+/// 导出器的可用选项说明
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExporterOption {
+    /// 选项键名（camelCase）
+    pub key: String,
+    /// 选项说明
+    pub description: String,
+}
+
+/// 导出器描述（供界面展示）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExporterInfo {
+    /// 导出器名称（选择时使用）
+    pub id: String,
+    /// 显示名称
+    pub label: String,
+    /// 能力描述
+    pub description: String,
+    /// 缺省输出路径（相对项目目录）
+    pub default_output: String,
+    /// 支持的选项
+    pub options: Vec<ExporterOption>,
+    /// 不可用时需要满足的条件（如安装 Node.js）
+    pub requirement: Option<String>,
+    /// 当前是否可用
+    pub available: bool,
+}
+
+type Probe = Box<dyn Fn() -> bool + Send + Sync>;
+type Factory = Box<dyn Fn(&ExporterOptions) -> Result<Box<dyn PptxBackend>> + Send + Sync>;
+
+/// 注册表中的一个导出器
+pub struct ExporterEntry {
+    id: String,
+    label: String,
+    description: String,
+    default_output: String,
+    options: Vec<ExporterOption>,
+    requirement: Option<String>,
+    probe: Probe,
+    factory: Factory,
+}
+
+impl ExporterEntry {
+    /// 创建导出器条目，缺省总是可用、输出为 `output.pptx`
+    pub fn new<B, F>(id: impl Into<String>, label: impl Into<String>, factory: F) -> Self
+    where
+        B: PptxBackend + 'static,
+        F: Fn(&ExporterOptions) -> Result<B> + Send + Sync + 'static,
+    {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            description: String::new(),
+            default_output: "output.pptx".to_string(),
+            options: Vec::new(),
+            requirement: None,
+            probe: Box::new(|| true),
+            factory: Box::new(move |options| {
+                factory(options).map(|backend| Box::new(backend) as Box<dyn PptxBackend>)
+            }),
+        }
+    }
+
+    /// 设置能力描述
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// 设置缺省输出路径（相对项目目录）
+    pub fn with_default_output(mut self, default_output: impl Into<String>) -> Self {
+        self.default_output = default_output.into();
+        self
+    }
+
+    /// 登记一个支持的选项
+    pub fn with_option(mut self, key: impl Into<String>, description: impl Into<String>) -> Self {
+        self.options.push(ExporterOption {
+            key: key.into(),
+            description: description.into(),
+        });
+        self
+    }
+
+    /// 设置可用性检测及不可用时的提示
+    pub fn with_probe<P>(mut self, probe: P, requirement: impl Into<String>) -> Self
+    where
+        P: Fn() -> bool + Send + Sync + 'static,
+    {
+        self.probe = Box::new(probe);
+        self.requirement = Some(requirement.into());
+        self
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn default_output(&self) -> &str {
+        &self.default_output
+    }
+
+    pub fn is_available(&self) -> bool {
+        (self.probe)()
+    }
+
+    /// 生成描述（执行一次可用性检测）
+    pub fn info(&self) -> ExporterInfo {
+        ExporterInfo {
+            id: self.id.clone(),
+            label: self.label.clone(),
+            description: self.description.clone(),
+            default_output: self.default_output.clone(),
+            options: self.options.clone(),
+            requirement: self.requirement.clone(),
+            available: self.is_available(),
+        }
+    }
+}
+
+/// 导出器注册表
+///
+/// 按注册顺序保存导出器；同名导出器后注册的覆盖先注册的
+#[derive(Default)]
+pub struct ExporterRegistry {
+    entries: Vec<ExporterEntry>,
+}
+
+impl ExporterRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 创建包含全部内置导出器的注册表（PptxGenJS sidecar 使用缺省路径）
+    pub fn builtin() -> Self {
+        Self::with_pptxgen(PptxGenSidecar::new())
+    }
+
+    /// 创建包含全部内置导出器的注册表，PptxGenJS 导出使用指定的 sidecar
+    pub fn with_pptxgen(sidecar: PptxGenSidecar) -> Self {
+        let mut registry = Self::new();
+
+        // 所有 PptxGenJS 导出共用同一个常驻 sidecar 进程
+        let probe = sidecar.clone();
+        registry.register(
+            ExporterEntry::new("pptxgen", "PptxGenJS", move |_| Ok(sidecar.clone()))
+                .with_description(
+                    "通过 Node.js sidecar 调用 PptxGenJS 生成 PPTX，幻灯片为整页图片，不含切换效果",
+                )
+                .with_probe(
+                    move || probe.is_available(),
+                    "请确保已安装 Node.js，并在 sidecars/pptxgen 目录执行 npm install",
+                ),
+        );
+        registry.register(
+            ExporterEntry::new("native", "原生 PPTX", |options| {
+                let svg_mode = match options.get_str("svgMode") {
+                    Some("svg") => SvgMode::SvgBlip,
+                    _ => SvgMode::Shapes,
+                };
+                Ok(NativeOoxml::new().with_svg_mode(svg_mode))
+            })
+            .with_description(
+                "Rust 原生生成 PPTX，SVG 转换为可编辑形状，支持备注、切换效果、动画、分节与原生图表",
+            )
+            .with_option(
+                "svgMode",
+                "SVG 导出模式：shapes（可编辑形状，默认）或 svg（嵌入 SVG 图片）",
+            ),
+        );
+        registry.register(
+            ExporterEntry::new("odp", "OpenDocument 演示文稿", |options| {
+                let backend =
+                    Odp::new().with_embed_svg(options.get_bool("embedSvg").unwrap_or(true));
+                Ok(match options.base_dir() {
+                    Some(dir) => backend.with_base_dir(dir),
+                    None => backend,
+                })
+            })
+            .with_description("生成 LibreOffice/WPS 可打开的 ODP，包含备注与切换效果")
+            .with_default_output("output.odp")
+            .with_option("embedSvg", "是否嵌入 SVG 原图（默认 true，否则只嵌入 PNG）"),
+        );
+        registry.register(
+            ExporterEntry::new("pdf", "PDF", |options| {
+                Ok(PdfExport::new()
+                    .with_scale(options.get_f64("pdfScale").unwrap_or(2.0) as f32)
+                    .with_notes_pages(options.get_bool("notesPages").unwrap_or(false)))
+            })
+            .with_description("将幻灯片渲染为高分辨率位图写入 PDF，可附加备注页")
+            .with_default_output("output.pdf")
+            .with_option("pdfScale", "渲染倍率（1.0 ~ 4.0，默认 2.0）")
+            .with_option("notesPages", "是否附加备注页"),
+        );
+        registry.register(
+            ExporterEntry::new("images", "图片序列", |options| {
+                let format_name = options.get_str("imageFormat").unwrap_or("png");
+                let quality = options
+                    .get_u64("imageQuality")
+                    .map(|quality| quality.min(100) as u8);
+                let format = ImageFormat::parse(format_name, quality).ok_or_else(|| {
+                    PptxError::Backend(format!("未知的图片格式: {}", format_name))
+                })?;
+                let scale = options
+                    .get_u64("imageScale")
+                    .map_or(1, |scale| u32::try_from(scale).unwrap_or(u32::MAX));
+                Ok(ImageSequence::new()
+                    .with_format(format)
+                    .with_scale(scale)
+                    .with_zip(options.get_bool("zip").unwrap_or(false)))
+            })
+            .with_description("每页导出为编号的 PNG/JPEG 图片，可打包为 zip，适合社交媒体发布")
+            .with_default_output("images")
+            .with_option("imageFormat", "图片格式：png（默认）或 jpeg")
+            .with_option("imageQuality", "JPEG 质量（1-100，默认 90）")
+            .with_option("imageScale", "导出倍率（1/2/3，默认 1）")
+            .with_option("zip", "是否同时打包为 zip"),
+        );
+        registry.register(
+            ExporterEntry::new("html", "HTML 放映", |options| {
+                let backend = match options.base_dir() {
+                    Some(dir) => HtmlSlideshow::new().with_base_dir(dir),
+                    None => HtmlSlideshow::new(),
+                };
+                Ok(backend)
+            })
+            .with_description("生成单个离线 HTML 文件，支持键盘翻页、全屏、切换效果与演讲者视图")
+            .with_default_output("output.html"),
+        );
+
+        registry
+    }
+
+    /// 注册导出器（同名导出器被替换）
+    pub fn register(&mut self, entry: ExporterEntry) {
+        match self.entries.iter_mut().find(|e| e.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ExporterEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// 列出全部导出器的描述
+    pub fn list(&self) -> Vec<ExporterInfo> {
+        self.entries.iter().map(ExporterEntry::info).collect()
+    }
+
+    /// 按名称创建导出后端（未知或不可用时返回错误）
+    pub fn create(&self, id: &str, options: &ExporterOptions) -> Result<Box<dyn PptxBackend>> {
+        let entry = self
+            .get(id)
+            .ok_or_else(|| PptxError::Backend(format!("未知的导出器: {}", id)))?;
+
+        if !entry.is_available() {
+            return Err(PptxError::Backend(match &entry.requirement {
+                Some(requirement) => format!("{} 不可用，{}", entry.label, requirement),
+                None => format!("{} 不可用", entry.label),
+            }));
+        }
+
+        (entry.factory)(options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_list_and_create() {
+        let mut registry = ExporterRegistry::builtin();
+        let ids: Vec<String> = registry.list().into_iter().map(|info| info.id).collect();
+        assert_eq!(ids, ["pptxgen", "native", "odp", "pdf", "images", "html"]);
+
+        let pdf = registry.get("pdf").expect("应注册 PDF 导出器").info();
+        assert_eq!(pdf.default_output, "output.pdf");
+        assert!(pdf.available);
+        assert!(pdf.options.iter().any(|option| option.key == "notesPages"));
+
+        let options: Map<String, Value> =
+            serde_json::from_str(r#"{"imageFormat": "jpeg", "imageQuality": 80}"#).unwrap();
+        let backend = registry
+            .create("images", &ExporterOptions::new(options))
+            .expect("应能创建图片导出器");
+        assert_eq!(backend.name(), "images");
+
+        let options: Map<String, Value> =
+            serde_json::from_str(r#"{"imageFormat": "gif"}"#).unwrap();
+        assert!(registry
+            .create("images", &ExporterOptions::new(options))
+            .is_err());
+        assert!(registry
+            .create("keynote", &ExporterOptions::default())
+            .is_err());
+
+        // 同名注册替换原条目，不可用的导出器无法创建
+        registry.register(
+            ExporterEntry::new("html", "HTML", |_| Ok(HtmlSlideshow::new()))
+                .with_probe(|| false, "需要浏览器"),
+        );
+        assert_eq!(registry.list().len(), 6);
+        let info = registry.get("html").unwrap().info();
+        assert!(!info.available);
+        assert_eq!(info.requirement.as_deref(), Some("需要浏览器"));
+        match registry.create("html", &ExporterOptions::default()) {
+            Err(PptxError::Backend(message)) => assert_eq!(message, "HTML 不可用，需要浏览器"),
+            _ => panic!("不可用的导出器应返回错误"),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// 幻灯片分节
+// 分节来自项目元数据（.pptm-meta.json）中的声明，未声明时按 svg_final 文件名前缀推断
+
+use crate::Slide;
+use pptm_domain::{SectionSpec, PROJECT_METADATA_FILE};
+use serde::Deserialize;
+use std::path::Path;
+
+/// 未归入任何声明分节的开头幻灯片所在的节
+pub const DEFAULT_SECTION_NAME: &str = "默认节";
+
+/// 连续的一组幻灯片
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// 节名称
+    pub name: String,
+    /// 幻灯片下标（在幻灯片列表中的位置）
+    pub slides: Vec<usize>,
+}
+
+/// 元数据中与分节相关的部分（其余字段可缺省）
+#[derive(Debug, Default, Deserialize)]
+struct SectionManifest {
+    #[serde(default)]
+    sections: Vec<SectionSpec>,
+}
+
+/// 读取项目元数据中声明的分节，元数据不存在时返回空列表
+///
+/// 元数据无法读取或格式错误时记录警告并返回空列表（改为按文件名推断），
+/// 不影响未使用分节的项目导出
+pub fn load_manifest_sections(project_path: &Path) -> Vec<SectionSpec> {
+    let path = project_path.join(PROJECT_METADATA_FILE);
+    if !path.exists() {
+        return Vec::new();
+    }
+
+    let manifest = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_json::from_str::<SectionManifest>(&text).map_err(|e| e.to_string()));
+    match manifest {
+        Ok(manifest) => manifest.sections,
+        Err(e) => {
+            tracing::warn!(
+                "无法读取项目元数据 {}，按文件名推断分节: {}",
+                path.display(),
+                e
+            );
+            Vec::new()
+        }
+    }
+}
+
+/// 为幻灯片设置所属分节
+///
+/// 声明的分节优先；未列出的幻灯片沿用上一页的分节。没有声明时按文件名前缀
+/// `{章}-{页}_标题`（如 `02-01_市场概览`）推断，同一章的幻灯片归为一节，
+/// 节名称取该章第一页的标题
+pub fn assign_sections(slides: &mut [Slide], manifest: &[SectionSpec]) {
+    if manifest.is_empty() {
+        infer_sections(slides);
+        return;
+    }
+
+    let mut current: Option<String> = None;
+    for slide in slides.iter_mut() {
+        if let Some(spec) = manifest.iter().find(|spec| {
+            spec.slides
+                .iter()
+                .any(|name| name.trim_end_matches(".svg") == slide.title)
+        }) {
+            current = Some(spec.name.clone());
+        }
+        slide.section = current.clone();
+    }
+}
+
+fn infer_sections(slides: &mut [Slide]) {
+    let mut current: Option<(String, String)> = None;
+
+    for slide in slides.iter_mut() {
+        if let Some((chapter, title)) = chapter_prefix(&slide.title) {
+            if current
+                .as_ref()
+                .map(|(current_chapter, _)| current_chapter.as_str())
+                != Some(chapter)
+            {
+                current = Some((chapter.to_string(), title.to_string()));
+            }
+        }
+        slide.section = current.as_ref().map(|(_, name)| name.clone());
+    }
+}
+
+/// 解析 `{章}-{页}_标题`，返回章号与标题
+fn chapter_prefix(stem: &str) -> Option<(&str, &str)> {
+    let (prefix, title) = stem.split_once('_')?;
+    let (chapter, page) = prefix.split_once('-')?;
+
+    let is_number = |text: &str| !text.is_empty() && text.chars().all(|c| c.is_ascii_digit());
+    (is_number(chapter) && is_number(page) && !title.is_empty()).then_some((chapter, title))
+}
+
+/// 将幻灯片按所属分节分组为连续的节
+///
+/// 没有任何幻灯片设置分节时返回空列表；开头未分节的幻灯片归入默认节
+pub fn group_sections(slides: &[Slide]) -> Vec<Section> {
+    if slides.iter().all(|slide| slide.section.is_none()) {
+        return Vec::new();
+    }
+
+    let mut sections: Vec<Section> = Vec::new();
+    for (index, slide) in slides.iter().enumerate() {
+        let name = slide.section.as_deref().unwrap_or(DEFAULT_SECTION_NAME);
+        match sections.last_mut() {
+            Some(section) if section.name == name => section.slides.push(index),
+            _ => sections.push(Section {
+                name: name.to_string(),
+                slides: vec![index],
+            }),
+        }
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SlideContent;
+
+    fn slides(titles: &[&str]) -> Vec<Slide> {
+        titles
+            .iter()
+            .enumerate()
+            .map(|(index, title)| Slide {
+                number: index + 1,
+                title: title.to_string(),
+                content: SlideContent::Svg(String::new()),
+                notes: None,
+                transition: None,
+                animation: None,
+                section: None,
+                source_svg: None,
+            })
+            .collect()
+    }
+
+    fn names(slides: &[Slide]) -> Vec<Option<&str>> {
+        slides
+            .iter()
+            .map(|slide| slide.section.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_infer_sections_from_prefix() {
+        let mut deck = slides(&[
+            "01-01_开场",
+            "01-02_目录",
+            "02-01_市场概览",
+            "02-02_份额",
+            "附录",
+        ]);
+        assign_sections(&mut deck, &[]);
+
+        assert_eq!(
+            names(&deck),
+            vec![
+                Some("开场"),
+                Some("开场"),
+                Some("市场概览"),
+                Some("市场概览"),
+                Some("市场概览"),
+            ]
+        );
+
+        let mut plain = slides(&["slide_01_cover", "slide_02_agenda"]);
+        assign_sections(&mut plain, &[]);
+        assert!(group_sections(&plain).is_empty());
+    }
+
+    #[test]
+    fn test_manifest_sections() {
+        let mut deck = slides(&["P01_封面", "P02_背景", "P03_现状", "P04_方案", "P05_致谢"]);
+        let manifest = vec![
+            SectionSpec {
+                name: "背景".to_string(),
+                slides: vec!["P02_背景.svg".to_string(), "P03_现状".to_string()],
+            },
+            SectionSpec {
+                name: "方案".to_string(),
+                slides: vec!["P04_方案".to_string()],
+            },
+        ];
+        assign_sections(&mut deck, &manifest);
+
+        assert_eq!(
+            group_sections(&deck),
+            vec![
+                Section {
+                    name: DEFAULT_SECTION_NAME.to_string(),
+                    slides: vec![0],
+                },
+                Section {
+                    name: "背景".to_string(),
+                    slides: vec![1, 2],
+                },
+                Section {
+                    name: "方案".to_string(),
+                    slides: vec![3, 4],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed_manifest_falls_back_to_inference() {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        std::fs::write(
+            temp_dir.path().join(PROJECT_METADATA_FILE),
+            "{\"sections\": [",
+        )
+        .unwrap();
+        let manifest = load_manifest_sections(temp_dir.path());
+        assert!(manifest.is_empty());
+
+        let mut deck = slides(&["01-01_市场", "01-02_规模", "02-01_方案"]);
+        assign_sections(&mut deck, &manifest);
+        let names: Vec<_> = group_sections(&deck)
+            .into_iter()
+            .map(|section| section.name)
+            .collect();
+        assert_eq!(names, ["市场", "方案"]);
+    }
+}
@@ -0,0 +1,663 @@
+// Sidecar 会话
+// 与常驻 sidecar 进程通过按行分隔的 JSON-RPC 2.0 通信：启动时握手校验协议版本，
+// 并发读取 stdout 与 stderr，每个请求带超时与取消，进程崩溃或被终止后下次请求自动重启
+
+use crate::{PptxError, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+/// 协议版本（与 sidecar 的 `initialize` 响应比对）
+pub const PROTOCOL_VERSION: u64 = 1;
+
+/// 握手超时
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// 错误信息中保留的 stderr 行数
+const STDERR_TAIL_LINES: usize = 20;
+
+/// 进程退出后等待 stderr 读完的最长时间
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 放弃请求后等待 sidecar 结束该请求的默认宽限时间
+pub const DEFAULT_CANCEL_GRACE: Duration = Duration::from_secs(5);
+
+/// sidecar 启动命令
+#[derive(Debug, Clone)]
+pub struct SidecarCommand {
+    program: PathBuf,
+    args: Vec<String>,
+    envs: Vec<(String, String)>,
+}
+
+impl SidecarCommand {
+    pub fn new(program: impl Into<PathBuf>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            envs: Vec::new(),
+        }
+    }
+
+    /// 追加命令行参数
+    pub fn with_arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// 设置环境变量
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.envs.push((key.into(), value.into()));
+        self
+    }
+}
+
+/// 握手返回的 sidecar 信息
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarInfo {
+    pub protocol_version: u64,
+    pub name: String,
+    pub version: String,
+}
+
+/// sidecar 处理请求期间发出的通知（如进度）
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub method: String,
+    pub params: Value,
+}
+
+/// sidecar 发来的一条消息
+#[derive(Debug, Deserialize)]
+struct Incoming {
+    id: Option<u64>,
+    method: Option<String>,
+    #[serde(default)]
+    params: Value,
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+/// 等待响应的请求：按请求 ID 转发响应与通知
+type Pending = Arc<Mutex<HashMap<u64, mpsc::UnboundedSender<Incoming>>>>;
+
+/// 一个 sidecar 进程
+struct Session {
+    child: Mutex<Child>,
+    stdin: tokio::sync::Mutex<ChildStdin>,
+    pending: Pending,
+    next_id: AtomicU64,
+    alive: Arc<AtomicBool>,
+    stderr: Arc<Mutex<VecDeque<String>>>,
+    /// stderr 已读到结尾（进程退出前的输出都已收集）
+    stderr_closed: CancellationToken,
+    /// 放弃请求后等待 sidecar 确认的时间
+    cancel_grace: Duration,
+}
+
+impl Session {
+    /// 启动进程并开始并发读取 stdout 与 stderr
+    fn spawn(command: &SidecarCommand, cancel_grace: Duration) -> Result<Self> {
+        let mut child = Command::new(&command.program)
+            .args(&command.args)
+            .envs(command.envs.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                PptxError::Backend(format!(
+                    "无法启动 sidecar {}: {}",
+                    command.program.display(),
+                    e
+                ))
+            })?;
+
+        let (Some(stdin), Some(stdout), Some(stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            return Err(PptxError::Backend("无法连接 sidecar 标准流".to_string()));
+        };
+
+        let pending: Pending = Arc::default();
+        let alive = Arc::new(AtomicBool::new(true));
+        let stderr_tail = Arc::new(Mutex::new(VecDeque::with_capacity(STDERR_TAIL_LINES)));
+        let stderr_closed = CancellationToken::new();
+
+        tokio::spawn(read_stdout(stdout, pending.clone(), alive.clone()));
+        tokio::spawn(read_stderr(
+            stderr,
+            stderr_tail.clone(),
+            stderr_closed.clone(),
+        ));
+
+        Ok(Self {
+            child: Mutex::new(child),
+            stdin: tokio::sync::Mutex::new(stdin),
+            pending,
+            next_id: AtomicU64::new(1),
+            alive,
+            stderr: stderr_tail,
+            stderr_closed,
+            cancel_grace,
+        })
+    }
+
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    /// 终止进程，并立即标记为已退出（不等待 stdout 关闭）
+    fn kill(&self) {
+        self.alive.store(false, Ordering::SeqCst);
+        if let Ok(mut child) = self.child.lock() {
+            let _ = child.start_kill();
+        }
+    }
+
+    /// 发送请求并等待响应，期间收到的通知交给 `on_notify`
+    ///
+    /// 超时或取消时只放弃本请求，见 [`Session::abandon`]
+    async fn request<F>(
+        self: &Arc<Self>,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+        cancel: &CancellationToken,
+        on_notify: F,
+    ) -> Result<Value>
+    where
+        F: FnMut(Notification) + Send,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        self.pending.lock().unwrap().insert(id, sender);
+        let guard = PendingGuard {
+            pending: self.pending.clone(),
+            id,
+        };
+
+        // 登记后再检查，避免进程刚退出时请求无人应答
+        if !self.is_alive() {
+            return Err(self.exited_error().await);
+        }
+
+        self.send(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "method": method,
+            "params": params,
+        }))
+        .await?;
+
+        let error = tokio::select! {
+            result = self.wait(&mut receiver, on_notify) => return result,
+            _ = cancel.cancelled() => PptxError::Cancelled,
+            _ = tokio::time::sleep(timeout) => PptxError::Backend(format!(
+                "sidecar 请求 {} 超时（{} 秒）",
+                method,
+                timeout.as_secs_f32()
+            )),
+        };
+        self.abandon(guard, receiver);
+        Err(error)
+    }
+
+    /// 写入一条消息
+    async fn send(&self, message: Value) -> Result<()> {
+        let mut line = serde_json::to_string(&message)?;
+        line.push('\n');
+
+        let written = {
+            let mut stdin = self.stdin.lock().await;
+            match stdin.write_all(line.as_bytes()).await {
+                Ok(()) => stdin.flush().await,
+                Err(e) => Err(e),
+            }
+        };
+        match written {
+            Ok(()) => Ok(()),
+            Err(_) => Err(self.exited_error().await),
+        }
+    }
+
+    /// 放弃请求：通知 sidecar 取消，并在后台等待它结束该请求
+    ///
+    /// 进程为多个请求共用，sidecar 在宽限时间内结束该请求（返回结果或取消错误）时保留进程，
+    /// 下次导出无需重启；否则视为无响应，终止进程（下次请求时重启）
+    fn abandon(
+        self: &Arc<Self>,
+        guard: PendingGuard,
+        mut receiver: mpsc::UnboundedReceiver<Incoming>,
+    ) {
+        let session = self.clone();
+        tokio::spawn(async move {
+            let acknowledged = async {
+                session
+                    .send(serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "method": "cancel",
+                        "params": { "requestId": guard.id },
+                    }))
+                    .await
+                    .ok()?;
+                // 跳过取消前发出的通知，收到响应即为确认
+                while let Some(message) = receiver.recv().await {
+                    if message.id.is_some() {
+                        return Some(());
+                    }
+                }
+                None
+            };
+
+            match tokio::time::timeout(session.cancel_grace, acknowledged).await {
+                Ok(Some(())) => {}
+                _ => session.kill(),
+            }
+            drop(guard);
+        });
+    }
+
+    /// 等待响应，期间收到的通知交给 `on_notify`
+    async fn wait<F>(
+        &self,
+        receiver: &mut mpsc::UnboundedReceiver<Incoming>,
+        mut on_notify: F,
+    ) -> Result<Value>
+    where
+        F: FnMut(Notification) + Send,
+    {
+        while let Some(message) = receiver.recv().await {
+            match message {
+                Incoming {
+                    id: None,
+                    method: Some(method),
+                    params,
+                    ..
+                } => on_notify(Notification { method, params }),
+                Incoming {
+                    error: Some(error), ..
+                } => {
+                    return Err(PptxError::Backend(format!(
+                        "sidecar 返回错误 ({}): {}",
+                        error.code, error.message
+                    )))
+                }
+                Incoming { result, .. } => return Ok(result.unwrap_or(Value::Null)),
+            }
+        }
+
+        Err(self.exited_error().await)
+    }
+
+    /// 进程退出的错误信息（附带最近的 stderr 输出）
+    ///
+    /// 先等待 stderr 读到结尾，崩溃前输出的信息才能完整收集
+    async fn exited_error(&self) -> PptxError {
+        let _ = tokio::time::timeout(STDERR_DRAIN_TIMEOUT, self.stderr_closed.cancelled()).await;
+        let tail = self.stderr.lock().unwrap();
+        if tail.is_empty() {
+            PptxError::Backend("sidecar 进程意外退出".to_string())
+        } else {
+            PptxError::Backend(format!(
+                "sidecar 进程意外退出\nstderr: {}",
+                tail.iter().cloned().collect::<Vec<_>>().join("\n")
+            ))
+        }
+    }
+}
+
+/// 请求结束时移除登记（放弃的请求在 sidecar 确认或终止进程后移除）
+struct PendingGuard {
+    pending: Pending,
+    id: u64,
+}
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&self.id);
+        }
+    }
+}
+
+/// 逐行读取 stdout，将响应与通知转发给对应请求；进程退出时结束所有等待
+async fn read_stdout(stdout: impl AsyncRead + Unpin, pending: Pending, alive: Arc<AtomicBool>) {
+    let mut lines = BufReader::new(stdout).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        // 忽略非 JSON-RPC 输出
+        let Ok(message) = serde_json::from_str::<Incoming>(&line) else {
+            continue;
+        };
+        // 通知通过 `params.requestId` 指明所属请求
+        let request_id = match (&message.id, &message.method) {
+            (Some(id), None) => Some(*id),
+            (None, Some(_)) => message.params.get("requestId").and_then(Value::as_u64),
+            _ => None,
+        };
+        if let Some(sender) = request_id.and_then(|id| pending.lock().unwrap().get(&id).cloned()) {
+            let _ = sender.send(message);
+        }
+    }
+
+    alive.store(false, Ordering::SeqCst);
+    pending.lock().unwrap().clear();
+}
+
+/// 持续读取 stderr，只保留最近几行，避免管道写满阻塞 sidecar；读到结尾时触发 `closed`
+async fn read_stderr(
+    stderr: impl AsyncRead + Unpin,
+    tail: Arc<Mutex<VecDeque<String>>>,
+    closed: CancellationToken,
+) {
+    let mut lines = BufReader::new(stderr).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let mut tail = tail.lock().unwrap();
+        if tail.len() == STDERR_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+    closed.cancel();
+}
+
+/// 存活的进程及其握手信息
+#[derive(Clone)]
+struct Live {
+    session: Arc<Session>,
+    info: SidecarInfo,
+}
+
+/// 常驻 sidecar 客户端
+///
+/// 首次请求时启动进程并握手，之后复用同一进程；
+/// 进程退出或被终止（放弃的请求在宽限时间内未结束）后，下次请求重新启动
+pub struct SidecarClient {
+    command: SidecarCommand,
+    cancel_grace: Duration,
+    live: tokio::sync::Mutex<Option<Live>>,
+}
+
+impl SidecarClient {
+    pub fn new(command: SidecarCommand) -> Self {
+        Self {
+            command,
+            cancel_grace: DEFAULT_CANCEL_GRACE,
+            live: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    /// 设置放弃请求后等待 sidecar 结束该请求的时间，超过后终止进程
+    pub fn with_cancel_grace(mut self, grace: Duration) -> Self {
+        self.cancel_grace = grace;
+        self
+    }
+
+    /// 返回存活的进程，必要时启动并握手
+    async fn live(&self) -> Result<Live> {
+        let mut live = self.live.lock().await;
+        if let Some(current) = live.as_ref().filter(|current| current.session.is_alive()) {
+            return Ok(current.clone());
+        }
+
+        let session = Arc::new(Session::spawn(&self.command, self.cancel_grace)?);
+        let result = session
+            .request(
+                "initialize",
+                serde_json::json!({ "protocolVersion": PROTOCOL_VERSION }),
+                HANDSHAKE_TIMEOUT,
+                &CancellationToken::new(),
+                |_| {},
+            )
+            .await;
+        let info: SidecarInfo = match result.and_then(|value| Ok(serde_json::from_value(value)?)) {
+            Ok(info) => info,
+            Err(e) => {
+                session.kill();
+                return Err(e);
+            }
+        };
+        if info.protocol_version != PROTOCOL_VERSION {
+            session.kill();
+            return Err(PptxError::Backend(format!(
+                "sidecar 协议版本不兼容: 需要 {}，{} {} 为 {}",
+                PROTOCOL_VERSION, info.name, info.version, info.protocol_version
+            )));
+        }
+
+        let current = Live { session, info };
+        *live = Some(current.clone());
+        Ok(current)
+    }
+
+    /// 启动（如尚未启动）并返回握手信息
+    pub async fn info(&self) -> Result<SidecarInfo> {
+        Ok(self.live().await?.info)
+    }
+
+    /// 发送请求并等待响应
+    pub async fn request(&self, method: &str, params: Value, timeout: Duration) -> Result<Value> {
+        self.request_with_notifications(method, params, timeout, &CancellationToken::new(), |_| {})
+            .await
+    }
+
+    /// 发送请求，期间收到的通知交给 `on_notify`
+    ///
+    /// 超时或 `cancel` 触发时放弃本请求并返回错误（取消时为 [`PptxError::Cancelled`]），
+    /// 同一进程上的其他请求不受影响
+    pub async fn request_with_notifications<F>(
+        &self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+        cancel: &CancellationToken,
+        on_notify: F,
+    ) -> Result<Value>
+    where
+        F: FnMut(Notification) + Send,
+    {
+        let live = self.live().await?;
+        live.session
+            .request(method, params, timeout, cancel, on_notify)
+            .await
+    }
+
+    /// 终止当前进程（下次请求时重新启动）
+    pub async fn shutdown(&self) {
+        if let Some(live) = self.live.lock().await.take() {
+            live.session.kill();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stub_command() -> Option<SidecarCommand> {
+        // 测试 sidecar 需要 Node.js
+        let available = std::process::Command::new("node")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| output.status.success());
+        available.then(|| {
+            SidecarCommand::new("node").with_arg(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/stub_sidecar.cjs"
+            ))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_sidecar_session() {
+        let Some(command) = stub_command() else {
+            eprintln!("未找到 Node.js，跳过 sidecar 测试");
+            return;
+        };
+        let timeout = Duration::from_secs(10);
+        let client = SidecarClient::new(command.clone());
+
+        let info = client.info().await.expect("握手应成功");
+        assert_eq!(info.protocol_version, PROTOCOL_VERSION);
+        assert_eq!(info.name, "stub-sidecar");
+
+        let echo = client
+            .request("echo", serde_json::json!({ "text": "你好" }), timeout)
+            .await
+            .expect("请求应成功");
+        assert_eq!(echo["text"], "你好");
+
+        // 大量 stderr 输出不会阻塞 sidecar
+        let noisy = client
+            .request("noisy", Value::Null, timeout)
+            .await
+            .expect("请求应成功");
+        assert_eq!(noisy, "ok");
+
+        let mut progress = Vec::new();
+        client
+            .request_with_notifications(
+                "progress",
+                serde_json::json!({ "total": 3 }),
+                timeout,
+                &CancellationToken::new(),
+                |notification| progress.push(notification.params["current"].clone()),
+            )
+            .await
+            .expect("请求应成功");
+        assert_eq!(progress, [1, 2, 3]);
+
+        match client.request("missing", Value::Null, timeout).await {
+            Err(PptxError::Backend(message)) => assert!(message.contains("-32601")),
+            other => panic!("未知方法应返回错误: {:?}", other),
+        }
+
+        // 版本不兼容时握手失败
+        let client = SidecarClient::new(command.with_env("STUB_PROTOCOL_VERSION", "2"));
+        assert!(client.info().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sidecar_timeout_and_restart() {
+        let Some(command) = stub_command() else {
+            eprintln!("未找到 Node.js，跳过 sidecar 测试");
+            return;
+        };
+        let timeout = Duration::from_secs(10);
+        let grace = Duration::from_millis(200);
+        let client = SidecarClient::new(command).with_cancel_grace(grace);
+        let first_pid = client.request("pid", Value::Null, timeout).await.unwrap();
+
+        // 崩溃时返回 stderr，下次请求启动新进程
+        match client.request("crash", Value::Null, timeout).await {
+            Err(PptxError::Backend(message)) => assert!(message.contains("模拟崩溃")),
+            other => panic!("崩溃应返回错误: {:?}", other),
+        }
+        let second_pid = client.request("pid", Value::Null, timeout).await.unwrap();
+        assert_ne!(first_pid, second_pid);
+
+        // 超时后 sidecar 未在宽限时间内结束请求，终止进程并重启
+        let result = client
+            .request("sleep", Value::Null, Duration::from_millis(200))
+            .await;
+        assert!(matches!(result, Err(PptxError::Backend(message)) if message.contains("超时")));
+        tokio::time::sleep(grace * 3).await;
+        let third_pid = client.request("pid", Value::Null, timeout).await.unwrap();
+        assert_ne!(second_pid, third_pid);
+    }
+
+    #[tokio::test]
+    async fn test_sidecar_cancel_keeps_process() {
+        let Some(command) = stub_command() else {
+            eprintln!("未找到 Node.js，跳过 sidecar 测试");
+            return;
+        };
+        let timeout = Duration::from_secs(10);
+        let grace = Duration::from_millis(500);
+        let client = SidecarClient::new(command).with_cancel_grace(grace);
+        let pid = client.request("pid", Value::Null, timeout).await.unwrap();
+
+        // 唯一的请求被取消：sidecar 确认取消后进程保留
+        let cancel = CancellationToken::new();
+        let (cancelled, ()) = tokio::join!(
+            client.request_with_notifications(
+                "delay",
+                serde_json::json!({ "ms": 5000 }),
+                timeout,
+                &cancel,
+                |_| {},
+            ),
+            async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                cancel.cancel();
+            }
+        );
+        assert!(matches!(cancelled, Err(PptxError::Cancelled)));
+        tokio::time::sleep(grace * 2).await;
+        assert_eq!(
+            client.request("pid", Value::Null, timeout).await.unwrap(),
+            pid
+        );
+
+        // 同一进程上并发两个请求，只取消其中一个
+        let cancel = CancellationToken::new();
+        let cancelled = client.request_with_notifications(
+            "delay",
+            serde_json::json!({ "ms": 5000 }),
+            timeout,
+            &cancel,
+            |_| {},
+        );
+        let delayed = client.request(
+            "delay",
+            serde_json::json!({ "ms": 300, "value": "done" }),
+            timeout,
+        );
+        let (cancelled, delayed, ()) = tokio::join!(cancelled, delayed, async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+        assert!(matches!(cancelled, Err(PptxError::Cancelled)));
+        assert_eq!(delayed.expect("未取消的请求应成功")["value"], "done");
+
+        // 超时同样只放弃本请求
+        let slow = client.request(
+            "delay",
+            serde_json::json!({ "ms": 5000 }),
+            Duration::from_millis(100),
+        );
+        let delayed = client.request("delay", serde_json::json!({ "ms": 300 }), timeout);
+        let (slow, delayed) = tokio::join!(slow, delayed);
+        assert!(matches!(slow, Err(PptxError::Backend(message)) if message.contains("超时")));
+        assert!(delayed.is_ok());
+
+        // 进程未被终止，sidecar 收到了全部取消通知
+        tokio::time::sleep(grace * 2).await;
+        assert_eq!(
+            client.request("pid", Value::Null, timeout).await.unwrap(),
+            pid
+        );
+        let received = client
+            .request("cancelled", Value::Null, timeout)
+            .await
+            .unwrap();
+        assert_eq!(received.as_array().map(Vec::len), Some(3));
+    }
+}
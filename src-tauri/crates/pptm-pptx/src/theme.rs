@@ -0,0 +1,601 @@
+// 主题部件
+// 生成 ppt/theme/theme1.xml（配色方案、字体方案与格式方案），
+// 配色与字体可从项目的设计规范（设计规范与内容大纲.md）中解析
+
+use crate::{escape_xml, Result};
+use std::path::Path;
+
+/// 演示文稿主题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    /// 主题名称
+    pub name: String,
+    /// 深色 1（正文文字），十六进制 RGB，例如 `000000`
+    pub dark1: String,
+    /// 浅色 1（背景）
+    pub light1: String,
+    /// 深色 2
+    pub dark2: String,
+    /// 浅色 2
+    pub light2: String,
+    /// 强调色 1-6
+    pub accents: [String; 6],
+    /// 超链接颜色
+    pub hyperlink: String,
+    /// 已访问超链接颜色
+    pub followed_hyperlink: String,
+    /// 标题字体（拉丁）
+    pub major_font: String,
+    /// 正文字体（拉丁）
+    pub minor_font: String,
+    /// 标题字体（东亚）
+    pub major_east_asian_font: String,
+    /// 正文字体（东亚）
+    pub minor_east_asian_font: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: "Office Theme".to_string(),
+            dark1: "000000".to_string(),
+            light1: "FFFFFF".to_string(),
+            dark2: "44546A".to_string(),
+            light2: "E7E6E6".to_string(),
+            accents: [
+                "4472C4".to_string(),
+                "ED7D31".to_string(),
+                "A5A5A5".to_string(),
+                "FFC000".to_string(),
+                "5B9BD5".to_string(),
+                "70AD47".to_string(),
+            ],
+            hyperlink: "0563C1".to_string(),
+            followed_hyperlink: "954F72".to_string(),
+            major_font: "Calibri Light".to_string(),
+            minor_font: "Calibri".to_string(),
+            major_east_asian_font: "微软雅黑".to_string(),
+            minor_east_asian_font: "微软雅黑".to_string(),
+        }
+    }
+}
+
+impl Theme {
+    /// 生成主题部件 XML
+    pub fn to_xml(&self) -> String {
+        let color = |name: &str, value: &str| {
+            format!(
+                r#"<a:{name}><a:srgbClr val="{}"/></a:{name}>"#,
+                escape_xml(value)
+            )
+        };
+
+        let mut colors = String::new();
+        colors.push_str(&color("dk1", &self.dark1));
+        colors.push_str(&color("lt1", &self.light1));
+        colors.push_str(&color("dk2", &self.dark2));
+        colors.push_str(&color("lt2", &self.light2));
+        for (index, accent) in self.accents.iter().enumerate() {
+            colors.push_str(&color(&format!("accent{}", index + 1), accent));
+        }
+        colors.push_str(&color("hlink", &self.hyperlink));
+        colors.push_str(&color("folHlink", &self.followed_hyperlink));
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="{name}">
+  <a:themeElements>
+    <a:clrScheme name="{name}">{colors}</a:clrScheme>
+    <a:fontScheme name="{name}">
+      <a:majorFont><a:latin typeface="{major}"/><a:ea typeface="{major_ea}"/><a:cs typeface=""/></a:majorFont>
+      <a:minorFont><a:latin typeface="{minor}"/><a:ea typeface="{minor_ea}"/><a:cs typeface=""/></a:minorFont>
+    </a:fontScheme>
+    <a:fmtScheme name="{name}">
+      <a:fillStyleLst>
+        <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+        <a:solidFill><a:schemeClr val="phClr"><a:tint val="50000"/></a:schemeClr></a:solidFill>
+        <a:solidFill><a:schemeClr val="phClr"><a:shade val="80000"/></a:schemeClr></a:solidFill>
+      </a:fillStyleLst>
+      <a:lnStyleLst>
+        <a:ln w="6350"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>
+        <a:ln w="12700"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>
+        <a:ln w="19050"><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:ln>
+      </a:lnStyleLst>
+      <a:effectStyleLst>
+        <a:effectStyle><a:effectLst/></a:effectStyle>
+        <a:effectStyle><a:effectLst/></a:effectStyle>
+        <a:effectStyle><a:effectLst/></a:effectStyle>
+      </a:effectStyleLst>
+      <a:bgFillStyleLst>
+        <a:solidFill><a:schemeClr val="phClr"/></a:solidFill>
+        <a:solidFill><a:schemeClr val="phClr"><a:tint val="95000"/></a:schemeClr></a:solidFill>
+        <a:solidFill><a:schemeClr val="phClr"><a:shade val="90000"/></a:schemeClr></a:solidFill>
+      </a:bgFillStyleLst>
+    </a:fmtScheme>
+  </a:themeElements>
+  <a:objectDefaults/>
+  <a:extraClrSchemeLst/>
+</a:theme>"#,
+            name = escape_xml(&self.name),
+            colors = colors,
+            major = escape_xml(&self.major_font),
+            minor = escape_xml(&self.minor_font),
+            major_ea = escape_xml(&self.major_east_asian_font),
+            minor_ea = escape_xml(&self.minor_east_asian_font),
+        )
+    }
+}
+
+/// 设计规范中颜色的语义角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorRole {
+    Background,
+    SecondaryBackground,
+    Text,
+    SecondaryText,
+    PrimaryAccent,
+    Accent,
+    Hyperlink,
+    Ignored,
+}
+
+/// 通用字体族与系统字体别名，不写入主题
+const GENERIC_FONTS: &[&str] = &[
+    "serif",
+    "sans-serif",
+    "monospace",
+    "cursive",
+    "fantasy",
+    "system-ui",
+    "-apple-system",
+    "blinkmacsystemfont",
+    "ui-sans-serif",
+    "ui-serif",
+    "ui-monospace",
+];
+
+/// 可识别为中文字体的名称片段
+const EAST_ASIAN_FONT_HINTS: &[&str] = &[
+    "yahei",
+    "pingfang",
+    "hei",
+    "song",
+    "kai",
+    "fang",
+    "hiragino",
+    "source han",
+    "noto sans sc",
+    "noto serif sc",
+    "noto sans cjk",
+];
+
+impl Theme {
+    /// 从项目设计规范（Markdown）解析主题
+    ///
+    /// 只读取标题含“色”的章节中的 `#RRGGBB` 色值与标题含“字体”的章节中的字体，
+    /// 未能识别的配色与字体保留 Office 默认值
+    pub fn from_design_spec(markdown: &str) -> Self {
+        let mut theme = Theme::default();
+        if let Some(title) = spec_title(markdown) {
+            theme.name = title;
+        }
+
+        let mut roles: Vec<(ColorRole, String)> = Vec::new();
+        let mut heading_fonts: Option<Vec<String>> = None;
+        let mut body_fonts: Option<Vec<String>> = None;
+        let mut font_stack: Option<Vec<String>> = None;
+
+        let mut in_color_section = false;
+        let mut in_font_section = false;
+
+        for line in markdown.lines() {
+            let trimmed = line.trim();
+
+            if let Some(heading) = heading_text(trimmed) {
+                in_color_section =
+                    heading.contains('色') || heading.to_lowercase().contains("color");
+                in_font_section = heading.contains("字体");
+                continue;
+            }
+
+            if in_color_section {
+                if let Some((role, hex)) = parse_color_line(trimmed) {
+                    roles.push((role, hex));
+                }
+            }
+
+            if font_stack.is_none() {
+                font_stack = font_stack_text(trimmed, in_font_section)
+                    .map(parse_font_list)
+                    .filter(|fonts| !fonts.is_empty());
+            }
+
+            // 字体表：| **标题** | 中文字体 | 英文字体 | 备选 |
+            if in_font_section && trimmed.starts_with('|') {
+                let cells: Vec<&str> = trimmed
+                    .trim_matches('|')
+                    .split('|')
+                    .map(str::trim)
+                    .collect();
+                let label = cells
+                    .first()
+                    .map(|cell| cell.trim_matches('*'))
+                    .unwrap_or_default();
+                let fonts: Vec<String> = cells
+                    .iter()
+                    .skip(1)
+                    .flat_map(|cell| parse_font_list(cell))
+                    .collect();
+
+                if fonts.is_empty() {
+                    continue;
+                }
+                if label.contains("正文") && body_fonts.is_none() {
+                    body_fonts = Some(fonts);
+                } else if label.contains("标题") && heading_fonts.is_none() {
+                    heading_fonts = Some(fonts);
+                }
+            }
+        }
+
+        theme.apply_colors(&roles);
+
+        let heading_fonts = heading_fonts.or_else(|| font_stack.clone());
+        let body_fonts = body_fonts.or(font_stack);
+        if let Some(fonts) = heading_fonts {
+            let (latin, east_asian) = split_fonts(&fonts);
+            if let Some(latin) = latin.or_else(|| east_asian.clone()) {
+                theme.major_font = latin;
+            }
+            if let Some(east_asian) = east_asian {
+                theme.major_east_asian_font = east_asian;
+            }
+        }
+        if let Some(fonts) = body_fonts {
+            let (latin, east_asian) = split_fonts(&fonts);
+            if let Some(latin) = latin.or_else(|| east_asian.clone()) {
+                theme.minor_font = latin;
+            }
+            if let Some(east_asian) = east_asian {
+                theme.minor_east_asian_font = east_asian;
+            }
+        }
+
+        theme
+    }
+
+    /// 按语义角色填充配色方案，同一角色取第一次出现的色值
+    fn apply_colors(&mut self, roles: &[(ColorRole, String)]) {
+        let first = |wanted: ColorRole| {
+            roles
+                .iter()
+                .find(|(role, _)| *role == wanted)
+                .map(|(_, hex)| hex.clone())
+        };
+
+        if let Some(hex) = first(ColorRole::Background) {
+            self.light1 = hex;
+        }
+        if let Some(hex) = first(ColorRole::SecondaryBackground) {
+            self.light2 = hex;
+        }
+        if let Some(hex) = first(ColorRole::Text) {
+            self.dark1 = hex;
+        }
+        // 没有单独的次要文字色时，使用第二个文字色
+        let secondary_text = first(ColorRole::SecondaryText).or_else(|| {
+            roles
+                .iter()
+                .filter(|(role, _)| *role == ColorRole::Text)
+                .nth(1)
+                .map(|(_, hex)| hex.clone())
+        });
+        if let Some(hex) = secondary_text {
+            self.dark2 = hex;
+        }
+        if let Some(hex) = first(ColorRole::Hyperlink) {
+            self.hyperlink = hex;
+        }
+
+        // 主导色排在最前，其余强调色按出现顺序去重
+        let mut accents: Vec<String> = Vec::new();
+        let ordered = roles
+            .iter()
+            .filter(|(role, _)| *role == ColorRole::PrimaryAccent)
+            .chain(roles.iter().filter(|(role, _)| *role == ColorRole::Accent));
+        for (_, hex) in ordered {
+            if !accents.contains(hex) && accents.len() < self.accents.len() {
+                accents.push(hex.clone());
+            }
+        }
+        for (slot, hex) in self.accents.iter_mut().zip(accents) {
+            *slot = hex;
+        }
+    }
+}
+
+/// 读取项目设计规范生成主题，项目没有设计规范时返回 None
+pub fn load_project_theme(project_path: &Path) -> Result<Option<Theme>> {
+    let info = pptm_domain::project_utils::get_project_info(project_path);
+
+    match info.spec_file {
+        Some(spec_file) => {
+            let markdown = std::fs::read_to_string(project_path.join(spec_file))?;
+            Ok(Some(Theme::from_design_spec(&markdown)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// 一级标题作为主题名称（去掉“设计规范与内容大纲”等后缀）
+fn spec_title(markdown: &str) -> Option<String> {
+    let title = markdown
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("# "))?;
+    let end = [" - ", " —", "——", "设计规范"]
+        .iter()
+        .filter_map(|separator| title.find(separator))
+        .min()
+        .unwrap_or(title.len());
+    let title = title[..end].trim();
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Markdown 标题行的文本
+fn heading_text(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    (line.starts_with('#') && text.starts_with(' ')).then(|| text.trim())
+}
+
+/// 以 `#` 开头的六位十六进制色值
+fn is_hex_start(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    bytes.len() >= 7 && bytes[0] == b'#' && bytes[1..7].iter().all(u8::is_ascii_hexdigit)
+}
+
+/// 提取字体栈文本：`font-family: ...`、`字体栈: ...` 或字体章节代码块中的逗号列表
+fn font_stack_text(line: &str, in_font_section: bool) -> Option<&str> {
+    if let Some(index) = line.find("font-family") {
+        return Some(&line[index + "font-family".len()..]);
+    }
+    if !in_font_section {
+        return None;
+    }
+
+    if line.contains("字体栈") {
+        line.split_once([':', '：']).map(|(_, rest)| rest)
+    } else if line.contains(',') && !line.contains([':', '：', '|']) && !line.starts_with('-') {
+        Some(line)
+    } else {
+        None
+    }
+}
+
+/// 解析包含色值的一行，返回语义角色与大写十六进制色值
+///
+/// 渐变描述与 SVG 代码片段不参与解析
+fn parse_color_line(line: &str) -> Option<(ColorRole, String)> {
+    if line.contains('<') || line.contains('=') || line.contains('→') {
+        return None;
+    }
+
+    let start = line
+        .find('#')
+        .filter(|&index| is_hex_start(&line[index..]))?;
+    let hex = line[start + 1..start + 7].to_uppercase();
+
+    let label = line[..start]
+        .trim_matches(|c: char| c.is_whitespace() || "|*-:：`".contains(c))
+        .split('|')
+        .next()
+        .unwrap_or_default()
+        .trim_matches(|c: char| c.is_whitespace() || "*`".contains(c));
+    let usage = &line[start + 7..];
+
+    let role = classify_label(label).or_else(|| classify_usage(usage))?;
+    Some((role, hex))
+}
+
+/// 按颜色名称（配色表第一列）识别语义角色，“深蓝色”等纯颜色名返回 None
+fn classify_label(label: &str) -> Option<ColorRole> {
+    let has = |keywords: &[&str]| keywords.iter().any(|k| label.contains(k));
+
+    let role = if has(&["边框", "分割", "弱", "阴影", "渐变"]) {
+        ColorRole::Ignored
+    } else if has(&["次背景", "卡片背景", "辅背景", "区块背景"]) {
+        ColorRole::SecondaryBackground
+    } else if has(&["背景"]) {
+        ColorRole::Background
+    } else if has(&[
+        "次要文字",
+        "文字次色",
+        "次文字",
+        "副标题",
+        "辅助文本",
+        "说明文字",
+    ]) {
+        ColorRole::SecondaryText
+    } else if has(&["文字", "文本", "正文", "标题"]) {
+        ColorRole::Text
+    } else if has(&["链接"]) {
+        ColorRole::Hyperlink
+    } else if has(&["主导色", "主色", "主强调", "品牌"]) {
+        ColorRole::PrimaryAccent
+    } else if has(&["强调", "辅助", "成功", "警示", "警告", "数据系列"]) {
+        ColorRole::Accent
+    } else {
+        return None;
+    };
+
+    Some(role)
+}
+
+/// 颜色名称无法识别时（如 `Google Blue`），按用途说明识别
+fn classify_usage(usage: &str) -> Option<ColorRole> {
+    let has = |keywords: &[&str]| keywords.iter().any(|k| usage.contains(k));
+
+    let role = if has(&["边框", "分割", "页脚"]) {
+        ColorRole::Ignored
+    } else if has(&["主背景", "页面背景"]) {
+        ColorRole::Background
+    } else if has(&["背景"]) {
+        ColorRole::SecondaryBackground
+    } else if has(&["次要", "说明"]) {
+        ColorRole::SecondaryText
+    } else if has(&["正文", "文本"]) {
+        ColorRole::Text
+    } else {
+        ColorRole::Accent
+    };
+
+    Some(role)
+}
+
+/// 解析逗号分隔的字体列表，去掉引号、占位符以及字号、字重等非字体内容
+fn parse_font_list(text: &str) -> Vec<String> {
+    const WEIGHTS: &[&str] = &["Bold", "Regular", "Medium", "Semibold", "SemiBold", "Light"];
+    // 中文字体名称中常见的字
+    const CJK_FONT_CHARS: &[char] = &['体', '黑', '宋', '楷', '雅', '圆'];
+
+    text.split([',', '，'])
+        .map(|font| {
+            font.trim_matches(|c: char| c.is_whitespace() || "`'\";:：=*".contains(c))
+                .to_string()
+        })
+        // “Arial Bold” 这类写法去掉字重后缀
+        .map(|font| match font.rsplit_once(' ') {
+            Some((name, weight)) if WEIGHTS.contains(&weight) => name.to_string(),
+            _ => font,
+        })
+        .filter(|font| {
+            !font.is_empty()
+                && !font.starts_with('[')
+                && !font.chars().all(|c| c == '-')
+                && !font.starts_with(|c: char| c.is_ascii_digit())
+                && !WEIGHTS.contains(&font.as_str())
+                && (font.is_ascii() || font.contains(CJK_FONT_CHARS))
+        })
+        .collect()
+}
+
+/// 拆分为拉丁字体与东亚字体（各取第一个非通用字体）
+fn split_fonts(fonts: &[String]) -> (Option<String>, Option<String>) {
+    let mut latin = None;
+    let mut east_asian = None;
+
+    for font in fonts {
+        let lower = font.to_lowercase();
+        if GENERIC_FONTS.contains(&lower.as_str()) {
+            continue;
+        }
+
+        let is_east_asian = !font.is_ascii()
+            || EAST_ASIAN_FONT_HINTS
+                .iter()
+                .any(|hint| lower.contains(hint));
+        if is_east_asian {
+            east_asian.get_or_insert_with(|| font.clone());
+        } else {
+            latin.get_or_insert_with(|| font.clone());
+        }
+    }
+
+    (latin, east_asian)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = r#"# 季度经营分析 - 设计规范与内容大纲
+
+## 三、视觉主题
+
+### 配色方案
+
+| 角色          | 色值      | 用途           |
+| ------------- | --------- | -------------- |
+| **背景色**    | `#FAFBFC` | 页面背景       |
+| **次背景**    | `#F1F5F9` | 卡片背景       |
+| **主导色**    | `#6366F1` | 标题装饰       |
+| **强调色**    | `#06B6D4` | 数据高亮       |
+| **渐变色**    | `#6366F1 → #06B6D4` | 装饰 |
+| **正文文字**  | `#1F2937` | 主要正文       |
+| **次要文字**  | `#6B7280` | 说明文字       |
+| **边框/分割** | `#E5E7EB` | 分割线         |
+| **成功色**    | `#10B981` | 正向指标       |
+
+## 四、排版体系
+
+### 字体方案
+
+| 角色     | 中文     | 英文     | 备选     |
+| -------- | -------- | -------- | -------- |
+| **标题** | 思源黑体 | Montserrat | Arial |
+| **正文** | 微软雅黑 | Open Sans | Arial |
+
+### 字号层级
+
+| 层级 | 字号 | 颜色 |
+| H1 | 36px | `#000000` |
+"#;
+
+    #[test]
+    fn test_from_design_spec_colors() {
+        let theme = Theme::from_design_spec(SPEC);
+
+        assert_eq!(theme.name, "季度经营分析");
+        assert_eq!(theme.light1, "FAFBFC");
+        assert_eq!(theme.light2, "F1F5F9");
+        assert_eq!(theme.dark1, "1F2937");
+        assert_eq!(theme.dark2, "6B7280");
+        assert_eq!(theme.accents[0], "6366F1");
+        assert_eq!(theme.accents[1], "06B6D4");
+        assert_eq!(theme.accents[2], "10B981");
+        // 未给出的强调色保留默认值
+        assert_eq!(theme.accents[3], Theme::default().accents[3]);
+    }
+
+    #[test]
+    fn test_from_design_spec_fonts() {
+        let theme = Theme::from_design_spec(SPEC);
+
+        assert_eq!(theme.major_font, "Montserrat");
+        assert_eq!(theme.major_east_asian_font, "思源黑体");
+        assert_eq!(theme.minor_font, "Open Sans");
+        assert_eq!(theme.minor_east_asian_font, "微软雅黑");
+    }
+
+    #[test]
+    fn test_from_design_spec_font_stack_and_usage() {
+        let spec = r#"# Annual Report
+
+### 色彩规范
+
+- Google Blue：`#4285F4`（主要标题、关键数据）
+- 深蓝色：`#1A237E`（标题、核心文本）
+- 纯白：`#FFFFFF`（主背景）
+
+### 字体规范
+
+```
+font-family: system-ui, -apple-system, "Segoe UI", "PingFang SC", sans-serif
+```
+"#;
+        let theme = Theme::from_design_spec(spec);
+
+        assert_eq!(theme.accents[0], "4285F4");
+        assert_eq!(theme.dark1, "1A237E");
+        assert_eq!(theme.light1, "FFFFFF");
+        assert_eq!(theme.major_font, "Segoe UI");
+        assert_eq!(theme.minor_east_asian_font, "PingFang SC");
+    }
+
+    #[test]
+    fn test_theme_xml_contains_scheme() {
+        let xml = Theme::from_design_spec(SPEC).to_xml();
+        assert!(xml.contains(r#"<a:accent1><a:srgbClr val="6366F1"/></a:accent1>"#));
+        assert!(xml.contains(r#"<a:majorFont><a:latin typeface="Montserrat"/>"#));
+    }
+}
@@ -0,0 +1,483 @@
+// PPTX 包结构校验
+// 打开已写入的 .pptx，检查关系目标、内容类型、幻灯片 ID 与必需部件
+
+use crate::package::{read_parts, rel_types, resolve_target};
+use crate::{PptxError, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// 最小合法幻灯片 ID
+const MIN_SLIDE_ID: u32 = 256;
+
+/// 校验问题类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueKind {
+    /// 缺少必需部件
+    MissingPart,
+    /// 关系目标部件不存在
+    BrokenRelationship,
+    /// 部件没有声明内容类型
+    MissingContentType,
+    /// 幻灯片 ID 小于 256 或无法解析
+    InvalidSlideId,
+    /// 幻灯片 ID 重复
+    DuplicateSlideId,
+    /// XML 无法解析
+    MalformedXml,
+}
+
+/// 单条校验问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationIssue {
+    /// 问题类型
+    pub kind: IssueKind,
+    /// 出问题的部件名
+    pub part: String,
+    /// 问题描述
+    pub message: String,
+}
+
+/// 校验报告
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+    /// 包内部件数量（不含目录）
+    pub part_count: usize,
+    /// presentation.xml 中登记的幻灯片数量
+    pub slide_count: usize,
+    /// 发现的问题
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// 是否没有发现任何问题
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push(&mut self, kind: IssueKind, part: &str, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            kind,
+            part: part.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// 汇总全部问题，用于错误信息
+    pub fn summary(&self) -> String {
+        self.issues
+            .iter()
+            .map(|issue| format!("{}: {}", issue.part, issue.message))
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
+
+/// 校验 PPTX 文件
+///
+/// 只有文件无法打开或不是 zip 时返回错误，结构问题记录在报告中
+pub fn validate_pptx(path: &Path) -> Result<ValidationReport> {
+    Ok(validate_parts(&read_parts(path)?))
+}
+
+/// 导出后校验，发现问题时返回错误
+pub fn ensure_valid(path: &Path) -> Result<()> {
+    let report = validate_pptx(path)?;
+
+    if report.is_valid() {
+        Ok(())
+    } else {
+        Err(PptxError::Package(format!(
+            "导出的 PPTX 未通过校验: {}",
+            report.summary()
+        )))
+    }
+}
+
+fn validate_parts(parts: &HashMap<String, Vec<u8>>) -> ValidationReport {
+    let mut report = ValidationReport {
+        part_count: parts.len(),
+        ..ValidationReport::default()
+    };
+
+    let text = |name: &str| parts.get(name).map(|data| String::from_utf8_lossy(data));
+
+    // 内容类型
+    match text("[Content_Types].xml") {
+        Some(xml) => check_content_types(&xml, parts, &mut report),
+        None => report.push(
+            IssueKind::MissingPart,
+            "[Content_Types].xml",
+            "缺少内容类型部件",
+        ),
+    }
+
+    // 关系目标
+    let mut relationships: HashMap<String, Vec<HashMap<String, String>>> = HashMap::new();
+    let mut rels_names: Vec<&String> = parts
+        .keys()
+        .filter(|name| name.ends_with(".rels"))
+        .collect();
+    rels_names.sort();
+
+    for rels_name in rels_names {
+        let xml = String::from_utf8_lossy(&parts[rels_name]);
+        let elements = match elements(&xml, "Relationship") {
+            Ok(elements) => elements,
+            Err(message) => {
+                report.push(IssueKind::MalformedXml, rels_name, message);
+                continue;
+            }
+        };
+
+        let source = source_part_name(rels_name);
+        for rel in &elements {
+            if rel.get("TargetMode").map(String::as_str) == Some("External") {
+                continue;
+            }
+            let Some(target) = rel.get("Target") else {
+                report.push(IssueKind::BrokenRelationship, rels_name, "关系缺少 Target");
+                continue;
+            };
+
+            let resolved = resolve_target(&source, target);
+            if !parts.contains_key(&resolved) {
+                report.push(
+                    IssueKind::BrokenRelationship,
+                    rels_name,
+                    format!("关系目标不存在: {}", resolved),
+                );
+            }
+        }
+
+        relationships.insert(source, elements);
+    }
+
+    // 必需部件：包级关系 -> 演示文稿 -> 母版
+    let Some(root_rels) = relationships.get("") else {
+        report.push(IssueKind::MissingPart, "_rels/.rels", "缺少包级关系部件");
+        return report;
+    };
+
+    let Some(presentation) = root_rels
+        .iter()
+        .find(|rel| rel.get("Type").map(String::as_str) == Some(rel_types::OFFICE_DOCUMENT))
+        .and_then(|rel| rel.get("Target"))
+        .map(|target| resolve_target("", target))
+    else {
+        report.push(
+            IssueKind::MissingPart,
+            "_rels/.rels",
+            "缺少指向演示文稿的 officeDocument 关系",
+        );
+        return report;
+    };
+
+    let Some(presentation_xml) = text(&presentation) else {
+        // 目标缺失已在关系检查中报告
+        return report;
+    };
+
+    let presentation_rels = relationships
+        .get(&presentation)
+        .cloned()
+        .unwrap_or_default();
+    if !presentation_rels
+        .iter()
+        .any(|rel| rel.get("Type").map(String::as_str) == Some(rel_types::SLIDE_MASTER))
+    {
+        report.push(
+            IssueKind::MissingPart,
+            &presentation,
+            "演示文稿没有引用幻灯片母版",
+        );
+    }
+
+    check_slide_ids(
+        &presentation,
+        &presentation_xml,
+        &presentation_rels,
+        &mut report,
+    );
+
+    report
+}
+
+fn check_content_types(xml: &str, parts: &HashMap<String, Vec<u8>>, report: &mut ValidationReport) {
+    let (defaults, overrides) = match (elements(xml, "Default"), elements(xml, "Override")) {
+        (Ok(defaults), Ok(overrides)) => (defaults, overrides),
+        (Err(message), _) | (_, Err(message)) => {
+            report.push(IssueKind::MalformedXml, "[Content_Types].xml", message);
+            return;
+        }
+    };
+
+    let extensions: HashSet<String> = defaults
+        .iter()
+        .filter_map(|e| e.get("Extension"))
+        .map(|ext| ext.to_lowercase())
+        .collect();
+    let overridden: HashSet<String> = overrides
+        .iter()
+        .filter_map(|e| e.get("PartName"))
+        .map(|name| name.trim_start_matches('/').to_lowercase())
+        .collect();
+
+    let mut names: Vec<&String> = parts.keys().collect();
+    names.sort();
+
+    for name in names
+        .into_iter()
+        .filter(|name| *name != "[Content_Types].xml")
+    {
+        let extension = name.rsplit_once('.').map(|(_, ext)| ext.to_lowercase());
+        let declared = overridden.contains(&name.to_lowercase())
+            || extension.is_some_and(|ext| extensions.contains(&ext));
+
+        if !declared {
+            report.push(IssueKind::MissingContentType, name, "部件没有声明内容类型");
+        }
+    }
+}
+
+fn check_slide_ids(
+    presentation: &str,
+    xml: &str,
+    presentation_rels: &[HashMap<String, String>],
+    report: &mut ValidationReport,
+) {
+    // 分节列表中的 p14:sldId 只引用幻灯片 ID，没有 r:id
+    let slide_ids = match elements(xml, "sldId") {
+        Ok(slide_ids) => slide_ids
+            .into_iter()
+            .filter(|attrs| attrs.keys().any(|key| key.ends_with(":id")))
+            .collect::<Vec<_>>(),
+        Err(message) => {
+            report.push(IssueKind::MalformedXml, presentation, message);
+            return;
+        }
+    };
+    report.slide_count = slide_ids.len();
+
+    let rel_ids: HashSet<&str> = presentation_rels
+        .iter()
+        .filter_map(|rel| rel.get("Id").map(String::as_str))
+        .collect();
+    let mut seen = HashSet::new();
+
+    for slide_id in &slide_ids {
+        let raw_id = slide_id.get("id").map(String::as_str).unwrap_or_default();
+        match raw_id.parse::<u32>() {
+            Ok(id) if id < MIN_SLIDE_ID => report.push(
+                IssueKind::InvalidSlideId,
+                presentation,
+                format!("幻灯片 ID {} 小于 {}", id, MIN_SLIDE_ID),
+            ),
+            Ok(id) => {
+                if !seen.insert(id) {
+                    report.push(
+                        IssueKind::DuplicateSlideId,
+                        presentation,
+                        format!("幻灯片 ID {} 重复", id),
+                    );
+                }
+            }
+            Err(_) => report.push(
+                IssueKind::InvalidSlideId,
+                presentation,
+                format!("无法解析幻灯片 ID: {:?}", raw_id),
+            ),
+        }
+
+        // r:id 的前缀由生成工具决定，按局部名匹配
+        let rid = slide_id
+            .iter()
+            .find(|(key, _)| key.ends_with(":id"))
+            .map(|(_, value)| value.as_str());
+        if let Some(rid) = rid.filter(|rid| !rel_ids.contains(rid)) {
+            report.push(
+                IssueKind::BrokenRelationship,
+                presentation,
+                format!("幻灯片引用了不存在的关系 {}", rid),
+            );
+        }
+    }
+}
+
+/// 关系部件对应的源部件名，例如 `ppt/slides/_rels/slide1.xml.rels` -> `ppt/slides/slide1.xml`
+fn source_part_name(rels_name: &str) -> String {
+    let (dir, file) = rels_name.rsplit_once('/').unwrap_or(("", rels_name));
+    let dir = dir.strip_suffix("_rels").unwrap_or(dir);
+    format!("{}{}", dir, file.strip_suffix(".rels").unwrap_or(file))
+}
+
+/// 收集指定局部名的所有元素属性（属性键保留原始限定名）
+fn elements(
+    xml: &str,
+    local_name: &str,
+) -> std::result::Result<Vec<HashMap<String, String>>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut buf = Vec::new();
+    let mut found = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e))
+                if e.local_name().as_ref() == local_name.as_bytes() =>
+            {
+                let mut attrs = HashMap::new();
+                for attr in e.attributes().flatten() {
+                    let key = String::from_utf8_lossy(attr.key.as_ref()).to_string();
+                    let value = attr
+                        .unescape_value()
+                        .map(|v| v.to_string())
+                        .unwrap_or_default();
+                    attrs.insert(key, value);
+                }
+                found.push(attrs);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("XML 解析失败: {}", e)),
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::package::{content_types, OoxmlPackage};
+
+    fn minimal_package() -> OoxmlPackage {
+        let mut package = OoxmlPackage::new();
+        package.add_relationship("", rel_types::OFFICE_DOCUMENT, "ppt/presentation.xml");
+        package.add_relationship(
+            "ppt/presentation.xml",
+            rel_types::SLIDE_MASTER,
+            "slideMasters/slideMaster1.xml",
+        );
+        package.add_relationship(
+            "ppt/presentation.xml",
+            rel_types::SLIDE,
+            "slides/slide1.xml",
+        );
+        package.add_part(
+            "ppt/slideMasters/slideMaster1.xml",
+            content_types::SLIDE_MASTER,
+            "<p:sldMaster/>",
+        );
+        package.add_part("ppt/slides/slide1.xml", content_types::SLIDE, "<p:sld/>");
+        package
+    }
+
+    fn presentation(slide_ids: &[u32]) -> String {
+        let ids: String = slide_ids
+            .iter()
+            .map(|id| format!(r#"<p:sldId id="{}" r:id="rId2"/>"#, id))
+            .collect();
+        format!(
+            r#"<p:presentation xmlns:p="p" xmlns:r="r"><p:sldIdLst>{}</p:sldIdLst></p:presentation>"#,
+            ids
+        )
+    }
+
+    fn validate(package: &OoxmlPackage) -> ValidationReport {
+        let temp_dir = tempfile::tempdir().expect("应能创建临时目录");
+        let output = temp_dir.path().join("deck.pptx");
+        // 这里故意不经过 write 的关系检查，直接校验写出的文件
+        let _ = package.write(&output);
+        validate_pptx(&output).expect("应能读取测试包")
+    }
+
+    #[test]
+    fn test_valid_package() {
+        let mut package = minimal_package();
+        package.add_part(
+            "ppt/presentation.xml",
+            content_types::PRESENTATION,
+            presentation(&[256]),
+        );
+
+        let report = validate(&package);
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.slide_count, 1);
+    }
+
+    #[test]
+    fn test_invalid_and_duplicate_slide_ids() {
+        let mut package = minimal_package();
+        package.add_part(
+            "ppt/presentation.xml",
+            content_types::PRESENTATION,
+            presentation(&[255, 300, 300]),
+        );
+
+        let kinds: Vec<IssueKind> = validate(&package).issues.iter().map(|i| i.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![IssueKind::InvalidSlideId, IssueKind::DuplicateSlideId]
+        );
+    }
+
+    #[test]
+    fn test_broken_relationship_and_missing_content_type() {
+        let mut package = minimal_package();
+        package.add_part(
+            "ppt/presentation.xml",
+            content_types::PRESENTATION,
+            presentation(&[256]),
+        );
+        package.add_relationship(
+            "ppt/slides/slide1.xml",
+            rel_types::IMAGE,
+            "../media/image1.png",
+        );
+        // 内容类型为 application/xml 的部件只能依赖 Default，扩展名未声明时报告缺失
+        package.add_part("ppt/embeddings/data.bin", content_types::XML, vec![0u8]);
+
+        let report = validate(&package);
+        assert!(report
+            .issues
+            .iter()
+            .any(|issue| issue.kind == IssueKind::BrokenRelationship
+                && issue.message.contains("ppt/media/image1.png")));
+        assert!(report
+            .issues
+            .iter()
+            .any(|issue| issue.kind == IssueKind::MissingContentType
+                && issue.part == "ppt/embeddings/data.bin"));
+    }
+
+    #[test]
+    fn test_missing_presentation() {
+        let mut package = OoxmlPackage::new();
+        package.add_part(
+            "docProps/app.xml",
+            content_types::EXTENDED_PROPERTIES,
+            "<Properties/>",
+        );
+
+        let report = validate(&package);
+        assert!(report
+            .issues
+            .iter()
+            .any(|issue| issue.kind == IssueKind::MissingPart && issue.part == "_rels/.rels"));
+    }
+
+    #[test]
+    fn test_source_part_name() {
+        assert_eq!(source_part_name("_rels/.rels"), "");
+        assert_eq!(
+            source_part_name("ppt/slides/_rels/slide1.xml.rels"),
+            "ppt/slides/slide1.xml"
+        );
+    }
+}
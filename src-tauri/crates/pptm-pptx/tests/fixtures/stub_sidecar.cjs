@@ -0,0 +1,93 @@
+#!/usr/bin/env node
+
+/**
+ * 测试用 sidecar
+ *
+ * 实现与 sidecars/pptxgen 相同的按行 JSON-RPC 协议，提供能力查询、回显、进度、
+ * 延迟响应、大量 stderr 输出、不响应、取消与崩溃等方法
+ */
+
+const readline = require('readline');
+
+const protocolVersion = Number(process.env.STUB_PROTOCOL_VERSION || 1);
+
+function send(message) {
+  process.stdout.write(JSON.stringify({ jsonrpc: '2.0', ...message }) + '\n');
+}
+
+// 收到取消通知的请求 ID
+const cancelled = [];
+
+// 尚未响应的 delay 请求，收到取消通知时提前以取消错误结束（sleep 请求不确认取消）
+const timers = new Map();
+
+const rl = readline.createInterface({ input: process.stdin });
+
+rl.on('line', (line) => {
+  const { id, method, params = {} } = JSON.parse(line);
+
+  switch (method) {
+    case 'initialize':
+      send({ id, result: { protocolVersion, name: 'stub-sidecar', version: '0.0.1' } });
+      break;
+    case 'capabilities':
+      send({
+        id,
+        result: {
+          nodeVersion: process.versions.node,
+          libraryVersion: '0.0.0-stub',
+          contentTypes: ['png'],
+          transitions: ['fade', 'push'],
+        },
+      });
+      break;
+    case 'echo':
+      send({ id, result: params });
+      break;
+    case 'pid':
+      send({ id, result: process.pid });
+      break;
+    case 'progress':
+      for (let current = 1; current <= params.total; current++) {
+        send({ method: 'progress', params: { requestId: id, current, total: params.total } });
+      }
+      send({ id, result: { done: true } });
+      break;
+    case 'noisy':
+      // 远超管道缓冲区的 stderr 输出，读取端不并发读取 stderr 时会卡住
+      for (let i = 0; i < 4096; i++) {
+        process.stderr.write(`log ${i} ${'x'.repeat(256)}\n`);
+      }
+      send({ id, result: 'ok' });
+      break;
+    case 'delay':
+      timers.set(
+        id,
+        setTimeout(() => {
+          timers.delete(id);
+          send({ id, result: params });
+        }, params.ms)
+      );
+      break;
+    case 'cancel':
+      // 通知本身无需响应，被取消的请求以 -32800 错误结束
+      cancelled.push(params.requestId);
+      if (timers.has(params.requestId)) {
+        clearTimeout(timers.get(params.requestId));
+        timers.delete(params.requestId);
+        send({ id: params.requestId, error: { code: -32800, message: '请求已取消' } });
+      }
+      break;
+    case 'cancelled':
+      send({ id, result: cancelled });
+      break;
+    case 'sleep':
+      // 不响应，用于测试超时
+      break;
+    case 'crash':
+      process.stderr.write('boom: 模拟崩溃\n', () => process.exit(3));
+      break;
+    default:
+      send({ id, error: { code: -32601, message: `未知方法: ${method}` } });
+  }
+});
@@ -1,21 +1,31 @@
 // PPTX 导出命令
 
+use super::jobs::TauriProgressSink;
+use crate::events::{emit_job_event, JobEventPayload};
+use crate::state::{AppState, JobStatus};
 use pptm_pptx::{
-    backend::{NativeOoxml, PptxGenSidecar},
-    load_slides, PptxBackend, PptxConfig,
+    accessibility::AccessibilityReport,
+    animation::AnimationSpec,
+    backend::pptxgen_sidecar::SidecarProbe,
+    deck::{load_merged_slides, project_canvas, DeckSource, SlideRange},
+    load_slides,
+    reader::{import_into_project, ImportSummary},
+    registry::{ExporterInfo, ExporterOptions, DEFAULT_EXPORTER},
+    theme::{load_project_theme, Theme},
+    validate::{validate_pptx, ValidationReport},
+    PptxBackend, PptxConfig, PptxError, Slide,
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use tauri::{State, Window};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
-/// PPTX 导出请求
-#[derive(Debug, Deserialize)]
+/// 导出选项（导出与合并请求共用）
+#[derive(Debug, Default, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ExportPptxRequest {
-    /// 项目路径
-    pub project_path: String,
-    /// 输出文件路径（可选，默认为项目目录下的 output.pptx）
-    pub output_path: Option<String>,
-    /// 后端类型（"pptxgen" 或 "native"）
+pub struct ExportOptions {
+    /// 导出器名称（见 `cmd_list_exporters`，默认 "pptxgen"）
     pub backend: Option<String>,
     /// 画布宽度
     pub width: Option<u32>,
@@ -25,132 +35,438 @@ pub struct ExportPptxRequest {
     pub enable_transitions: Option<bool>,
     /// 切换效果类型
     pub transition_type: Option<String>,
+    /// 切换持续时间（秒）
+    pub transition_duration: Option<f32>,
+    /// 自动翻页间隔（秒）
+    pub auto_advance: Option<f32>,
+    /// 全局入场动画类型（如 "fade", "fly" 等）
+    pub animation: Option<String>,
+    /// 导出器专属选项（如 `svgMode`、`pdfScale`，由导出器自行解析）
+    #[serde(flatten)]
+    pub exporter_options: serde_json::Map<String, serde_json::Value>,
 }
 
-/// PPTX 导出响应
-#[derive(Debug, Serialize)]
+/// PPTX 导出请求
+#[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ExportPptxResponse {
-    /// 是否成功
-    pub success: bool,
-    /// 输出文件路径
+pub struct ExportPptxRequest {
+    /// 项目路径
+    pub project_path: String,
+    /// 输出文件路径（可选，默认为项目目录下导出器的缺省输出，如 output.pptx）
     pub output_path: Option<String>,
-    /// 错误信息
-    pub error: Option<String>,
+    #[serde(flatten)]
+    pub options: ExportOptions,
+}
+
+/// 合并来源
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeSourceRequest {
+    /// 项目路径
+    pub project_path: String,
+    /// 幻灯片范围（如 "1-3,5,8-"，缺省为全部）
+    pub slides: Option<String>,
+}
+
+/// PPTX 合并请求
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergePptxRequest {
+    /// 按顺序合并的项目
+    pub sources: Vec<MergeSourceRequest>,
+    /// 输出文件路径
+    pub output_path: String,
+    #[serde(flatten)]
+    pub options: ExportOptions,
+}
+
+/// 导出任务响应
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportJobResponse {
+    pub job_id: String,
+}
+
+/// 导出完成事件携带的结果
+#[derive(Debug, Serialize)]
+pub struct ExportJobResult {
+    /// 输出文件路径
+    pub output_path: String,
     /// 幻灯片数量
-    pub slide_count: Option<usize>,
+    pub slide_count: usize,
+    /// 媒体去重节省的字节数
+    pub media_bytes_saved: u64,
+    /// 无障碍检查结果（缺少替代文字的图片）
+    pub accessibility: AccessibilityReport,
 }
 
 /// 导出 PPTX 命令
+///
+/// 以任务形式在后台加载幻灯片并导出，立即返回任务 ID；
+/// 进度与结果通过 `job:event` 事件推送，可用 `cmd_cancel_job` 取消
 #[tauri::command]
-pub async fn cmd_export_pptx(request: ExportPptxRequest) -> Result<ExportPptxResponse, String> {
+pub async fn cmd_export_pptx(
+    request: ExportPptxRequest,
+    state: State<'_, AppState>,
+    window: Window,
+) -> Result<ExportJobResponse, String> {
     // 解析项目路径
     let project_path = PathBuf::from(&request.project_path);
     if !project_path.exists() {
-        return Ok(ExportPptxResponse {
-            success: false,
-            output_path: None,
-            error: Some(format!("项目路径不存在: {:?}", project_path)),
-            slide_count: None,
-        });
+        return Err(format!("项目路径不存在: {:?}", project_path));
     }
 
-    // 确定输出路径
-    let output_path = if let Some(path) = request.output_path {
-        PathBuf::from(path)
-    } else {
-        project_path.join("output.pptx")
+    // 确定输出路径（缺省取导出器的默认输出）
+    let exporter = exporter_name(&request.options);
+    let output_path = match request.output_path {
+        Some(path) => PathBuf::from(path),
+        None => {
+            let entry = state
+                .exporters
+                .get(exporter)
+                .ok_or_else(|| format!("未知的导出器: {}", exporter))?;
+            project_path.join(entry.default_output())
+        }
     };
 
-    // 创建配置
-    let config = PptxConfig {
-        width: request.width.unwrap_or(1280),
-        height: request.height.unwrap_or(720),
-        enable_transitions: request.enable_transitions.unwrap_or(true),
-        transition_type: request.transition_type.or_else(|| Some("fade".to_string())),
-    };
+    // 读取设计规范中的配色与字体
+    let theme =
+        load_project_theme(&project_path).map_err(|e| format!("读取设计规范失败: {}", e))?;
 
-    // 加载幻灯片
-    let slides = match load_slides(&project_path, &config) {
-        Ok(slides) => slides,
-        Err(e) => {
-            return Ok(ExportPptxResponse {
-                success: false,
-                output_path: None,
-                error: Some(format!("加载幻灯片失败: {}", e)),
-                slide_count: None,
-            });
+    // 创建配置（画布缺省取项目的画布格式）
+    let (default_width, default_height) = project_canvas(&project_path).unwrap_or((1280, 720));
+    let canvas = (
+        request.options.width.unwrap_or(default_width),
+        request.options.height.unwrap_or(default_height),
+    );
+    let config = build_config(&request.options, canvas, theme);
+
+    let options = ExporterOptions::new(request.options.exporter_options.clone())
+        .with_base_dir(project_path.join("svg_final"));
+    let backend = create_backend(&state, exporter, options).await?;
+
+    // 幻灯片在任务中加载
+    let load_config = config.clone();
+    spawn_export_job(
+        &state,
+        window,
+        backend,
+        move || load_slides(&project_path, &load_config),
+        output_path,
+        config,
+    )
+    .await
+}
+
+/// 合并 PPTX 命令
+///
+/// 按顺序合并多个项目（可截取幻灯片范围）为一个 PPTX；只指定一个项目和范围即为拆分。
+/// 画布缺省取第一个项目的画布，主题取第一个项目的设计规范；与导出相同以任务形式执行
+#[tauri::command]
+pub async fn cmd_merge_pptx(
+    request: MergePptxRequest,
+    state: State<'_, AppState>,
+    window: Window,
+) -> Result<ExportJobResponse, String> {
+    let mut sources = Vec::with_capacity(request.sources.len());
+    for source in &request.sources {
+        let project_path = PathBuf::from(&source.project_path);
+        if !project_path.exists() {
+            return Err(format!("项目路径不存在: {:?}", project_path));
         }
-    };
 
-    if slides.is_empty() {
-        return Ok(ExportPptxResponse {
-            success: false,
-            output_path: None,
-            error: Some("没有找到幻灯片".to_string()),
-            slide_count: Some(0),
+        let ranges = SlideRange::parse_list(source.slides.as_deref().unwrap_or_default())
+            .map_err(|e| e.to_string())?;
+        sources.push(DeckSource {
+            project_path,
+            ranges,
         });
     }
 
-    // 选择后端
-    let backend_name = request.backend.as_deref().unwrap_or("pptxgen");
-    let result = match backend_name {
-        "pptxgen" => {
-            let backend = PptxGenSidecar::new();
-            if !backend.is_available() {
-                return Ok(ExportPptxResponse {
-                    success: false,
-                    output_path: None,
-                    error: Some("PptxGenJS 后端不可用，请确保已安装 Node.js".to_string()),
-                    slide_count: Some(slides.len()),
-                });
+    let first = sources
+        .first()
+        .ok_or_else(|| "没有指定要合并的项目".to_string())?;
+
+    let theme =
+        load_project_theme(&first.project_path).map_err(|e| format!("读取设计规范失败: {}", e))?;
+
+    let (default_width, default_height) =
+        project_canvas(&first.project_path).unwrap_or((1280, 720));
+    let canvas = (
+        request.options.width.unwrap_or(default_width),
+        request.options.height.unwrap_or(default_height),
+    );
+    let config = build_config(&request.options, canvas, theme);
+
+    let backend = create_backend(
+        &state,
+        exporter_name(&request.options),
+        ExporterOptions::new(request.options.exporter_options.clone()),
+    )
+    .await?;
+
+    let load_config = config.clone();
+    spawn_export_job(
+        &state,
+        window,
+        backend,
+        move || load_merged_slides(&sources, &load_config),
+        PathBuf::from(request.output_path),
+        config,
+    )
+    .await
+}
+
+fn build_config(options: &ExportOptions, canvas: (u32, u32), theme: Option<Theme>) -> PptxConfig {
+    PptxConfig {
+        width: canvas.0,
+        height: canvas.1,
+        enable_transitions: options.enable_transitions.unwrap_or(true),
+        transition_type: options
+            .transition_type
+            .clone()
+            .or_else(|| Some("fade".to_string())),
+        transition_duration: options.transition_duration.unwrap_or(0.5),
+        auto_advance: options.auto_advance,
+        animation: options.animation.clone().map(|effect| AnimationSpec {
+            effect,
+            duration: 0.5,
+            delay: 0.0,
+        }),
+        theme,
+    }
+}
+
+/// 请求选择的导出器名称
+fn exporter_name(options: &ExportOptions) -> &str {
+    options.backend.as_deref().unwrap_or(DEFAULT_EXPORTER)
+}
+
+/// 创建导出后端
+///
+/// 可用性检测可能启动外部进程（如 `node`），放到阻塞线程池执行
+async fn create_backend(
+    state: &AppState,
+    exporter: &str,
+    options: ExporterOptions,
+) -> Result<Box<dyn PptxBackend>, String> {
+    let exporters = state.exporters.clone();
+    let exporter = exporter.to_string();
+    tauri::async_runtime::spawn_blocking(move || exporters.create(&exporter, &options))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+/// 创建导出任务并在后台执行
+///
+/// 任务先在阻塞线程池中加载幻灯片（读取文件、内嵌图片、回退渲染等），再交给后端导出；
+/// 两个阶段都上报进度并响应取消
+async fn spawn_export_job<F>(
+    state: &State<'_, AppState>,
+    window: Window,
+    backend: Box<dyn PptxBackend>,
+    load: F,
+    output_path: PathBuf,
+    config: PptxConfig,
+) -> Result<ExportJobResponse, String>
+where
+    F: FnOnce() -> pptm_pptx::Result<Vec<Slide>> + Send + 'static,
+{
+    let job_id = Uuid::new_v4().to_string();
+    let cancel_token = state.create_job(job_id.clone()).await;
+    let app_state = state.inner().clone();
+    let job_id_clone = job_id.clone();
+
+    tauri::async_runtime::spawn(async move {
+        app_state
+            .update_job_status(
+                &job_id_clone,
+                JobStatus::Running,
+                Some("加载中".to_string()),
+            )
+            .await;
+
+        let _ = emit_job_event(
+            &window,
+            &JobEventPayload::started(
+                job_id_clone.clone(),
+                format!("正在加载幻灯片（{}）", backend.name()),
+            ),
+        );
+
+        let (status, message, payload) = match load_job_slides(load, &cancel_token).await {
+            Err(e) => job_failure(&job_id_clone, "加载幻灯片失败", e),
+            Ok(slides) if slides.is_empty() => {
+                let message = "没有找到幻灯片".to_string();
+                (
+                    JobStatus::Failed,
+                    message.clone(),
+                    JobEventPayload::failed(job_id_clone.clone(), message),
+                )
             }
-            backend.export(&slides, &output_path, &config)
-        }
-        "native" => {
-            let backend = NativeOoxml::new();
-            backend.export(&slides, &output_path, &config)
-        }
-        _ => {
-            return Ok(ExportPptxResponse {
-                success: false,
-                output_path: None,
-                error: Some(format!("未知的后端类型: {}", backend_name)),
-                slide_count: Some(slides.len()),
-            });
+            Ok(slides) => {
+                app_state
+                    .update_job_status(
+                        &job_id_clone,
+                        JobStatus::Running,
+                        Some("导出中".to_string()),
+                    )
+                    .await;
+
+                let _ = emit_job_event(
+                    &window,
+                    &JobEventPayload::progress(
+                        job_id_clone.clone(),
+                        0,
+                        slides.len(),
+                        format!("开始导出 {} 页幻灯片（{}）", slides.len(), backend.name()),
+                    ),
+                );
+
+                let sink = TauriProgressSink::new(window.clone(), job_id_clone.clone());
+                match backend
+                    .export(&slides, &output_path, &config, &sink, &cancel_token)
+                    .await
+                {
+                    Ok(summary) => {
+                        let result = ExportJobResult {
+                            output_path: output_path.to_string_lossy().to_string(),
+                            slide_count: summary.slide_count,
+                            media_bytes_saved: summary.media_bytes_saved,
+                            accessibility: summary.accessibility,
+                        };
+                        (
+                            JobStatus::Completed,
+                            "导出完成".to_string(),
+                            JobEventPayload::completed(
+                                job_id_clone.clone(),
+                                "导出完成",
+                                serde_json::to_value(&result).unwrap_or_default(),
+                            ),
+                        )
+                    }
+                    Err(e) => job_failure(&job_id_clone, "导出失败", e),
+                }
+            }
+        };
+
+        app_state
+            .update_job_status(&job_id_clone, status, Some(message))
+            .await;
+        let _ = emit_job_event(&window, &payload);
+    });
+
+    Ok(ExportJobResponse { job_id })
+}
+
+/// 在阻塞线程池中加载幻灯片；取消后立即返回，不再等待加载结果
+async fn load_job_slides<F>(load: F, cancel: &CancellationToken) -> Result<Vec<Slide>, PptxError>
+where
+    F: FnOnce() -> pptm_pptx::Result<Vec<Slide>> + Send + 'static,
+{
+    if cancel.is_cancelled() {
+        return Err(PptxError::Cancelled);
+    }
+
+    tokio::select! {
+        _ = cancel.cancelled() => Err(PptxError::Cancelled),
+        result = tauri::async_runtime::spawn_blocking(load) => {
+            result.map_err(|e| PptxError::Backend(e.to_string()))?
         }
-    };
+    }
+}
 
-    match result {
-        Ok(_) => Ok(ExportPptxResponse {
-            success: true,
-            output_path: Some(output_path.to_string_lossy().to_string()),
-            error: None,
-            slide_count: Some(slides.len()),
-        }),
-        Err(e) => Ok(ExportPptxResponse {
-            success: false,
-            output_path: None,
-            error: Some(format!("导出失败: {}", e)),
-            slide_count: Some(slides.len()),
-        }),
+/// 任务失败（或取消）时的状态、消息与事件
+fn job_failure(
+    job_id: &str,
+    context: &str,
+    error: PptxError,
+) -> (JobStatus, String, JobEventPayload) {
+    match error {
+        PptxError::Cancelled => (
+            JobStatus::Cancelled,
+            "导出已取消".to_string(),
+            JobEventPayload::cancelled(job_id.to_string(), "导出已取消"),
+        ),
+        e => {
+            let message = format!("{}: {}", context, e);
+            (
+                JobStatus::Failed,
+                message.clone(),
+                JobEventPayload::failed(job_id.to_string(), message),
+            )
+        }
     }
 }
 
-/// 检查后端可用性命令
+/// 校验 PPTX 文件结构命令
+///
+/// 可用于校验其他工具生成的演示文稿
 #[tauri::command]
-pub fn cmd_check_pptx_backends() -> CheckBackendsResponse {
-    let pptxgen = PptxGenSidecar::new();
-    let native = NativeOoxml::new();
+pub async fn cmd_validate_pptx(path: String) -> Result<ValidationReport, String> {
+    let path = PathBuf::from(path);
+    if !path.exists() {
+        return Err(format!("文件不存在: {:?}", path));
+    }
+
+    validate_pptx(&path).map_err(|e| format!("无法读取 PPTX: {}", e))
+}
 
-    CheckBackendsResponse {
-        pptxgen: pptxgen.is_available(),
-        native: native.is_available(),
+/// 导入 PPTX 命令
+///
+/// 将客户修改过的演示文稿同步回项目：备注写入 notes 目录，
+/// `replace_slides` 为 true 时同时替换 svg_final 中的幻灯片（原目录改名备份）
+#[tauri::command]
+pub async fn cmd_import_pptx(
+    pptx_path: String,
+    project_path: String,
+    replace_slides: Option<bool>,
+) -> Result<ImportSummary, String> {
+    let pptx_path = PathBuf::from(pptx_path);
+    if !pptx_path.exists() {
+        return Err(format!("文件不存在: {:?}", pptx_path));
     }
+
+    import_into_project(
+        &pptx_path,
+        &PathBuf::from(project_path),
+        replace_slides.unwrap_or(false),
+    )
+    .map_err(|e| format!("导入 PPTX 失败: {}", e))
 }
 
-#[derive(Debug, Serialize)]
-pub struct CheckBackendsResponse {
-    pub pptxgen: bool,
-    pub native: bool,
+/// 列出全部导出器及其可用性命令
+///
+/// 可用性检测可能启动外部进程，在阻塞线程池中执行，避免卡住界面
+#[tauri::command]
+pub async fn cmd_list_exporters(state: State<'_, AppState>) -> Result<Vec<ExporterInfo>, String> {
+    let exporters = state.exporters.clone();
+    tauri::async_runtime::spawn_blocking(move || exporters.list())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 探测 PptxGenJS sidecar 命令
+///
+/// 返回 Node.js 与 PptxGenJS 版本、支持的内容类型与切换效果；不可用时给出原因
+#[tauri::command]
+pub async fn cmd_probe_pptxgen(state: State<'_, AppState>) -> Result<SidecarProbe, String> {
+    Ok(state.pptxgen.probe().await)
+}
+
+/// 查询单个导出器的能力与选项命令
+#[tauri::command]
+pub async fn cmd_describe_exporter(
+    exporter: String,
+    state: State<'_, AppState>,
+) -> Result<ExporterInfo, String> {
+    let exporters = state.exporters.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        exporters
+            .get(&exporter)
+            .map(|entry| entry.info())
+            .ok_or_else(|| format!("未知的导出器: {}", exporter))
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
@@ -29,11 +29,18 @@ pub struct JobStatusResponse {
     pub message: Option<String>,
 }
 
-struct TauriProgressSink {
+/// 将进度转发为 `job:event` 事件（管线与导出任务共用）
+pub(crate) struct TauriProgressSink {
     window: Window,
     job_id: String,
 }
 
+impl TauriProgressSink {
+    pub(crate) fn new(window: Window, job_id: String) -> Self {
+        Self { window, job_id }
+    }
+}
+
 impl ProgressSink for TauriProgressSink {
     fn report_progress(&self, current: usize, total: usize, message: String) {
         let payload = JobEventPayload::progress(self.job_id.clone(), current, total, message);
@@ -46,6 +53,16 @@ impl ProgressSink for TauriProgressSink {
     }
 }
 
+impl pptm_pptx::ProgressSink for TauriProgressSink {
+    fn report_progress(&self, current: usize, total: usize, message: String) {
+        ProgressSink::report_progress(self, current, total, message);
+    }
+
+    fn log(&self, level: &str, message: String) {
+        ProgressSink::log(self, level, message);
+    }
+}
+
 #[tauri::command]
 pub async fn cmd_run_pipeline(
     req: RunPipelineRequest,
@@ -84,10 +101,7 @@ pub async fn cmd_run_pipeline(
             options: req.options,
         };
 
-        let sink = TauriProgressSink {
-            window: window_clone.clone(),
-            job_id: job_id_clone.clone(),
-        };
+        let sink = TauriProgressSink::new(window_clone.clone(), job_id_clone.clone());
 
         let result = app_state
             .orchestrator
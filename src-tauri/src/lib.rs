@@ -1,9 +1,11 @@
-﻿mod commands;
+mod commands;
 mod events;
 mod state;
 
+use pptm_pptx::backend::pptxgen_sidecar::{find_sidecar_script, DEFAULT_SIDECAR_PATH};
 use state::AppState;
 use std::path::PathBuf;
+use tauri::Manager;
 
 fn workspace_root() -> PathBuf {
     std::env::current_dir()
@@ -11,12 +13,29 @@ fn workspace_root() -> PathBuf {
         .join("projects")
 }
 
+/// 定位 PptxGenJS sidecar 脚本
+///
+/// 优先使用随安装包分发的资源目录，开发时回退到仓库根目录（工作目录或其上级）；
+/// 都找不到时返回资源目录下的路径，由探测报告缺失
+fn sidecar_script(resource_dir: Option<PathBuf>) -> PathBuf {
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let roots = resource_dir
+        .iter()
+        .cloned()
+        .chain([cwd.clone(), cwd.join("..")]);
+
+    find_sidecar_script(roots)
+        .unwrap_or_else(|| resource_dir.unwrap_or(cwd).join(DEFAULT_SIDECAR_PATH))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let app_state = AppState::new(workspace_root());
-
     tauri::Builder::default()
-        .manage(app_state)
+        .setup(|app| {
+            let sidecar = sidecar_script(app.path().resource_dir().ok());
+            app.manage(AppState::new(workspace_root(), sidecar));
+            Ok(())
+        })
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_shell::init())
@@ -35,7 +54,12 @@ pub fn run() {
             commands::ingest::cmd_batch_pdf_to_md,
             commands::finalize::cmd_finalize_project,
             commands::export::cmd_export_pptx,
-            commands::export::cmd_check_pptx_backends,
+            commands::export::cmd_merge_pptx,
+            commands::export::cmd_list_exporters,
+            commands::export::cmd_describe_exporter,
+            commands::export::cmd_probe_pptxgen,
+            commands::export::cmd_validate_pptx,
+            commands::export::cmd_import_pptx,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
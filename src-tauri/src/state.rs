@@ -1,4 +1,6 @@
 use pptm_pipeline::PipelineOrchestrator;
+use pptm_pptx::backend::PptxGenSidecar;
+use pptm_pptx::registry::ExporterRegistry;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -42,16 +44,23 @@ pub struct AppState {
     #[allow(dead_code)]
     pub workspace_root: PathBuf,
     pub orchestrator: PipelineOrchestrator,
+    /// 可用的导出器
+    pub exporters: Arc<ExporterRegistry>,
+    /// PptxGenJS sidecar（与 pptxgen 导出器共用同一进程）
+    pub pptxgen: PptxGenSidecar,
     job_registry: Arc<RwLock<HashMap<String, JobRecord>>>,
 }
 
 impl AppState {
-    pub fn new(workspace_root: PathBuf) -> Self {
+    pub fn new(workspace_root: PathBuf, sidecar_path: PathBuf) -> Self {
         let _ = std::fs::create_dir_all(&workspace_root);
+        let pptxgen = PptxGenSidecar::new().with_sidecar_path(sidecar_path);
 
         Self {
             workspace_root,
             orchestrator: PipelineOrchestrator::new(),
+            exporters: Arc::new(ExporterRegistry::with_pptxgen(pptxgen.clone())),
+            pptxgen,
             job_registry: Arc::new(RwLock::new(HashMap::new())),
         }
     }